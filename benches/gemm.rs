@@ -0,0 +1,78 @@
+// Timings of the blocked matrix multiplication against the previous
+// triple loop over get_element
+//
+//  cargo bench --bench gemm
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use linalg::complex::{Complex, ComplexArithmetic};
+use linalg::gemm::Op;
+use linalg::matrix::Matrix;
+
+fn naive_multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    Matrix::from_fn(a.num_rows, b.num_columns, |row, column| {
+        let mut sum = Complex::from(0.0, 0.0);
+        for index in 0..a.num_columns {
+            let product = a
+                .get_element(row, index)
+                .unwrap()
+                .multiplication(&b.get_element(index, column).unwrap());
+            sum = sum.addition(&product);
+        }
+        sum
+    })
+}
+
+fn time<G: FnMut()>(mut function: G) -> Duration {
+    // best of a few runs
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            function();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn gflops(size: usize, duration: Duration) -> f64 {
+    // a complex multiply-add takes 8 real floating point operations
+    8.0 * (size as f64).powi(3) / duration.as_secs_f64() / 1e9
+}
+
+fn main() {
+    println!("{:>6}  {:>12}  {:>12}  {:>14}  {:>8}  {:>7}", "size", "naive", "blocked", "blocked A^H B", "speedup", "GFLOP/s");
+    for size in [128, 256, 512, 1024] {
+        let a = Matrix::random_gaussian(size, size, 1);
+        let b = Matrix::random_gaussian(size, size, 2);
+
+        let naive = if size <= 512 {
+            Some(time(|| {
+                black_box(naive_multiply(black_box(&a), black_box(&b)));
+            }))
+        } else {
+            None
+        };
+        let blocked = time(|| {
+            black_box(black_box(&a).multiply(black_box(&b)).unwrap());
+        });
+        let adjoint = time(|| {
+            black_box(black_box(&a).multiply_op(Op::Adjoint, black_box(&b), Op::Normal).unwrap());
+        });
+
+        let naive_text = naive.map_or(String::from("-"), |naive| format!("{:.1?}", naive));
+        let speedup = naive.map_or(String::from("-"), |naive| {
+            format!("{:.0}x", naive.as_secs_f64() / blocked.as_secs_f64())
+        });
+        println!(
+            "{:>6}  {:>12}  {:>12}  {:>14}  {:>8}  {:>7.2}",
+            size,
+            naive_text,
+            format!("{:.1?}", blocked),
+            format!("{:.1?}", adjoint),
+            speedup,
+            gflops(size, blocked)
+        );
+    }
+}
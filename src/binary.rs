@@ -0,0 +1,377 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * Binary container for vectors and matrices, all values little endian
+ *
+ *  offset  size  content
+ *       0     8  magic "LINALGB\0"
+ *       8     2  format version
+ *      10     1  kind: 0 matrix, 1 column vector, 2 row vector
+ *      11     1  scalar type: 0 complex64, 1 complex128
+ *      12     1  layout: 0 row major, 1 column major
+ *      13     3  reserved, zero
+ *      16     8  number of rows
+ *      24     8  number of columns
+ *      32        elements as (real, imaginary) pairs
+ *     end     4  CRC-32 of everything before it
+ */
+const MAGIC: &[u8; 8] = b"LINALGB\0";
+const VERSION: u16 = 1;
+const HEADER_SIZE: usize = 32;
+// number of elements decoded per read
+const CHUNK_ELEMENTS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryScalar {
+    // pairs of f32
+    Complex64,
+    // pairs of f64
+    Complex128,
+}
+
+impl BinaryScalar {
+    fn size(&self) -> usize {
+        match self {
+            BinaryScalar::Complex64 => 8,
+            BinaryScalar::Complex128 => 16,
+        }
+    }
+
+    fn encode(&self, element: &ComplexNum, buffer: &mut Vec<u8>) {
+        match self {
+            BinaryScalar::Complex64 => {
+                buffer.extend_from_slice(&element.real.to_le_bytes());
+                buffer.extend_from_slice(&element.imaginary.to_le_bytes());
+            }
+            BinaryScalar::Complex128 => {
+                buffer.extend_from_slice(&(element.real as f64).to_le_bytes());
+                buffer.extend_from_slice(&(element.imaginary as f64).to_le_bytes());
+            }
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> ComplexNum {
+        match self {
+            BinaryScalar::Complex64 => Complex::from(
+                f32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+                f32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            ),
+            BinaryScalar::Complex128 => Complex::from(
+                f64::from_le_bytes(bytes[0..8].try_into().unwrap()) as FloatType,
+                f64::from_le_bytes(bytes[8..16].try_into().unwrap()) as FloatType,
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(io::Error),
+    // corrupt, truncated or mismatched file
+    Format(&'static str),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryError::Io(error) => write!(f, "{}", error),
+            BinaryError::Format(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for BinaryError {}
+
+impl From<io::Error> for BinaryError {
+    fn from(error: io::Error) -> Self {
+        // running out of data means the file was cut off
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            BinaryError::Format("File is truncated")
+        } else {
+            BinaryError::Io(error)
+        }
+    }
+}
+
+// CRC-32 as used by zip and png (reflected polynomial 0xEDB88320)
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 == 1 { (value >> 1) ^ 0xedb8_8320 } else { value >> 1 };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+}
+
+fn crc_update(crc: u32, bytes: &[u8]) -> u32 {
+    // crc is kept inverted between updates, start with !0 and invert at the end
+    bytes
+        .iter()
+        .fold(crc, |crc, byte| CRC_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !crc_update(!0, bytes)
+}
+
+struct CrcWriter<W: Write> {
+    inner: W,
+    crc: u32,
+}
+
+impl<W: Write> CrcWriter<W> {
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.crc = crc_update(self.crc, bytes);
+        self.inner.write_all(bytes)
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        let crc = !self.crc;
+        self.inner.write_all(&crc.to_le_bytes())?;
+        self.inner.flush()
+    }
+}
+
+struct CrcReader<R: Read> {
+    inner: R,
+    crc: u32,
+}
+
+impl<R: Read> CrcReader<R> {
+    fn read_exact(&mut self, buffer: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buffer)?;
+        self.crc = crc_update(self.crc, buffer);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), BinaryError> {
+        let mut stored = [0u8; 4];
+        self.inner.read_exact(&mut stored)?;
+        if u32::from_le_bytes(stored) != !self.crc {
+            return Err(BinaryError::Format("Checksum does not match, the file is corrupt"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Matrix,
+    ColumnVector,
+    RowVector,
+}
+
+struct Header {
+    kind: Kind,
+    scalar: BinaryScalar,
+    column_major: bool,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+fn write_container<W, I>(writer: W, header: &Header, elements: I) -> Result<(), BinaryError>
+where
+    W: Write,
+    I: Iterator<Item = ComplexNum>,
+{
+    let mut writer = CrcWriter { inner: writer, crc: !0 };
+    let mut bytes = Vec::with_capacity(HEADER_SIZE);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.push(match header.kind {
+        Kind::Matrix => 0,
+        Kind::ColumnVector => 1,
+        Kind::RowVector => 2,
+    });
+    bytes.push(match header.scalar {
+        BinaryScalar::Complex64 => 0,
+        BinaryScalar::Complex128 => 1,
+    });
+    bytes.push(header.column_major as u8);
+    bytes.extend_from_slice(&[0; 3]);
+    bytes.extend_from_slice(&(header.num_rows as u64).to_le_bytes());
+    bytes.extend_from_slice(&(header.num_cols as u64).to_le_bytes());
+    writer.write_all(&bytes)?;
+
+    let chunk_size = CHUNK_ELEMENTS * header.scalar.size();
+    let mut buffer = Vec::with_capacity(chunk_size);
+    for element in elements {
+        header.scalar.encode(&element, &mut buffer);
+        if buffer.len() >= chunk_size {
+            writer.write_all(&buffer)?;
+            buffer.clear();
+        }
+    }
+    writer.write_all(&buffer)?;
+    writer.finish()?;
+    Ok(())
+}
+
+fn read_container<R: Read>(reader: R) -> Result<(Header, Vec<ComplexNum>), BinaryError> {
+    let mut reader = CrcReader { inner: reader, crc: !0 };
+    let mut bytes = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut bytes)?;
+    if &bytes[0..8] != MAGIC {
+        return Err(BinaryError::Format("Not a linalg binary file"));
+    }
+    if u16::from_le_bytes([bytes[8], bytes[9]]) != VERSION {
+        return Err(BinaryError::Format("Unsupported format version"));
+    }
+    let kind = match bytes[10] {
+        0 => Kind::Matrix,
+        1 => Kind::ColumnVector,
+        2 => Kind::RowVector,
+        _ => return Err(BinaryError::Format("Unknown container kind")),
+    };
+    let scalar = match bytes[11] {
+        0 => BinaryScalar::Complex64,
+        1 => BinaryScalar::Complex128,
+        _ => return Err(BinaryError::Format("Unknown scalar type")),
+    };
+    let column_major = match bytes[12] {
+        0 => false,
+        1 => true,
+        _ => return Err(BinaryError::Format("Unknown layout")),
+    };
+    let num_rows = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
+    let num_cols = u64::from_le_bytes(bytes[24..32].try_into().unwrap());
+    let count = num_rows
+        .checked_mul(num_cols)
+        .and_then(|count| usize::try_from(count).ok())
+        .ok_or(BinaryError::Format("Dimensions are too large"))?;
+    if kind != Kind::Matrix && num_cols != 1 {
+        return Err(BinaryError::Format("Vectors have to be stored with a single column"));
+    }
+    let header = Header {
+        kind,
+        scalar,
+        column_major,
+        num_rows: num_rows as usize,
+        num_cols: num_cols as usize,
+    };
+
+    // the elements are read in chunks, so a corrupt size can not
+    // allocate more memory than the file provides
+    let mut elements = Vec::with_capacity(count.min(CHUNK_ELEMENTS));
+    let mut buffer = vec![0u8; CHUNK_ELEMENTS * scalar.size()];
+    let mut remaining = count;
+    while remaining > 0 {
+        let chunk = remaining.min(CHUNK_ELEMENTS);
+        let bytes = &mut buffer[..chunk * scalar.size()];
+        reader.read_exact(bytes)?;
+        elements.extend(bytes.chunks_exact(scalar.size()).map(|bytes| scalar.decode(bytes)));
+        remaining -= chunk;
+    }
+    reader.finish()?;
+
+    if header.column_major && header.kind == Kind::Matrix {
+        let (num_rows, num_cols) = (header.num_rows, header.num_cols);
+        elements = (0..count)
+            .map(|index| elements[(index % num_cols) * num_rows + index / num_cols].clone())
+            .collect();
+    }
+    Ok((header, elements))
+}
+
+impl Matrix {
+    pub fn write_binary<W: Write>(&self, writer: W, scalar: BinaryScalar) -> Result<(), BinaryError> {
+        // written in row major layout
+        let header = Header {
+            kind: Kind::Matrix,
+            scalar,
+            column_major: false,
+            num_rows: self.num_rows,
+            num_cols: self.num_columns,
+        };
+        let elements = (0..self.num_rows).flat_map(|row_index| {
+            (0..self.num_columns).map(move |column_index| self.get_element(row_index, column_index).unwrap())
+        });
+        write_container(writer, &header, elements)
+    }
+
+    pub fn read_binary<R: Read>(reader: R) -> Result<Self, BinaryError> {
+        let (header, elements) = read_container(reader)?;
+        if header.kind != Kind::Matrix {
+            return Err(BinaryError::Format("File contains a vector, not a matrix"));
+        }
+        Ok(Matrix::from_array(header.num_rows, header.num_cols, &elements).unwrap())
+    }
+
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P, scalar: BinaryScalar) -> Result<(), BinaryError> {
+        self.write_binary(BufWriter::new(File::create(path)?), scalar)
+    }
+
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, BinaryError> {
+        Matrix::read_binary(BufReader::new(File::open(path)?))
+    }
+
+    #[cfg(feature = "mmap")]
+    pub fn map_binary<P: AsRef<Path>>(path: P) -> Result<Self, BinaryError> {
+        // reads through a memory mapping instead of buffered reads;
+        // the file must not be modified while it is mapped
+        let file = File::open(path)?;
+        let mapping = unsafe { memmap2::Mmap::map(&file)? };
+        Matrix::read_binary(&mapping[..])
+    }
+}
+
+impl Vector {
+    pub fn write_binary<W: Write>(&self, writer: W, scalar: BinaryScalar) -> Result<(), BinaryError> {
+        let kind = match self.get_type() {
+            VectorType::COLUMN_VECTOR => Kind::ColumnVector,
+            VectorType::ROW_VECTOR => Kind::RowVector,
+        };
+        let header = Header {
+            kind,
+            scalar,
+            column_major: false,
+            num_rows: self.size(),
+            num_cols: 1,
+        };
+        write_container(writer, &header, self.iter())
+    }
+
+    pub fn read_binary<R: Read>(reader: R) -> Result<Self, BinaryError> {
+        let (header, elements) = read_container(reader)?;
+        match header.kind {
+            Kind::ColumnVector => Ok(Vector::from(&elements, VectorType::COLUMN_VECTOR)),
+            Kind::RowVector => Ok(Vector::from(&elements, VectorType::ROW_VECTOR)),
+            Kind::Matrix => Err(BinaryError::Format("File contains a matrix, not a vector")),
+        }
+    }
+
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P, scalar: BinaryScalar) -> Result<(), BinaryError> {
+        self.write_binary(BufWriter::new(File::create(path)?), scalar)
+    }
+
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, BinaryError> {
+        Vector::read_binary(BufReader::new(File::open(path)?))
+    }
+
+    #[cfg(feature = "mmap")]
+    pub fn map_binary<P: AsRef<Path>>(path: P) -> Result<Self, BinaryError> {
+        let file = File::open(path)?;
+        let mapping = unsafe { memmap2::Mmap::map(&file)? };
+        Vector::read_binary(&mapping[..])
+    }
+}
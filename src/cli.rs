@@ -0,0 +1,260 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::complex::*;
+use crate::csv::*;
+use crate::formats::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Non-interactive subcommands of the linalg binary
+// Results go to the file given with -o, or to stdout as CSV so that
+// they can be piped into other tools
+
+pub const USAGE: &str = "\
+Usage: linalg [COMMAND]
+
+Without a command an interactive calculator is started.
+
+Commands
+  multiply A B [-o OUT]          matrix product A B
+  kron A B [-o OUT]              kronecker product of A and B
+  solve A B [-o OUT]             solution X of A X = B
+  eig M [--hermitian] [-o OUT]   eigenvalues of M as a column
+  info M                         shape, norms, symmetry and condition of M
+  convert IN OUT                 rewrite IN in the format of OUT
+  help                           show this text
+
+Files are read and written as .mtx, .npy, .csv, .tsv, .txt or .bin
+depending on their extension.
+
+Exit codes
+  0  success
+  2  invalid command line
+  3  file could not be read or written
+  4  numerical failure, e.g. incompatible shapes or a singular matrix";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    Usage(String),
+    Io(String),
+    Numerical(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            CliError::Io(_) => 3,
+            CliError::Numerical(_) => 4,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(reason) | CliError::Io(reason) | CliError::Numerical(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+fn numerical(reason: &str) -> CliError {
+    CliError::Numerical(reason.to_string())
+}
+
+struct Arguments {
+    command: String,
+    paths: Vec<String>,
+    output: Option<String>,
+    hermitian: bool,
+}
+
+fn parse_arguments(arguments: &[String]) -> Result<Arguments, CliError> {
+    let mut parsed = Arguments {
+        command: arguments[0].clone(),
+        paths: Vec::new(),
+        output: None,
+        hermitian: false,
+    };
+    let mut iterator = arguments[1..].iter();
+    while let Some(argument) = iterator.next() {
+        match argument.as_str() {
+            "-o" | "--output" => {
+                let path = iterator
+                    .next()
+                    .ok_or(CliError::Usage(format!("{} needs a file name", argument)))?;
+                parsed.output = Some(path.clone());
+            }
+            "--hermitian" if parsed.command == "eig" => parsed.hermitian = true,
+            _ if argument.starts_with('-') && argument.len() > 1 => {
+                return Err(CliError::Usage(format!("Unknown option '{}'", argument)));
+            }
+            _ => parsed.paths.push(argument.clone()),
+        }
+    }
+    Ok(parsed)
+}
+
+fn expect_paths(arguments: &Arguments, count: usize) -> Result<(), CliError> {
+    if arguments.paths.len() != count {
+        return Err(CliError::Usage(format!(
+            "{} expects {} file{}, got {}",
+            arguments.command,
+            count,
+            if count == 1 { "" } else { "s" },
+            arguments.paths.len()
+        )));
+    }
+    Ok(())
+}
+
+fn check_format(path: &str) -> Result<(), CliError> {
+    // an unknown extension is a mistake on the command line, not in the file
+    FileFormat::from_path(path)
+        .map(|_| ())
+        .map_err(|error| CliError::Usage(format!("{}: {}", path, error)))
+}
+
+fn load(path: &str) -> Result<Matrix, CliError> {
+    check_format(path)?;
+    load_matrix(path).map_err(|error| CliError::Io(format!("{}: {}", path, error)))
+}
+
+fn emit<W: Write>(matrix: &Matrix, output: &Option<String>, stdout: &mut W) -> Result<(), CliError> {
+    match output {
+        Some(path) => save_matrix(matrix, path).map_err(|error| CliError::Io(format!("{}: {}", path, error))),
+        None => matrix
+            .write_csv(stdout, &CsvOptions::csv())
+            .map_err(|error| CliError::Io(error.to_string())),
+    }
+}
+
+fn eigenvalues(matrix: &Matrix, hermitian: bool) -> Result<Matrix, CliError> {
+    if hermitian && !matrix.is_hermitian() {
+        return Err(numerical("Matrix is not hermitian"));
+    }
+    let mut eigenvalues = matrix.calculate_eigenvalues().map_err(numerical)?;
+    if hermitian {
+        // the eigenvalues are real, so drop the rounding noise in the
+        // imaginary parts and sort them in ascending order
+        eigenvalues = eigenvalues
+            .iter()
+            .map(|eigenvalue| Complex::from(eigenvalue.real, 0.0))
+            .collect();
+        eigenvalues.sort_by(|a, b| a.real.total_cmp(&b.real));
+    }
+    Matrix::from_array(eigenvalues.len(), 1, &eigenvalues).map_err(numerical)
+}
+
+fn describe(matrix: &Matrix) -> Vec<(&'static str, String)> {
+    let yes_no = |value: bool| String::from(if value { "yes" } else { "no" });
+    let mut lines = vec![
+        ("shape", format!("{}x{}", matrix.num_rows, matrix.num_columns)),
+        ("frobenius norm", format!("{}", matrix.norm_frobenius())),
+        ("one norm", format!("{}", matrix.norm_one())),
+    ];
+    match matrix.singular_value_decomposition() {
+        Ok((_, singular_values, _)) => {
+            // singular values are sorted in descending order
+            let largest = singular_values[0];
+            let smallest = singular_values[singular_values.len() - 1];
+            lines.push(("two norm", format!("{}", largest)));
+            let condition = if smallest == 0.0 { FloatType::INFINITY } else { largest / smallest };
+            lines.push(("condition", format!("{}", condition)));
+        }
+        Err(_) => lines.push(("condition", String::from("undefined"))),
+    }
+    lines.push(("symmetric", yes_no(matrix.is_symmetric())));
+    lines.push(("hermitian", yes_no(matrix.is_hermitian())));
+    if matrix.is_quadratic() && matrix.num_rows > 0 {
+        let trace: ComplexNum = matrix.trace().unwrap();
+        lines.push(("trace", format!("{}", trace)));
+    }
+    lines
+}
+
+/**
+ * Runs the subcommand in arguments, which excludes the program name
+ * Results and reports are written to stdout
+ */
+pub fn run<W: Write>(arguments: &[String], stdout: &mut W) -> Result<(), CliError> {
+    if arguments.is_empty() {
+        return Err(CliError::Usage(String::from("Missing command")));
+    }
+    let arguments = parse_arguments(arguments)?;
+    let paths = &arguments.paths;
+    match arguments.command.as_str() {
+        "help" | "-h" | "--help" => {
+            writeln!(stdout, "{}", USAGE).map_err(|error| CliError::Io(error.to_string()))
+        }
+        "multiply" | "kron" | "solve" => {
+            expect_paths(&arguments, 2)?;
+            if let Some(path) = &arguments.output {
+                check_format(path)?;
+            }
+            let left = load(&paths[0])?;
+            let right = load(&paths[1])?;
+            let result = match arguments.command.as_str() {
+                "multiply" => left.multiply(&right),
+                "kron" => left.kronecker_product(&right),
+                _ => left.solve(&right),
+            }
+            .map_err(numerical)?;
+            emit(&result, &arguments.output, stdout)
+        }
+        "eig" => {
+            expect_paths(&arguments, 1)?;
+            if let Some(path) = &arguments.output {
+                check_format(path)?;
+            }
+            let matrix = load(&paths[0])?;
+            emit(&eigenvalues(&matrix, arguments.hermitian)?, &arguments.output, stdout)
+        }
+        "info" => {
+            expect_paths(&arguments, 1)?;
+            if arguments.output.is_some() {
+                return Err(CliError::Usage(String::from("info writes to stdout only")));
+            }
+            let matrix = load(&paths[0])?;
+            for (name, value) in describe(&matrix) {
+                writeln!(stdout, "{}: {}", name, value).map_err(|error| CliError::Io(error.to_string()))?;
+            }
+            Ok(())
+        }
+        "convert" => {
+            // the output may be given as second path or with -o
+            let mut paths = paths.clone();
+            if let Some(path) = &arguments.output {
+                paths.push(path.clone());
+            }
+            if paths.len() != 2 {
+                return Err(CliError::Usage(String::from("convert expects an input and an output file")));
+            }
+            check_format(&paths[1])?;
+            let matrix = load(&paths[0])?;
+            emit(&matrix, &Some(paths[1].clone()), stdout)
+        }
+        command => Err(CliError::Usage(format!("Unknown command '{}'", command))),
+    }
+}
+
+pub fn run_from_env() -> i32 {
+    // entry point for the binary, reports errors on stderr
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    match run(&arguments, &mut io::stdout().lock()) {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("linalg: {}", error);
+            if let CliError::Usage(_) = error {
+                eprintln!("Try 'linalg help' for more information.");
+            }
+            error.exit_code()
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use num::traits::FloatConst;
 use num::Float;
 
@@ -12,7 +14,11 @@ macro_rules! comp{
 
 pub(crate) use comp;
 
+// repr(C) fixes the layout to real part followed by imaginary part, so
+// that slices of complex numbers can be read as interleaved floats
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Complex<F: Float + FloatConst> {
     pub real: F,
     pub imaginary: F,
@@ -50,8 +56,8 @@ impl<F: Float + FloatConst> Complex<F> {
     }
 
     pub fn from_polar(norm: F, angle: F) -> Self {
-        let real = norm * F::sin(angle);
-        let imaginary = norm * F::cos(angle);
+        let real = norm * F::cos(angle);
+        let imaginary = norm * F::sin(angle);
 
         Complex {
             real,
@@ -64,7 +70,8 @@ impl<F: Float + FloatConst> Complex<F> {
     }
 
     pub fn calculate_angle(&self) -> F {
-        F::atan(self.imaginary / self.real)
+        // atan2 keeps track of the quadrant, the angle lies in (-pi, pi]
+        F::atan2(self.imaginary, self.real)
     }
 
     pub fn scale(&mut self, factor: F) {
@@ -76,6 +83,68 @@ impl<F: Float + FloatConst> Complex<F> {
         F::sqrt(F::powi(self.real, 2) + F::powi(self.imaginary, 2))
     }
 
+    pub fn exp(&self) -> Self {
+        Complex::from_polar(F::exp(self.real), self.imaginary)
+    }
+
+    pub fn ln(&self) -> Self {
+        // principal branch of the logarithm
+        Complex::from(F::ln(self.norm()), self.calculate_angle())
+    }
+
+    pub fn sqrt(&self) -> Self {
+        // principal square root, i.e. the one with non-negative real part
+        let (norm, angle) = self.to_polar();
+        let two = F::from(2.0).unwrap();
+        Complex::from_polar(F::sqrt(norm), angle / two)
+    }
+
+    pub fn sin(&self) -> Self {
+        Complex {
+            real: F::sin(self.real) * F::cosh(self.imaginary),
+            imaginary: F::cos(self.real) * F::sinh(self.imaginary),
+        }
+    }
+
+    pub fn cos(&self) -> Self {
+        Complex {
+            real: F::cos(self.real) * F::cosh(self.imaginary),
+            imaginary: -F::sin(self.real) * F::sinh(self.imaginary),
+        }
+    }
+
+}
+
+impl<F: Float + FloatConst + fmt::Display> Complex<F> {
+
+    pub(crate) fn format_with_precision(&self, precision: Option<usize>) -> String {
+        // renders a + bi or a - bi, the precision applies to both parts
+        let sign = if self.imaginary.is_sign_negative() { '-' } else { '+' };
+        let imaginary = self.imaginary.abs();
+        match precision {
+            Some(precision) => format!("{:.*} {} {:.*}i", precision, self.real, sign, precision, imaginary),
+            None => format!("{} {} {}i", self.real, sign, imaginary),
+        }
+    }
+}
+
+pub(crate) fn write_padded(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    // pads text to the width of the formatter, numbers align right by default
+    let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill().to_string();
+    write!(f, "{}{}{}", fill.repeat(before), text, fill.repeat(after))
+}
+
+impl<F: Float + FloatConst + fmt::Display> fmt::Display for Complex<F> {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_padded(f, &self.format_with_precision(f.precision()))
+    }
 }
 
 impl <F: Float + FloatConst> Clone for Complex<F> {
@@ -0,0 +1,263 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Reading and writing delimited text such as CSV and TSV files
+// Every line holds one row, blank lines are ignored and cells may be
+// enclosed in double quotes
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    Char(char),
+    // any run of spaces and tabs, written as a single space
+    Whitespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexCells {
+    // one cell per element, written like 1.5-2i
+    Combined,
+    // a real and an imaginary column per element
+    Paired,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub delimiter: Delimiter,
+    pub complex_cells: ComplexCells,
+    // lines skipped at the start of a file when reading
+    pub header_lines: usize,
+    // digits after the decimal point when writing, shortest
+    // representation if None
+    pub precision: Option<usize>,
+}
+
+impl CsvOptions {
+    pub fn csv() -> Self {
+        CsvOptions {
+            delimiter: Delimiter::Char(','),
+            complex_cells: ComplexCells::Combined,
+            header_lines: 0,
+            precision: None,
+        }
+    }
+
+    pub fn tsv() -> Self {
+        CsvOptions {
+            delimiter: Delimiter::Char('\t'),
+            ..CsvOptions::csv()
+        }
+    }
+
+    pub fn whitespace() -> Self {
+        CsvOptions {
+            delimiter: Delimiter::Whitespace,
+            ..CsvOptions::csv()
+        }
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::csv()
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    // malformed input, line numbers start at 1
+    Parse { line: usize, reason: &'static str },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(error) => write!(f, "{}", error),
+            CsvError::Parse { line, reason } => write!(f, "{} in line {}", reason, line),
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(error: io::Error) -> Self {
+        CsvError::Io(error)
+    }
+}
+
+fn split_cells(line: &str, delimiter: Delimiter) -> Vec<String> {
+    // delimiters within double quotes do not separate cells
+    let is_delimiter = |c: char| match delimiter {
+        Delimiter::Char(delimiter) => c == delimiter,
+        Delimiter::Whitespace => c == ' ' || c == '\t',
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && is_delimiter(c) {
+            cells.push(cell.trim().to_string());
+            cell.clear();
+        } else {
+            cell.push(c);
+        }
+    }
+    cells.push(cell.trim().to_string());
+    if delimiter == Delimiter::Whitespace {
+        cells.retain(|cell| !cell.is_empty());
+    }
+    cells
+}
+
+fn parse_row(cells: &[String], complex_cells: ComplexCells, line: usize) -> Result<Vec<ComplexNum>, CsvError> {
+    let error = |reason| CsvError::Parse { line, reason };
+    match complex_cells {
+        ComplexCells::Combined => cells
+            .iter()
+            .map(|cell| cell.parse::<ComplexNum>().map_err(|parse_error| error(parse_error.reason)))
+            .collect(),
+        ComplexCells::Paired => {
+            if !cells.len().is_multiple_of(2) {
+                return Err(error("Paired columns need an even number of cells"));
+            }
+            let real = |cell: &String| cell.parse::<FloatType>().map_err(|_| error("Invalid real number"));
+            cells
+                .chunks_exact(2)
+                .map(|pair| Ok(Complex::from(real(&pair[0])?, real(&pair[1])?)))
+                .collect()
+        }
+    }
+}
+
+fn read_rows<R: BufRead>(reader: R, options: &CsvOptions) -> Result<Vec<Vec<ComplexNum>>, CsvError> {
+    let mut rows: Vec<Vec<ComplexNum>> = Vec::new();
+    for (index, line) in reader.lines().enumerate().skip(options.header_lines) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = parse_row(&split_cells(&line, options.delimiter), options.complex_cells, index + 1)?;
+        if let Some(first) = rows.first() {
+            if first.len() != row.len() {
+                return Err(CsvError::Parse {
+                    line: index + 1,
+                    reason: "Row has a different number of elements than the first row",
+                });
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn format_cells(element: &ComplexNum, options: &CsvOptions) -> String {
+    let number = |value: FloatType| match options.precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => format!("{}", value),
+    };
+    match options.complex_cells {
+        ComplexCells::Combined => {
+            // no spaces, so the cell also works with whitespace delimiters
+            let sign = if element.imaginary.is_sign_negative() { '-' } else { '+' };
+            format!("{}{}{}i", number(element.real), sign, number(element.imaginary.abs()))
+        }
+        ComplexCells::Paired => {
+            let delimiter = delimiter_string(options.delimiter);
+            format!("{}{}{}", number(element.real), delimiter, number(element.imaginary))
+        }
+    }
+}
+
+fn delimiter_string(delimiter: Delimiter) -> String {
+    match delimiter {
+        Delimiter::Char(delimiter) => delimiter.to_string(),
+        Delimiter::Whitespace => String::from(" "),
+    }
+}
+
+fn write_rows<W, I>(mut writer: W, rows: I, options: &CsvOptions) -> Result<(), CsvError>
+where
+    W: Write,
+    I: Iterator<Item = Vec<ComplexNum>>,
+{
+    let delimiter = delimiter_string(options.delimiter);
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|element| format_cells(element, options)).collect();
+        writeln!(writer, "{}", cells.join(&delimiter))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+impl Matrix {
+    pub fn read_csv<R: BufRead>(reader: R, options: &CsvOptions) -> Result<Self, CsvError> {
+        let rows = read_rows(reader, options)?;
+        let num_cols = rows.first().map_or(0, |row| row.len());
+        let elements: Vec<ComplexNum> = rows.iter().flatten().cloned().collect();
+        Ok(Matrix::from_array(rows.len(), num_cols, &elements).unwrap())
+    }
+
+    pub fn write_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> Result<(), CsvError> {
+        let rows = (0..self.num_rows).map(|row_index| {
+            (0..self.num_columns)
+                .map(|column_index| self.get_element(row_index, column_index).unwrap())
+                .collect()
+        });
+        write_rows(writer, rows, options)
+    }
+
+    pub fn load_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Self, CsvError> {
+        Matrix::read_csv(BufReader::new(File::open(path)?), options)
+    }
+
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P, options: &CsvOptions) -> Result<(), CsvError> {
+        self.write_csv(BufWriter::new(File::create(path)?), options)
+    }
+}
+
+impl Vector {
+    pub fn read_csv<R: BufRead>(reader: R, options: &CsvOptions) -> Result<Self, CsvError> {
+        // a single line with several elements is a row vector,
+        // one element per line a column vector
+        let rows = read_rows(reader, options)?;
+        match rows.len() {
+            0 => Ok(Vector::from(&[], VectorType::COLUMN_VECTOR)),
+            1 if rows[0].len() > 1 => Ok(Vector::from(&rows[0], VectorType::ROW_VECTOR)),
+            _ if rows[0].len() == 1 => {
+                let elements: Vec<ComplexNum> = rows.into_iter().flatten().collect();
+                Ok(Vector::from(&elements, VectorType::COLUMN_VECTOR))
+            }
+            _ => Err(CsvError::Parse {
+                line: options.header_lines + 1,
+                reason: "Vectors need a single row or a single column",
+            }),
+        }
+    }
+
+    pub fn write_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> Result<(), CsvError> {
+        match self.get_type() {
+            VectorType::ROW_VECTOR => write_rows(writer, std::iter::once(self.iter().collect()), options),
+            VectorType::COLUMN_VECTOR => write_rows(writer, self.iter().map(|element| vec![element]), options),
+        }
+    }
+
+    pub fn load_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Self, CsvError> {
+        Vector::read_csv(BufReader::new(File::open(path)?), options)
+    }
+
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P, options: &CsvOptions) -> Result<(), CsvError> {
+        self.write_csv(BufWriter::new(File::create(path)?), options)
+    }
+}
@@ -0,0 +1,601 @@
+use crate::complex::*;
+use crate::matrix::*;
+use crate::parallel::*;
+use crate::simd;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// upper bound of QR sweeps per eigenvalue before giving up on the schur form
+const MAX_SWEEPS_PER_EIGENVALUE: usize = 30;
+
+// upper bound of jacobi sweeps before giving up on the singular values
+const MAX_JACOBI_SWEEPS: usize = 60;
+
+/**
+ * Computes a complex givens rotation (c, s) for the pair (a, b) s.t.
+ *
+ * [   c     s ] [a]   [r]
+ * [ -s^*    c ] [b] = [0]
+ *
+ * c is always real and non-negative
+ */
+pub(crate) fn givens_rotation(a: &ComplexNum, b: &ComplexNum) -> (FloatType, ComplexNum) {
+    let norm_a = a.norm();
+    let norm_b = b.norm();
+    if norm_b == 0.0 {
+        return (1.0, Complex::new());
+    }
+    if norm_a == 0.0 {
+        let mut s = b.clone();
+        s.conjugate();
+        s.scale(1.0 / norm_b);
+        return (0.0, s);
+    }
+    let norm = FloatType::hypot(norm_a, norm_b);
+    let mut b_conjugate = b.clone();
+    b_conjugate.conjugate();
+    let mut s = a.multiplication(&b_conjugate);
+    s.scale(1.0 / (norm_a * norm));
+    (norm_a / norm, s)
+}
+
+/**
+ * Applies the rotation from the left to rows first and second,
+ * restricted to the given column range
+ */
+pub(crate) fn rotate_rows(
+    matrix: &mut Matrix,
+    rotation: &(FloatType, ComplexNum),
+    first: usize,
+    second: usize,
+    columns: std::ops::Range<usize>,
+) -> Result<(), &'static str> {
+    let (c, s) = rotation;
+    let mut s_conjugate = s.clone();
+    s_conjugate.conjugate();
+    for column_index in columns {
+        let mut x = matrix.get_element(first, column_index)?;
+        let mut y = matrix.get_element(second, column_index)?;
+        let new_x = s.multiplication(&y);
+        let new_y = s_conjugate.multiplication(&x);
+        x.scale(*c);
+        y.scale(*c);
+        matrix.set_element(first, column_index, x.addition(&new_x))?;
+        matrix.set_element(second, column_index, y.subtraction(&new_y))?;
+    }
+    Ok(())
+}
+
+/**
+ * Applies the adjoint of the rotation from the right to columns
+ * first and second, restricted to the given row range
+ */
+pub(crate) fn rotate_columns(
+    matrix: &mut Matrix,
+    rotation: &(FloatType, ComplexNum),
+    first: usize,
+    second: usize,
+    rows: std::ops::Range<usize>,
+) -> Result<(), &'static str> {
+    let (c, s) = rotation;
+    let mut s_conjugate = s.clone();
+    s_conjugate.conjugate();
+    for row_index in rows {
+        let mut x = matrix.get_element(row_index, first)?;
+        let mut y = matrix.get_element(row_index, second)?;
+        let new_x = s_conjugate.multiplication(&y);
+        let new_y = s.multiplication(&x);
+        x.scale(*c);
+        y.scale(*c);
+        matrix.set_element(row_index, first, x.addition(&new_x))?;
+        matrix.set_element(row_index, second, y.subtraction(&new_y))?;
+    }
+    Ok(())
+}
+
+fn column_inner_product(first: &[ComplexNum], second: &[ComplexNum]) -> ComplexNum {
+    // x^H y of two columns
+    simd::dot_conjugate(first, second)
+}
+
+fn orthogonal_complement_vector(columns: &[Vec<ComplexNum>], size: usize) -> Result<Vec<ComplexNum>, &'static str> {
+    // orthogonalizes the unit vectors against the given orthonormal
+    // columns and returns the first one that does not vanish
+    for unit_index in 0..size {
+        let mut candidate = vec![Complex::new(); size];
+        candidate[unit_index] = Complex::from(1.0, 0.0);
+        for column in columns {
+            let projection = column_inner_product(column, &candidate);
+            for (element, basis) in candidate.iter_mut().zip(column) {
+                *element = element.subtraction(&basis.multiplication(&projection));
+            }
+        }
+        let norm = Vector::from_vec(candidate.clone()).norm_l2();
+        if norm > 0.5 {
+            candidate.iter_mut().for_each(|x| x.scale(1.0 / norm));
+            return Ok(candidate);
+        }
+    }
+    Err("No orthogonal complement left")
+}
+
+// householder vector v and factor f = 2 / ||v||^2 of P = I - f v v^H
+type Reflection = (Vec<ComplexNum>, FloatType);
+
+/**
+ * Householder reflection which maps the part x of the given column
+ * starting at start_row onto alpha * e_1, where v = x - alpha * e_1 and
+ * alpha has the norm of x and the opposite phase of its first element.
+ * Returns None if x vanishes
+ */
+fn householder_reflection(
+    matrix: &Matrix,
+    column: usize,
+    start_row: usize,
+) -> Result<Option<Reflection>, &'static str> {
+    let mut householder: Vec<ComplexNum> = Vec::new();
+    for row_index in start_row..matrix.num_rows {
+        householder.push(matrix.get_element(row_index, column)?);
+    }
+    let x_norm = Vector::from_vec(householder.clone()).norm_l2();
+    if x_norm == 0.0 {
+        return Ok(None);
+    }
+    let first_norm = householder[0].norm();
+    let mut alpha = if first_norm == 0.0 {
+        Complex::from(1.0, 0.0)
+    } else {
+        let mut phase = householder[0].clone();
+        phase.scale(1.0 / first_norm);
+        phase
+    };
+    alpha.scale(-x_norm);
+    householder[0] = householder[0].subtraction(&alpha);
+
+    let v_norm_squared = FloatType::powi(Vector::from_vec(householder.clone()).norm_l2(), 2);
+    if v_norm_squared == 0.0 {
+        return Ok(None);
+    }
+    Ok(Some((householder, 2.0 / v_norm_squared)))
+}
+
+fn reflect_rows(matrix: &mut Matrix, reflection: &Reflection, start_row: usize) -> Result<(), &'static str> {
+    // M <- P M, where P acts on the rows from start_row on; the
+    // projections f v^H M e_j of all columns are formed first, then
+    // every row is updated on its own
+    let (householder, factor) = reflection;
+    let work = householder.len() * matrix.num_columns;
+    let source: &Matrix = matrix;
+    let projections = map_collect(matrix.num_columns, work, |column_index| {
+        let mut projection = Complex::new();
+        for (offset, v) in householder.iter().enumerate() {
+            let mut v_conjugate = v.clone();
+            v_conjugate.conjugate();
+            let element = &source.row(start_row + offset)[column_index];
+            projection = projection.addition(&v_conjugate.multiplication(element));
+        }
+        projection.scale(*factor);
+        projection
+    });
+    let rows = &mut matrix.rows_mut()[start_row..start_row + householder.len()];
+    for_each_mut(rows, work, |offset, row| {
+        let v = &householder[offset];
+        simd::axpy(&Complex::from(-v.real, -v.imaginary), &projections, row.as_mut_slice());
+    });
+    Ok(())
+}
+
+fn reflect_columns(matrix: &mut Matrix, reflection: &Reflection, start_column: usize) -> Result<(), &'static str> {
+    // M <- M P, where P acts on the columns from start_column on
+    let (householder, factor) = reflection;
+    let work = householder.len() * matrix.num_rows;
+    for_each_mut(matrix.rows_mut(), work, |_, row| {
+        let row = &mut row.as_mut_slice()[start_column..start_column + householder.len()];
+        let mut projection = Complex::new();
+        for (element, v) in row.iter().zip(householder) {
+            projection = projection.addition(&element.multiplication(v));
+        }
+        projection.scale(*factor);
+        for (element, v) in row.iter_mut().zip(householder) {
+            let mut v_conjugate = v.clone();
+            v_conjugate.conjugate();
+            *element = element.subtraction(&projection.multiplication(&v_conjugate));
+        }
+    });
+    Ok(())
+}
+
+fn divide_by_pivot(numerator: &ComplexNum, pivot: &ComplexNum) -> ComplexNum {
+    // numerator / pivot for a pivot that already passed the singularity
+    // check; Complex::division rejects small but valid pivots. Smith's
+    // scaling keeps |pivot|^2 from underflowing
+    if pivot.real.abs() >= pivot.imaginary.abs() {
+        let ratio = pivot.imaginary / pivot.real;
+        let denominator = pivot.real + pivot.imaginary * ratio;
+        Complex::from(
+            (numerator.real + numerator.imaginary * ratio) / denominator,
+            (numerator.imaginary - numerator.real * ratio) / denominator,
+        )
+    } else {
+        let ratio = pivot.real / pivot.imaginary;
+        let denominator = pivot.real * ratio + pivot.imaginary;
+        Complex::from(
+            (numerator.real * ratio + numerator.imaginary) / denominator,
+            (numerator.imaginary * ratio - numerator.real) / denominator,
+        )
+    }
+}
+
+fn eliminate_below(matrix: &mut Matrix, pivot_index: usize, factors: &[ComplexNum], columns: std::ops::Range<usize>) {
+    // row_i <- row_i - factor_i pivot row for every row below the pivot
+    let work = factors.len() * columns.len();
+    let (upper, lower) = matrix.rows_mut().split_at_mut(pivot_index + 1);
+    let pivot_row = &upper[pivot_index].as_slice()[columns.clone()];
+    for_each_mut(lower, work, |offset, row| {
+        let factor = &factors[offset];
+        let negated = Complex::from(-factor.real, -factor.imaginary);
+        simd::axpy(&negated, pivot_row, &mut row.as_mut_slice()[columns.clone()]);
+    });
+}
+
+impl Matrix {
+    pub fn solve(&self, rhs: &Matrix) -> Result<Matrix, &'static str> {
+        // gaussian elimination with partial pivoting on copies of
+        // self and rhs, followed by back substitution
+        if !self.is_quadratic() {
+            return Err("Only quadratic systems of equations can be solved");
+        }
+        if self.num_rows != rhs.num_rows {
+            return Err("Number of rows of the right hand side must match the matrix");
+        }
+        let size = self.num_rows;
+        let mut lhs = self.clone();
+        let mut result = rhs.clone();
+
+        let tolerance = FloatType::EPSILON * size as FloatType * self.norm_one();
+
+        for pivot_index in 0..size {
+            // search for the largest pivot in the current column
+            let mut max_row = pivot_index;
+            let mut max_norm = lhs.get_element(pivot_index, pivot_index)?.norm();
+            for row_index in (pivot_index + 1)..size {
+                let norm = lhs.get_element(row_index, pivot_index)?.norm();
+                if norm > max_norm {
+                    max_row = row_index;
+                    max_norm = norm;
+                }
+            }
+            if max_norm <= tolerance || max_norm == 0.0 {
+                return Err("Matrix is singular");
+            }
+            lhs.swap_rows(pivot_index, max_row);
+            result.swap_rows(pivot_index, max_row);
+
+            let pivot = lhs.get_element(pivot_index, pivot_index)?;
+            let factors = ((pivot_index + 1)..size)
+                .map(|row_index| Ok(divide_by_pivot(&lhs.get_element(row_index, pivot_index)?, &pivot)))
+                .collect::<Result<Vec<_>, &'static str>>()?;
+            let num_columns = result.num_columns;
+            eliminate_below(&mut lhs, pivot_index, &factors, pivot_index..size);
+            eliminate_below(&mut result, pivot_index, &factors, 0..num_columns);
+        }
+
+        // back substitution on the upper triangular system
+        for column_index in 0..result.num_columns {
+            for row_index in (0..size).rev() {
+                let mut sum = result.get_element(row_index, column_index)?;
+                for inner_index in (row_index + 1)..size {
+                    let product = lhs
+                        .get_element(row_index, inner_index)?
+                        .multiplication(&result.get_element(inner_index, column_index)?);
+                    sum = sum.subtraction(&product);
+                }
+                let element = divide_by_pivot(&sum, &lhs.get_element(row_index, row_index)?);
+                result.set_element(row_index, column_index, element)?;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, &'static str> {
+        if !self.is_quadratic() {
+            return Err("Only quadratic matrices can be inverted");
+        }
+        self.solve(&Matrix::identity(self.num_rows))
+    }
+
+    pub fn qr_decomposition(&self) -> Result<(Matrix, Matrix), &'static str> {
+        // householder QR decomposition A = Q R of an m x n matrix with
+        // unitary m x m matrix Q and upper triangular m x n matrix R;
+        // returns (Q, R)
+        if self.num_rows == 0 || self.num_columns == 0 {
+            return Err("Matrix dimensions may not be 0");
+        }
+        let mut triangular = self.clone();
+        let mut unitary = Matrix::identity(self.num_rows);
+
+        for column in 0..usize::min(self.num_rows - 1, self.num_columns) {
+            if let Some(reflection) = householder_reflection(&triangular, column, column)? {
+                reflect_rows(&mut triangular, &reflection, column)?;
+                reflect_columns(&mut unitary, &reflection, column)?;
+            }
+            for row_index in (column + 1)..self.num_rows {
+                triangular.set_element(row_index, column, Complex::new())?;
+            }
+        }
+        Ok((unitary, triangular))
+    }
+
+    pub fn hessenberg_decomposition(&self) -> Result<(Matrix, Matrix), &'static str> {
+        // reduces self to upper hessenberg form H = Q^H A Q
+        // using householder reflections; returns (Q, H)
+        if !self.is_quadratic() {
+            return Err("Hessenberg form only exists for quadratic matrices");
+        }
+        let size = self.num_rows;
+        let mut hessenberg = self.clone();
+        let mut unitary = Matrix::identity(size);
+
+        for column in 0..size.saturating_sub(2) {
+            // reflect the part of the column below the subdiagonal
+            if let Some(reflection) = householder_reflection(&hessenberg, column, column + 1)? {
+                reflect_rows(&mut hessenberg, &reflection, column + 1)?;
+                reflect_columns(&mut hessenberg, &reflection, column + 1)?;
+                reflect_columns(&mut unitary, &reflection, column + 1)?;
+            }
+            for row_index in (column + 2)..size {
+                hessenberg.set_element(row_index, column, Complex::new())?;
+            }
+        }
+        Ok((unitary, hessenberg))
+    }
+
+    pub fn schur_decomposition(&self) -> Result<(Matrix, Matrix), &'static str> {
+        // complex schur decomposition A = Q T Q^H with unitary Q and
+        // upper triangular T; the eigenvalues of A are the diagonal of T.
+        // Uses the hessenberg form and single shift QR sweeps with
+        // wilkinson shifts; returns (Q, T)
+        if !self.is_quadratic() {
+            return Err("Schur decomposition only exists for quadratic matrices");
+        }
+        if self.num_rows == 0 {
+            return Err("Matrix dimensions may not be 0");
+        }
+        let size = self.num_rows;
+        let (mut unitary, mut triangular) = self.hessenberg_decomposition()?;
+        let matrix_norm = triangular.norm_frobenius();
+
+        let mut active_end = size - 1;
+        let mut iterations = 0;
+        let mut total_iterations = 0;
+        while active_end > 0 {
+            // find the start of the unreduced block ending at active_end
+            let mut active_start = active_end;
+            while active_start > 0 {
+                let subdiagonal = triangular.get_element(active_start, active_start - 1)?.norm();
+                let mut scale = triangular.get_element(active_start, active_start)?.norm()
+                    + triangular.get_element(active_start - 1, active_start - 1)?.norm();
+                if scale == 0.0 {
+                    scale = matrix_norm;
+                }
+                if subdiagonal <= FloatType::EPSILON * scale {
+                    triangular.set_element(active_start, active_start - 1, Complex::new())?;
+                    break;
+                }
+                active_start -= 1;
+            }
+
+            if active_start == active_end {
+                // the last eigenvalue of the block has converged
+                active_end -= 1;
+                iterations = 0;
+                continue;
+            }
+
+            iterations += 1;
+            total_iterations += 1;
+            if total_iterations > MAX_SWEEPS_PER_EIGENVALUE * size {
+                return Err("Schur decomposition did not converge");
+            }
+
+            let shift = if iterations % 10 == 0 {
+                // exceptional shift to break up cycles
+                let mut exceptional = Complex::from(
+                    triangular.get_element(active_end, active_end - 1)?.norm(),
+                    0.0,
+                );
+                exceptional.scale(0.75);
+                exceptional.addition(&triangular.get_element(active_end, active_end)?)
+            } else {
+                triangular.wilkinson_shift(active_end)?
+            };
+
+            // explicit shifted QR sweep on the active block
+            for index in active_start..=active_end {
+                let element = triangular.get_element(index, index)?.subtraction(&shift);
+                triangular.set_element(index, index, element)?;
+            }
+            let mut rotations = Vec::new();
+            for index in active_start..active_end {
+                let rotation = givens_rotation(
+                    &triangular.get_element(index, index)?,
+                    &triangular.get_element(index + 1, index)?,
+                );
+                rotate_rows(&mut triangular, &rotation, index, index + 1, index..size)?;
+                triangular.set_element(index + 1, index, Complex::new())?;
+                rotations.push(rotation);
+            }
+            for (offset, rotation) in rotations.iter().enumerate() {
+                let index = active_start + offset;
+                let row_end = usize::min(index + 2, active_end) + 1;
+                rotate_columns(&mut triangular, rotation, index, index + 1, 0..row_end)?;
+                rotate_columns(&mut unitary, rotation, index, index + 1, 0..size)?;
+            }
+            for index in active_start..=active_end {
+                let element = triangular.get_element(index, index)?.addition(&shift);
+                triangular.set_element(index, index, element)?;
+            }
+        }
+
+        for row_index in 1..size {
+            for column_index in 0..row_index {
+                triangular.set_element(row_index, column_index, Complex::new())?;
+            }
+        }
+        Ok((unitary, triangular))
+    }
+
+    pub fn singular_value_decomposition(&self) -> Result<(Matrix, Vec<FloatType>, Matrix), &'static str> {
+        // thin singular value decomposition A = U S V^H of an m x n matrix
+        // with k = min(m, n): U is m x k, V is n x k, both with orthonormal
+        // columns, and the k singular values are sorted in descending order
+        if self.num_rows == 0 || self.num_columns == 0 {
+            return Err("Matrix dimensions may not be 0");
+        }
+        if self.num_rows < self.num_columns {
+            // A^H = U S V^H  =>  A = V S U^H
+            let mut adjoint = self.clone();
+            adjoint.adjoint();
+            let (left, singular_values, right) = adjoint.singular_value_decomposition()?;
+            return Ok((right, singular_values, left));
+        }
+
+        // one-sided jacobi: rotate pairs of columns of W = A V until all
+        // columns are orthogonal, then S = column norms and U = W S^-1
+        let num_rows = self.num_rows;
+        let num_columns = self.num_columns;
+        let mut columns: Vec<Vec<ComplexNum>> = (0..num_columns)
+            .map(|column_index| {
+                (0..num_rows)
+                    .map(|row_index| self.get_element(row_index, column_index).unwrap())
+                    .collect()
+            })
+            .collect();
+        let mut right: Vec<Vec<ComplexNum>> = (0..num_columns)
+            .map(|column_index| {
+                let mut column = vec![Complex::new(); num_columns];
+                column[column_index] = Complex::from(1.0, 0.0);
+                column
+            })
+            .collect();
+
+        let mut converged = false;
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            converged = true;
+            for first in 0..num_columns {
+                for second in (first + 1)..num_columns {
+                    let alpha = column_inner_product(&columns[first], &columns[first]).real;
+                    let beta = column_inner_product(&columns[second], &columns[second]).real;
+                    let gamma = column_inner_product(&columns[first], &columns[second]);
+                    let gamma_norm = gamma.norm();
+                    let tolerance = num_rows as FloatType * FloatType::EPSILON * FloatType::sqrt(alpha * beta);
+                    if gamma_norm <= tolerance || gamma_norm == 0.0 {
+                        continue;
+                    }
+                    converged = false;
+
+                    // real rotation after removing the phase of gamma
+                    let zeta = (beta - alpha) / (2.0 * gamma_norm);
+                    let t = FloatType::signum(zeta) / (FloatType::abs(zeta) + FloatType::hypot(1.0, zeta));
+                    let c = 1.0 / FloatType::hypot(1.0, t);
+                    let s = c * t;
+                    let mut phase = gamma.clone();
+                    phase.conjugate();
+                    phase.scale(1.0 / gamma_norm);
+
+                    for target in [&mut columns, &mut right] {
+                        for index in 0..target[first].len() {
+                            let mut p = target[first][index].clone();
+                            let mut q = target[second][index].multiplication(&phase);
+                            let mut p_scaled = p.clone();
+                            let mut q_scaled = q.clone();
+                            p_scaled.scale(s);
+                            q_scaled.scale(s);
+                            p.scale(c);
+                            q.scale(c);
+                            target[first][index] = p.subtraction(&q_scaled);
+                            target[second][index] = p_scaled.addition(&q);
+                        }
+                    }
+                }
+            }
+            if converged {
+                break;
+            }
+        }
+        if !converged {
+            return Err("Singular value decomposition did not converge");
+        }
+
+        let mut order: Vec<(FloatType, usize)> = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| (Vector::from_vec(column.clone()).norm_l2(), index))
+            .collect();
+        order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+        let tolerance = FloatType::EPSILON * num_rows as FloatType * order[0].0;
+        let mut left = Matrix::zeros(num_rows, num_columns);
+        let mut right_matrix = Matrix::zeros(num_columns, num_columns);
+        let mut singular_values = Vec::with_capacity(num_columns);
+        let mut left_columns: Vec<Vec<ComplexNum>> = Vec::new();
+        for (singular_value, index) in &order {
+            if *singular_value > tolerance {
+                let mut column = columns[*index].clone();
+                column.iter_mut().for_each(|x| x.scale(1.0 / singular_value));
+                left_columns.push(column);
+                singular_values.push(*singular_value);
+            } else {
+                // complete U with an orthonormal vector of the complement
+                left_columns.push(orthogonal_complement_vector(&left_columns, num_rows)?);
+                singular_values.push(0.0);
+            }
+        }
+        for (column_index, (_, index)) in order.iter().enumerate() {
+            for (row_index, element) in left_columns[column_index].iter().enumerate() {
+                left.set_element(row_index, column_index, element.clone())?;
+            }
+            for (row_index, element) in right[*index].iter().enumerate() {
+                right_matrix.set_element(row_index, column_index, element.clone())?;
+            }
+        }
+        Ok((left, singular_values, right_matrix))
+    }
+
+    fn wilkinson_shift(&self, index: usize) -> Result<ComplexNum, &'static str> {
+        // eigenvalue of the trailing 2x2 block [a b; c d] closest to d
+        let a = self.get_element(index - 1, index - 1)?;
+        let b = self.get_element(index - 1, index)?;
+        let c = self.get_element(index, index - 1)?;
+        let d = self.get_element(index, index)?;
+
+        let mut half_difference = a.subtraction(&d);
+        half_difference.scale(0.5);
+        let discriminant = half_difference
+            .multiplication(&half_difference)
+            .addition(&b.multiplication(&c))
+            .sqrt();
+        let mut mean = a.addition(&d);
+        mean.scale(0.5);
+
+        let first = mean.addition(&discriminant);
+        let second = mean.subtraction(&discriminant);
+        if first.subtraction(&d).norm() < second.subtraction(&d).norm() {
+            Ok(first)
+        } else {
+            Ok(second)
+        }
+    }
+
+    fn swap_rows(&mut self, first: usize, second: usize) {
+        if first != second {
+            for column_index in 0..self.num_columns {
+                let x = self.get_element(first, column_index).unwrap();
+                let y = self.get_element(second, column_index).unwrap();
+                self.set_element(first, column_index, y).unwrap();
+                self.set_element(second, column_index, x).unwrap();
+            }
+        }
+    }
+}
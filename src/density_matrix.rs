@@ -0,0 +1,195 @@
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+// eigenvalues and schmidt coefficients below this bound are treated as 0
+const ZERO_TOLERANCE: FloatType = 1e-6;
+
+/**
+ * Schmidt decomposition |psi> = sum_k c_k |a_k> (x) |b_k> of a pure
+ * state of a bipartite system; only non-vanishing coefficients are kept
+ * and they are sorted in descending order
+ */
+#[derive(Debug, Clone)]
+pub struct SchmidtDecomposition {
+    pub coefficients: Vec<FloatType>,
+    pub states_a: Vec<Vector>,
+    pub states_b: Vec<Vector>,
+}
+
+pub fn density_matrix(state: &Vector) -> Result<Matrix, &'static str> {
+    // rho = |psi><psi|, the bra is the adjoint of the ket
+    let mut bra = state.clone();
+    bra.adjoint();
+    state.outer_product(&bra)
+}
+
+pub fn partial_trace(
+    density: &Matrix,
+    dimensions: &[usize],
+    traced_out: &[usize],
+) -> Result<Matrix, &'static str> {
+    // traces out the given subsystems of a density matrix on the space
+    // C^d_0 (x) C^d_1 (x) ... ; the remaining subsystems keep their order
+    if !density.is_quadratic() {
+        return Err("Density matrices have to be quadratic");
+    }
+    if dimensions.iter().product::<usize>() != density.num_rows {
+        return Err("Subsystem dimensions have to multiply to the matrix dimension");
+    }
+    for (index, subsystem) in traced_out.iter().enumerate() {
+        if *subsystem >= dimensions.len() {
+            return Err("Traced out subsystem does not exist");
+        }
+        if traced_out[..index].contains(subsystem) {
+            return Err("Traced out subsystems have to be distinct");
+        }
+    }
+
+    let kept: Vec<usize> = (0..dimensions.len())
+        .filter(|subsystem| !traced_out.contains(subsystem))
+        .collect();
+    let kept_size: usize = kept.iter().map(|subsystem| dimensions[*subsystem]).product();
+    let traced_size: usize = traced_out.iter().map(|subsystem| dimensions[*subsystem]).product();
+
+    // stride of every subsystem within the full basis index
+    let mut strides = vec![1; dimensions.len()];
+    for subsystem in (0..dimensions.len().saturating_sub(1)).rev() {
+        strides[subsystem] = strides[subsystem + 1] * dimensions[subsystem + 1];
+    }
+    let full_index = |subsystems: &[usize], mut index: usize| -> usize {
+        let mut result = 0;
+        for subsystem in subsystems.iter().rev() {
+            result += (index % dimensions[*subsystem]) * strides[*subsystem];
+            index /= dimensions[*subsystem];
+        }
+        result
+    };
+    let kept_offsets: Vec<usize> = (0..kept_size).map(|index| full_index(&kept, index)).collect();
+    let traced_offsets: Vec<usize> = (0..traced_size).map(|index| full_index(traced_out, index)).collect();
+
+    let mut result = Matrix::zeros(kept_size, kept_size);
+    for (row_index, row_offset) in kept_offsets.iter().enumerate() {
+        for (column_index, column_offset) in kept_offsets.iter().enumerate() {
+            let mut element = Complex::new();
+            for traced_offset in &traced_offsets {
+                element = element.addition(
+                    &density.get_element(row_offset + traced_offset, column_offset + traced_offset)?,
+                );
+            }
+            result.set_element(row_index, column_index, element)?;
+        }
+    }
+    Ok(result)
+}
+
+pub fn purity(density: &Matrix) -> Result<FloatType, &'static str> {
+    // tr(rho^2)
+    Ok(density.multiply(density)?.trace()?.real)
+}
+
+pub fn von_neumann_entropy(density: &Matrix) -> Result<FloatType, &'static str> {
+    // S = -tr(rho log2 rho) = -sum_k l_k log2 l_k, measured in bits
+    let entropy = hermitian_eigenvalues(density)?
+        .iter()
+        .filter(|eigenvalue| **eigenvalue > ZERO_TOLERANCE)
+        .map(|eigenvalue| -eigenvalue * FloatType::log2(*eigenvalue))
+        .sum::<FloatType>();
+    Ok(FloatType::max(entropy, 0.0))
+}
+
+pub fn fidelity(first: &Matrix, second: &Matrix) -> Result<FloatType, &'static str> {
+    // F = (tr sqrt(sqrt(rho) sigma sqrt(rho)))^2
+    if first.num_rows != second.num_rows || !first.is_quadratic() || !second.is_quadratic() {
+        return Err("Density matrices have to be quadratic and of equal dimension");
+    }
+    let root = hermitian_function(first, |x| FloatType::sqrt(FloatType::max(x, 0.0)))?;
+    let product = root.multiply(second)?.multiply(&root)?;
+    let trace: FloatType = hermitian_eigenvalues(&product)?
+        .iter()
+        .map(|eigenvalue| FloatType::sqrt(FloatType::max(*eigenvalue, 0.0)))
+        .sum();
+    Ok(trace * trace)
+}
+
+pub fn trace_distance(first: &Matrix, second: &Matrix) -> Result<FloatType, &'static str> {
+    // T = 1/2 tr|rho - sigma| = 1/2 sum_k |l_k| of the hermitian difference
+    let mut negative = second.clone();
+    negative.scale(-1.0);
+    let difference = first
+        .add(&negative)
+        .ok_or("Density matrices have to be of equal dimension")?;
+    let sum: FloatType = hermitian_eigenvalues(&difference)?
+        .iter()
+        .map(|eigenvalue| FloatType::abs(*eigenvalue))
+        .sum();
+    Ok(sum / 2.0)
+}
+
+pub fn schmidt_decomposition(
+    state: &Vector,
+    dimension_a: usize,
+    dimension_b: usize,
+) -> Result<SchmidtDecomposition, &'static str> {
+    // the state reshaped to a d_A x d_B matrix M = sum_k c_k a_k b_k^T
+    // is exactly the singular value decomposition M = U S V^H
+    // with a_k = u_k and b_k = conj(v_k)
+    if dimension_a * dimension_b != state.size() {
+        return Err("Subsystem dimensions have to multiply to the state dimension");
+    }
+    let elements: Vec<Complex<FloatType>> = state.iter().collect();
+    let reshaped = Matrix::from_array(dimension_a, dimension_b, &elements)?;
+    let (left, singular_values, right) = reshaped.singular_value_decomposition()?;
+
+    let mut decomposition = SchmidtDecomposition {
+        coefficients: Vec::new(),
+        states_a: Vec::new(),
+        states_b: Vec::new(),
+    };
+    for (index, coefficient) in singular_values.iter().enumerate() {
+        if *coefficient <= ZERO_TOLERANCE {
+            continue;
+        }
+        let state_a: Vec<Complex<FloatType>> = (0..dimension_a)
+            .map(|row_index| left.get_element(row_index, index))
+            .collect::<Result<_, _>>()?;
+        let mut state_b = Vector::from_vec(
+            (0..dimension_b)
+                .map(|row_index| right.get_element(row_index, index))
+                .collect::<Result<_, _>>()?,
+        );
+        state_b.conjugate();
+
+        decomposition.coefficients.push(*coefficient);
+        decomposition.states_a.push(Vector::from_vec(state_a));
+        decomposition.states_b.push(state_b);
+    }
+    Ok(decomposition)
+}
+
+fn hermitian_eigenvalues(matrix: &Matrix) -> Result<Vec<FloatType>, &'static str> {
+    // eigenvalues of hermitian matrices are real
+    Ok(matrix
+        .calculate_eigenvalues()?
+        .iter()
+        .map(|eigenvalue| eigenvalue.real)
+        .collect())
+}
+
+fn hermitian_function<G>(matrix: &Matrix, function: G) -> Result<Matrix, &'static str>
+where
+    G: Fn(FloatType) -> FloatType,
+{
+    // the schur form of a hermitian matrix is its real diagonal
+    // eigendecomposition A = Q D Q^H, so f(A) = Q f(D) Q^H
+    let (unitary, triangular) = matrix.schur_decomposition()?;
+    let size = matrix.num_rows;
+    let mut diagonal = Matrix::zeros(size, size);
+    for index in 0..size {
+        let eigenvalue = triangular.get_element(index, index)?.real;
+        diagonal.set_element(index, index, Complex::from(function(eigenvalue), 0.0))?;
+    }
+    let mut adjoint = unitary.clone();
+    adjoint.adjoint();
+    unitary.multiply(&diagonal)?.multiply(&adjoint)
+}
@@ -0,0 +1,885 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// largest number of elements of a matrix created by eye or zeros
+const MAX_CREATED_ELEMENTS: usize = 1 << 24;
+
+/**
+ * Expressions over scalars, vectors and matrices
+ *
+ *  expression := term (('+' | '-') term)*
+ *  term       := unary (('*' | '/') unary)*
+ *  unary      := ('-' | '+') unary | postfix
+ *  postfix    := primary "'"*
+ *  primary    := number | literal | name | name '(' arguments ')' | '(' expression ')'
+ *
+ * Numbers may carry an imaginary unit (2.5i, 1e-3j), literals use the
+ * MATLAB syntax of Matrix::from_str ([1 2; 3 4i]) and the postfix '
+ * is the adjoint. Positions are character indices into the source.
+ */
+#[derive(Debug, Clone)]
+pub enum Value {
+    Scalar(ComplexNum),
+    Vector(Vector),
+    Matrix(Matrix),
+}
+
+impl Value {
+    pub fn shape(&self) -> (usize, usize) {
+        // vectors count as n x 1 or 1 x n matrices
+        match self {
+            Value::Scalar(_) => (1, 1),
+            Value::Vector(vector) => match vector.get_type() {
+                VectorType::COLUMN_VECTOR => (vector.size(), 1),
+                VectorType::ROW_VECTOR => (1, vector.size()),
+            },
+            Value::Matrix(matrix) => (matrix.num_rows, matrix.num_columns),
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix {
+        let (num_rows, num_cols) = self.shape();
+        match self {
+            Value::Scalar(scalar) => Matrix::from_array(1, 1, std::slice::from_ref(scalar)).unwrap(),
+            Value::Vector(vector) => Matrix::from_array(num_rows, num_cols, &vector.iter().collect::<Vec<_>>()).unwrap(),
+            Value::Matrix(matrix) => matrix.clone(),
+        }
+    }
+
+    fn from_matrix(matrix: Matrix, as_vector: bool) -> Self {
+        // results of operations on vectors are vectors again if their shape allows
+        if !as_vector {
+            return Value::Matrix(matrix);
+        }
+        let elements = |matrix: &Matrix| -> Vec<ComplexNum> {
+            (0..matrix.num_rows)
+                .flat_map(|row_index| (0..matrix.num_columns).map(move |column_index| (row_index, column_index)))
+                .map(|(row_index, column_index)| matrix.get_element(row_index, column_index).unwrap())
+                .collect()
+        };
+        match (matrix.num_rows, matrix.num_columns) {
+            (1, 1) => Value::Scalar(matrix.get_element(0, 0).unwrap()),
+            (_, 1) => Value::Vector(Vector::from(&elements(&matrix), VectorType::COLUMN_VECTOR)),
+            (1, _) => Value::Vector(Vector::from(&elements(&matrix), VectorType::ROW_VECTOR)),
+            _ => Value::Matrix(matrix),
+        }
+    }
+
+    fn is_vector(&self) -> bool {
+        matches!(self, Value::Vector(_))
+    }
+
+    fn map(&self, function: impl Fn(&ComplexNum) -> ComplexNum) -> Self {
+        match self {
+            Value::Scalar(scalar) => Value::Scalar(function(scalar)),
+            Value::Vector(vector) => {
+                let elements: Vec<ComplexNum> = vector.iter().map(|element| function(&element)).collect();
+                Value::Vector(Vector::from(&elements, vector.get_type()))
+            }
+            Value::Matrix(matrix) => Value::Matrix(Matrix::from_fn(matrix.num_rows, matrix.num_columns, |row, column| {
+                function(&matrix.get_element(row, column).unwrap())
+            })),
+        }
+    }
+}
+
+impl From<ComplexNum> for Value {
+    fn from(scalar: ComplexNum) -> Self {
+        Value::Scalar(scalar)
+    }
+}
+
+impl From<Vector> for Value {
+    fn from(vector: Vector) -> Self {
+        Value::Vector(vector)
+    }
+}
+
+impl From<Matrix> for Value {
+    fn from(matrix: Matrix) -> Self {
+        Value::Matrix(matrix)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Scalar(scalar) => fmt::Display::fmt(scalar, f),
+            Value::Vector(vector) => fmt::Display::fmt(vector, f),
+            Value::Matrix(matrix) => fmt::Display::fmt(matrix, f),
+        }
+    }
+}
+
+/**
+ * Error of parsing or evaluating an expression, start and end enclose
+ * the offending sub-expression
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    pub start: usize,
+    pub end: usize,
+    pub reason: String,
+}
+
+impl ExpressionError {
+    fn new(start: usize, end: usize, reason: impl Into<String>) -> Self {
+        ExpressionError {
+            start,
+            end,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        // the source with the offending part underlined
+        let width = self.end.saturating_sub(self.start).max(1);
+        format!("{}\n{}{}\n{}", source, " ".repeat(self.start), "^".repeat(width), self.reason)
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.reason, self.start, self.end)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/**
+ * Shape of a value as far as it is known before evaluation
+ * Vectors carry their rows and columns, one of which is 1
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Scalar,
+    Vector(usize, usize),
+    Matrix(usize, usize),
+    // depends on values, e.g. the size of eye(n) for a variable n
+    Unknown,
+}
+
+impl Shape {
+    pub fn of(value: &Value) -> Self {
+        let (num_rows, num_cols) = value.shape();
+        match value {
+            Value::Scalar(_) => Shape::Scalar,
+            Value::Vector(_) => Shape::Vector(num_rows, num_cols),
+            Value::Matrix(_) => Shape::Matrix(num_rows, num_cols),
+        }
+    }
+
+    fn from_dimensions(num_rows: usize, num_cols: usize, as_vector: bool) -> Self {
+        // mirrors Value::from_matrix
+        match (num_rows, num_cols) {
+            _ if !as_vector => Shape::Matrix(num_rows, num_cols),
+            (1, 1) => Shape::Scalar,
+            (_, 1) | (1, _) => Shape::Vector(num_rows, num_cols),
+            _ => Shape::Matrix(num_rows, num_cols),
+        }
+    }
+
+    fn dimensions(&self) -> Option<(usize, usize)> {
+        match self {
+            Shape::Scalar => Some((1, 1)),
+            Shape::Vector(num_rows, num_cols) | Shape::Matrix(num_rows, num_cols) => Some((*num_rows, *num_cols)),
+            Shape::Unknown => None,
+        }
+    }
+
+    fn transposed(&self) -> Self {
+        match self {
+            Shape::Vector(num_rows, num_cols) => Shape::Vector(*num_cols, *num_rows),
+            Shape::Matrix(num_rows, num_cols) => Shape::Matrix(*num_cols, *num_rows),
+            shape => *shape,
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Shape::Scalar => write!(f, "scalar"),
+            Shape::Vector(num_rows, num_cols) => write!(f, "{}x{} vector", num_rows, num_cols),
+            Shape::Matrix(num_rows, num_cols) => write!(f, "{}x{} matrix", num_rows, num_cols),
+            Shape::Unknown => write!(f, "value of unknown shape"),
+        }
+    }
+}
+
+/**
+ * Named values that expressions can refer to
+ *
+ *  let mut bindings = Bindings::new();
+ *  bindings.bind("X", pauli_x).bind("I", Matrix::identity(2));
+ *  let hamiltonian = bindings.evaluate("kron(X, I) + 0.5 * kron(X, X)")?;
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    values: HashMap<String, Value>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Bindings::default()
+    }
+
+    pub fn bind(&mut self, name: &str, value: impl Into<Value>) -> &mut Self {
+        self.values.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.values.remove(name)
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        // sorted alphabetically
+        let mut names: Vec<&str> = self.values.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    pub fn evaluate(&self, source: &str) -> Result<Value, ExpressionError> {
+        evaluate(&parse(source)?, self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExpressionKind {
+    Constant(Value),
+    Variable(String),
+    Negate(Box<Expression>),
+    Adjoint(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Call(String, Vec<Expression>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Expression {
+    fn new(kind: ExpressionKind, start: usize, end: usize) -> Self {
+        Expression { kind, start, end }
+    }
+}
+
+// Tokenizer
+
+#[derive(Debug, Clone)]
+enum Token {
+    Number(ComplexNum),
+    Literal(String),
+    Name(String),
+    Operator(char),
+    Quote,
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize, usize)>, ExpressionError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let start = index;
+        let c = chars[index];
+        if c.is_whitespace() {
+            index += 1;
+            continue;
+        }
+        let token = if c.is_ascii_digit() || (c == '.' && chars.get(index + 1).is_some_and(char::is_ascii_digit)) {
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            if index < chars.len() && (chars[index] == 'e' || chars[index] == 'E') {
+                let mut exponent = index + 1;
+                if exponent < chars.len() && (chars[exponent] == '+' || chars[exponent] == '-') {
+                    exponent += 1;
+                }
+                if exponent < chars.len() && chars[exponent].is_ascii_digit() {
+                    index = exponent;
+                    while index < chars.len() && chars[index].is_ascii_digit() {
+                        index += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..index].iter().collect();
+            let value: FloatType = text
+                .parse()
+                .map_err(|_| ExpressionError::new(start, index, "Invalid number"))?;
+            // an imaginary unit directly after the number, unless it starts a name
+            let imaginary = index < chars.len()
+                && (chars[index] == 'i' || chars[index] == 'j')
+                && !chars.get(index + 1).is_some_and(|c| c.is_alphanumeric() || *c == '_');
+            if imaginary {
+                index += 1;
+                Token::Number(Complex::from(0.0, value))
+            } else {
+                Token::Number(Complex::from(value, 0.0))
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            Token::Name(chars[start..index].iter().collect())
+        } else if c == '[' {
+            while index < chars.len() && chars[index] != ']' {
+                index += 1;
+            }
+            if index == chars.len() {
+                return Err(ExpressionError::new(start, index, "Unclosed '['"));
+            }
+            index += 1;
+            Token::Literal(chars[start..index].iter().collect())
+        } else {
+            index += 1;
+            match c {
+                '+' | '-' | '*' | '/' => Token::Operator(c),
+                '\'' => Token::Quote,
+                '(' => Token::Open,
+                ')' => Token::Close,
+                ',' => Token::Comma,
+                _ => return Err(ExpressionError::new(start, index, format!("Unexpected character '{}'", c))),
+            }
+        };
+        tokens.push((token, start, index));
+    }
+    Ok(tokens)
+}
+
+// Parser
+
+struct Parser {
+    tokens: Vec<(Token, usize, usize)>,
+    position: usize,
+    length: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _, _)| token)
+    }
+
+    fn at(&self, expected: &Token) -> bool {
+        self.peek()
+            .is_some_and(|token| std::mem::discriminant(token) == std::mem::discriminant(expected))
+    }
+
+    fn span(&self) -> (usize, usize) {
+        // span of the next token, or the end of the source
+        self.tokens
+            .get(self.position)
+            .map_or((self.length, self.length + 1), |(_, start, end)| (*start, *end))
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> Result<usize, ExpressionError> {
+        let (start, end) = self.span();
+        if !self.at(&expected) {
+            return Err(ExpressionError::new(start, end, format!("Expected {}", description)));
+        }
+        self.position += 1;
+        Ok(end)
+    }
+
+    fn expression(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Operator(c @ ('+' | '-'))) = self.peek() {
+            let operator = if *c == '+' { Operator::Add } else { Operator::Subtract };
+            self.position += 1;
+            let rhs = self.term()?;
+            let (start, end) = (lhs.start, rhs.end);
+            lhs = Expression::new(ExpressionKind::Binary(operator, Box::new(lhs), Box::new(rhs)), start, end);
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expression, ExpressionError> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Operator(c @ ('*' | '/'))) = self.peek() {
+            let operator = if *c == '*' { Operator::Multiply } else { Operator::Divide };
+            self.position += 1;
+            let rhs = self.unary()?;
+            let (start, end) = (lhs.start, rhs.end);
+            lhs = Expression::new(ExpressionKind::Binary(operator, Box::new(lhs), Box::new(rhs)), start, end);
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ExpressionError> {
+        let (start, _) = self.span();
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.position += 1;
+                let operand = self.unary()?;
+                let end = operand.end;
+                Ok(Expression::new(ExpressionKind::Negate(Box::new(operand)), start, end))
+            }
+            Some(Token::Operator('+')) => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.postfix(),
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Expression, ExpressionError> {
+        let mut operand = self.primary()?;
+        while self.at(&Token::Quote) {
+            let (_, end) = self.span();
+            self.position += 1;
+            let start = operand.start;
+            operand = Expression::new(ExpressionKind::Adjoint(Box::new(operand)), start, end);
+        }
+        Ok(operand)
+    }
+
+    fn primary(&mut self) -> Result<Expression, ExpressionError> {
+        let (start, end) = self.span();
+        let token = match self.tokens.get(self.position) {
+            Some((token, _, _)) => token.clone(),
+            None => return Err(ExpressionError::new(start, end, "Unexpected end of expression")),
+        };
+        self.position += 1;
+        match token {
+            Token::Number(number) => Ok(Expression::new(ExpressionKind::Constant(Value::Scalar(number)), start, end)),
+            Token::Literal(text) => {
+                let value = parse_literal(&text).map_err(|error| {
+                    ExpressionError::new(start + error.position, start + error.position + 1, error.reason)
+                })?;
+                Ok(Expression::new(ExpressionKind::Constant(value), start, end))
+            }
+            Token::Name(name) => {
+                if !self.at(&Token::Open) {
+                    return Ok(Expression::new(ExpressionKind::Variable(name), start, end));
+                }
+                self.position += 1;
+                let mut arguments = Vec::new();
+                if !self.at(&Token::Close) {
+                    loop {
+                        arguments.push(self.expression()?);
+                        if !self.at(&Token::Comma) {
+                            break;
+                        }
+                        self.position += 1;
+                    }
+                }
+                let end = self.expect(Token::Close, "')' or ','")?;
+                Ok(Expression::new(ExpressionKind::Call(name, arguments), start, end))
+            }
+            Token::Open => {
+                let mut inner = self.expression()?;
+                let end = self.expect(Token::Close, "')'")?;
+                inner.start = start;
+                inner.end = end;
+                Ok(inner)
+            }
+            _ => Err(ExpressionError::new(start, end, "Expected a value")),
+        }
+    }
+}
+
+fn parse_literal(text: &str) -> Result<Value, crate::parse::ParseError> {
+    // a single row or column gives a vector, everything else a matrix
+    let matrix = Matrix::from_str(text)?;
+    if (matrix.num_rows == 1) != (matrix.num_columns == 1) {
+        Ok(Value::from_matrix(matrix, true))
+    } else {
+        Ok(Value::Matrix(matrix))
+    }
+}
+
+pub fn parse(source: &str) -> Result<Expression, ExpressionError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+        length: source.chars().count(),
+    };
+    let expression = parser.expression()?;
+    if parser.position < parser.tokens.len() {
+        let (start, end) = parser.span();
+        return Err(ExpressionError::new(start, end, "Expected an operator"));
+    }
+    Ok(expression)
+}
+
+// Shape checking
+// Runs before evaluation, so that e.g. a mismatch at the end of a long
+// formula is reported without computing its expensive parts first
+
+fn canonical_name(name: &str) -> &str {
+    match name {
+        "adj" => "adjoint",
+        "tr" => "trace",
+        name => name,
+    }
+}
+
+fn arity(name: &str) -> Option<usize> {
+    match canonical_name(name) {
+        "kron" | "solve" | "zeros" => Some(2),
+        "adjoint" | "transpose" | "conj" | "eig" | "inv" | "trace" | "norm" | "expm" | "logm" | "sqrtm" | "eye" => {
+            Some(1)
+        }
+        _ => None,
+    }
+}
+
+fn check_binary(operator: Operator, lhs: Shape, rhs: Shape) -> Result<Shape, String> {
+    if lhs == Shape::Unknown || rhs == Shape::Unknown {
+        return Ok(Shape::Unknown);
+    }
+    match operator {
+        Operator::Add | Operator::Subtract => {
+            if lhs == Shape::Scalar && rhs == Shape::Scalar {
+                return Ok(Shape::Scalar);
+            }
+            if lhs == Shape::Scalar || rhs == Shape::Scalar || lhs.dimensions() != rhs.dimensions() {
+                let verb = if operator == Operator::Add { "add" } else { "subtract" };
+                return Err(format!("Cannot {} a {} and a {}", verb, lhs, rhs));
+            }
+            let (num_rows, num_cols) = lhs.dimensions().unwrap();
+            Ok(Shape::from_dimensions(num_rows, num_cols, matches!(lhs, Shape::Vector(..))))
+        }
+        Operator::Multiply => match (lhs, rhs) {
+            (Shape::Scalar, shape) | (shape, Shape::Scalar) => Ok(shape),
+            _ => {
+                let (num_rows, inner_lhs) = lhs.dimensions().unwrap();
+                let (inner_rhs, num_cols) = rhs.dimensions().unwrap();
+                if inner_lhs != inner_rhs {
+                    return Err(format!("Cannot multiply a {} by a {}", lhs, rhs));
+                }
+                let as_vector = matches!(lhs, Shape::Vector(..)) || matches!(rhs, Shape::Vector(..));
+                Ok(Shape::from_dimensions(num_rows, num_cols, as_vector))
+            }
+        },
+        Operator::Divide => match rhs {
+            Shape::Scalar => Ok(lhs),
+            _ => Err(String::from("Only division by scalars is supported, use inv or solve")),
+        },
+    }
+}
+
+fn check_call(expression: &Expression, name: &str, arguments: &[Expression], shapes: &[Shape]) -> Result<Shape, ExpressionError> {
+    // argument errors point at the argument, all others at the call
+    let call_error = |reason: String| ExpressionError::new(expression.start, expression.end, reason);
+    let argument_error =
+        |index: usize, reason: &str| ExpressionError::new(arguments[index].start, arguments[index].end, reason);
+    let expected = arity(name).ok_or_else(|| call_error(format!("Unknown function '{}'", name)))?;
+    if arguments.len() != expected {
+        return Err(call_error(format!("{} expects {} argument(s)", name, expected)));
+    }
+    if shapes.contains(&Shape::Unknown) {
+        return Ok(match canonical_name(name) {
+            "trace" | "norm" => Shape::Scalar,
+            _ => Shape::Unknown,
+        });
+    }
+    let square_matrix = |index: usize| -> Result<usize, ExpressionError> {
+        match shapes[index] {
+            Shape::Matrix(num_rows, num_cols) if num_rows == num_cols => Ok(num_rows),
+            Shape::Matrix(..) => Err(argument_error(index, "Argument has to be a square matrix")),
+            _ => Err(argument_error(index, "Argument has to be a matrix")),
+        }
+    };
+    let size = |index: usize| -> Result<Option<usize>, ExpressionError> {
+        // known if the argument is a number written in the expression
+        if shapes[index] != Shape::Scalar {
+            return Err(argument_error(index, "Argument has to be a non negative integer"));
+        }
+        match &arguments[index].kind {
+            ExpressionKind::Constant(value) => expect_size(value).map(Some).map_err(|reason| argument_error(index, reason)),
+            _ => Ok(None),
+        }
+    };
+    match canonical_name(name) {
+        "kron" => match (shapes[0], shapes[1]) {
+            (Shape::Vector(lhs_rows, lhs_cols), Shape::Vector(rhs_rows, rhs_cols)) => {
+                if (lhs_cols == 1) != (rhs_cols == 1) {
+                    return Err(call_error(String::from(
+                        "Kronecker product of vectors requires matching vector types",
+                    )));
+                }
+                Ok(Shape::Vector(lhs_rows * rhs_rows, lhs_cols * rhs_cols))
+            }
+            (lhs, rhs) => {
+                let (lhs_rows, lhs_cols) = lhs.dimensions().unwrap();
+                let (rhs_rows, rhs_cols) = rhs.dimensions().unwrap();
+                Ok(Shape::Matrix(lhs_rows * rhs_rows, lhs_cols * rhs_cols))
+            }
+        },
+        "adjoint" | "transpose" => Ok(shapes[0].transposed()),
+        "conj" => Ok(shapes[0]),
+        "eig" => square_matrix(0).map(|size| Shape::Vector(size, 1)),
+        "solve" => {
+            let size = square_matrix(0)?;
+            let (num_rows, num_cols) = shapes[1].dimensions().unwrap();
+            if num_rows != size {
+                return Err(argument_error(1, "Number of rows of the right hand side must match the matrix"));
+            }
+            Ok(Shape::from_dimensions(num_rows, num_cols, matches!(shapes[1], Shape::Vector(..))))
+        }
+        "inv" | "expm" | "logm" | "sqrtm" => square_matrix(0).map(|size| Shape::Matrix(size, size)),
+        "trace" => square_matrix(0).map(|_| Shape::Scalar),
+        "norm" => Ok(Shape::Scalar),
+        "eye" => match size(0)? {
+            Some(size) => check_element_count(size, size)
+                .map(|_| Shape::Matrix(size, size))
+                .map_err(|reason| argument_error(0, reason)),
+            None => Ok(Shape::Unknown),
+        },
+        _ => match (size(0)?, size(1)?) {
+            (Some(num_rows), Some(num_cols)) => check_element_count(num_rows, num_cols)
+                .map(|_| Shape::Matrix(num_rows, num_cols))
+                .map_err(|reason| argument_error(1, reason)),
+            _ => Ok(Shape::Unknown),
+        },
+    }
+}
+
+pub fn check(expression: &Expression, bindings: &Bindings) -> Result<Shape, ExpressionError> {
+    let error = |reason: String| ExpressionError::new(expression.start, expression.end, reason);
+    match &expression.kind {
+        ExpressionKind::Constant(value) => Ok(Shape::of(value)),
+        ExpressionKind::Variable(name) => match bindings.get(name) {
+            Some(value) => Ok(Shape::of(value)),
+            // i and j are the imaginary unit unless they are bound
+            None if name == "i" || name == "j" => Ok(Shape::Scalar),
+            None => Err(error(format!("Unknown variable '{}'", name))),
+        },
+        ExpressionKind::Negate(operand) => check(operand, bindings),
+        ExpressionKind::Adjoint(operand) => Ok(check(operand, bindings)?.transposed()),
+        ExpressionKind::Binary(operator, lhs, rhs) => {
+            let lhs = check(lhs, bindings)?;
+            let rhs = check(rhs, bindings)?;
+            check_binary(*operator, lhs, rhs).map_err(error)
+        }
+        ExpressionKind::Call(name, arguments) => {
+            let shapes = arguments
+                .iter()
+                .map(|argument| check(argument, bindings))
+                .collect::<Result<Vec<_>, _>>()?;
+            check_call(expression, name, arguments, &shapes)
+        }
+    }
+}
+
+// Evaluation
+
+fn add(lhs: &Value, rhs: &Value, sign: FloatType) -> Result<Value, &'static str> {
+    // lhs + sign * rhs for operands of equal shape
+    if let (Value::Scalar(lhs), Value::Scalar(rhs)) = (lhs, rhs) {
+        let mut rhs = rhs.clone();
+        rhs.scale(sign);
+        return Ok(Value::Scalar(lhs.addition(&rhs)));
+    }
+    if lhs.shape() != rhs.shape() || matches!(lhs, Value::Scalar(_)) || matches!(rhs, Value::Scalar(_)) {
+        return Err("Operands have to be of equal shape");
+    }
+    let mut scaled = rhs.to_matrix();
+    scaled.scale(sign);
+    let sum = lhs.to_matrix().add(&scaled).ok_or("Operands have to be of equal shape")?;
+    Ok(Value::from_matrix(sum, lhs.is_vector()))
+}
+
+fn scale(value: &Value, factor: &ComplexNum) -> Value {
+    value.map(|element| element.multiplication(factor))
+}
+
+fn multiply(lhs: &Value, rhs: &Value) -> Result<Value, &'static str> {
+    match (lhs, rhs) {
+        (Value::Scalar(factor), value) | (value, Value::Scalar(factor)) => Ok(scale(value, factor)),
+        _ => {
+            let product = lhs.to_matrix().multiply(&rhs.to_matrix())?;
+            Ok(Value::from_matrix(product, lhs.is_vector() || rhs.is_vector()))
+        }
+    }
+}
+
+fn divide(lhs: &Value, rhs: &Value) -> Result<Value, &'static str> {
+    match rhs {
+        Value::Scalar(divisor) => {
+            let inverse = Complex::from(1.0, 0.0).division(divisor)?;
+            Ok(scale(lhs, &inverse))
+        }
+        _ => Err("Only division by scalars is supported, use inv or solve"),
+    }
+}
+
+fn adjoint(value: &Value) -> Value {
+    match value {
+        Value::Scalar(scalar) => {
+            let mut result = scalar.clone();
+            result.conjugate();
+            Value::Scalar(result)
+        }
+        Value::Vector(vector) => {
+            let mut result = vector.clone();
+            result.adjoint();
+            Value::Vector(result)
+        }
+        Value::Matrix(matrix) => {
+            let mut result = matrix.clone();
+            result.adjoint();
+            Value::Matrix(result)
+        }
+    }
+}
+
+fn conjugate(value: &Value) -> Value {
+    value.map(|element| {
+        let mut conjugated = element.clone();
+        conjugated.conjugate();
+        conjugated
+    })
+}
+
+fn expect_matrix(value: &Value) -> Result<Matrix, &'static str> {
+    match value {
+        Value::Matrix(matrix) => Ok(matrix.clone()),
+        _ => Err("Argument has to be a matrix"),
+    }
+}
+
+fn expect_size(value: &Value) -> Result<usize, &'static str> {
+    match value {
+        Value::Scalar(scalar) if scalar.imaginary == 0.0 && scalar.real >= 0.0 && scalar.real.fract() == 0.0 => {
+            Ok(scalar.real as usize)
+        }
+        _ => Err("Argument has to be a non negative integer"),
+    }
+}
+
+fn check_element_count(num_rows: usize, num_cols: usize) -> Result<(), &'static str> {
+    match num_rows.checked_mul(num_cols) {
+        Some(count) if count <= MAX_CREATED_ELEMENTS => Ok(()),
+        _ => Err("Matrix would have too many elements"),
+    }
+}
+
+fn check_created_size(arguments: &[Expression], values: &[Value]) -> Result<(), ExpressionError> {
+    // sizes of eye(n) and zeros(m, n) that were not constants during check,
+    // errors point at the argument and the count at the last one
+    let argument_error = |argument: &Expression, reason: &str| ExpressionError::new(argument.start, argument.end, reason);
+    let sizes = arguments
+        .iter()
+        .zip(values)
+        .map(|(argument, value)| expect_size(value).map_err(|reason| argument_error(argument, reason)))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_element_count(sizes[0], sizes[sizes.len() - 1])
+        .map_err(|reason| argument_error(&arguments[arguments.len() - 1], reason))
+}
+
+fn call(name: &str, arguments: &[Value]) -> Result<Value, &'static str> {
+    // names and number of arguments are validated by check
+    match canonical_name(name) {
+        "kron" => match (&arguments[0], &arguments[1]) {
+            (Value::Vector(lhs), Value::Vector(rhs)) => lhs.kron_vector(rhs).map(Value::Vector),
+            (lhs, rhs) => lhs
+                .to_matrix()
+                .kronecker_product(&rhs.to_matrix())
+                .map(|product| Value::from_matrix(product, false)),
+        },
+        "adjoint" => Ok(adjoint(&arguments[0])),
+        "transpose" => Ok(adjoint(&conjugate(&arguments[0]))),
+        "conj" => Ok(conjugate(&arguments[0])),
+        "eig" => expect_matrix(&arguments[0])
+            .and_then(|matrix| matrix.calculate_eigenvalues())
+            .map(|eigenvalues| Value::Vector(Vector::from_vec(eigenvalues))),
+        "solve" => {
+            let rhs_is_vector = arguments[1].is_vector();
+            expect_matrix(&arguments[0])
+                .and_then(|matrix| matrix.solve(&arguments[1].to_matrix()))
+                .map(|solution| Value::from_matrix(solution, rhs_is_vector))
+        }
+        "inv" => expect_matrix(&arguments[0]).and_then(|matrix| matrix.inverse()).map(Value::Matrix),
+        "trace" => expect_matrix(&arguments[0]).and_then(|matrix| matrix.trace()).map(Value::Scalar),
+        "norm" => {
+            let norm = match &arguments[0] {
+                Value::Scalar(scalar) => scalar.norm(),
+                Value::Vector(vector) => vector.norm_l2(),
+                Value::Matrix(matrix) => matrix.norm_frobenius(),
+            };
+            Ok(Value::Scalar(Complex::from(norm, 0.0)))
+        }
+        "expm" => expect_matrix(&arguments[0]).and_then(|matrix| matrix.expm()).map(Value::Matrix),
+        "logm" => expect_matrix(&arguments[0]).and_then(|matrix| matrix.logm()).map(Value::Matrix),
+        "sqrtm" => expect_matrix(&arguments[0]).and_then(|matrix| matrix.sqrtm()).map(Value::Matrix),
+        "eye" => expect_size(&arguments[0]).map(|size| Value::Matrix(Matrix::identity(size))),
+        "zeros" => {
+            let num_rows = expect_size(&arguments[0])?;
+            let num_cols = expect_size(&arguments[1])?;
+            Ok(Value::Matrix(Matrix::zeros(num_rows, num_cols)))
+        }
+        _ => Err("Unknown function"),
+    }
+}
+
+fn evaluate_checked(expression: &Expression, bindings: &Bindings) -> Result<Value, ExpressionError> {
+    let error = |reason: &str| ExpressionError::new(expression.start, expression.end, reason);
+    match &expression.kind {
+        ExpressionKind::Constant(value) => Ok(value.clone()),
+        ExpressionKind::Variable(name) => match bindings.get(name) {
+            Some(value) => Ok(value.clone()),
+            None => Ok(Value::Scalar(Complex::from(0.0, 1.0))),
+        },
+        ExpressionKind::Negate(operand) => Ok(scale(&evaluate_checked(operand, bindings)?, &Complex::from(-1.0, 0.0))),
+        ExpressionKind::Adjoint(operand) => Ok(adjoint(&evaluate_checked(operand, bindings)?)),
+        ExpressionKind::Binary(operator, lhs, rhs) => {
+            let lhs = evaluate_checked(lhs, bindings)?;
+            let rhs = evaluate_checked(rhs, bindings)?;
+            match operator {
+                Operator::Add => add(&lhs, &rhs, 1.0),
+                Operator::Subtract => add(&lhs, &rhs, -1.0),
+                Operator::Multiply => multiply(&lhs, &rhs),
+                Operator::Divide => divide(&lhs, &rhs),
+            }
+            .map_err(error)
+        }
+        ExpressionKind::Call(name, arguments) => {
+            let values = arguments
+                .iter()
+                .map(|argument| evaluate_checked(argument, bindings))
+                .collect::<Result<Vec<_>, _>>()?;
+            if matches!(canonical_name(name), "eye" | "zeros") {
+                check_created_size(arguments, &values)?;
+            }
+            call(name, &values).map_err(error)
+        }
+    }
+}
+
+pub fn evaluate(expression: &Expression, bindings: &Bindings) -> Result<Value, ExpressionError> {
+    // shapes that only become known during evaluation, e.g. of eye(n),
+    // are still caught by the operations themselves
+    check(expression, bindings)?;
+    evaluate_checked(expression, bindings)
+}
@@ -0,0 +1,289 @@
+use num::traits::FloatConst;
+use num::Float;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * Vectors and matrices with dimensions fixed at compile time
+ *
+ * SVector<T, N> and SMatrix<T, R, C> store their elements in arrays on
+ * the stack, a matrix as R rows of C elements. The dimensions are part of
+ * the type, so adding matrices of different shapes or multiplying
+ * matrices whose inner dimensions differ does not compile, and these
+ * operations return their result directly instead of a Result. Fixed
+ * size values convert into the dynamic Vector and Matrix, the other
+ * direction checks the runtime dimensions.
+ */
+pub trait Scalar: Clone + ComplexArithmetic {
+    fn zero() -> Self;
+
+    fn one() -> Self;
+}
+
+impl<F: Float + FloatConst> Scalar for Complex<F> {
+    fn zero() -> Self {
+        Complex::new()
+    }
+
+    fn one() -> Self {
+        Complex::from(F::one(), F::zero())
+    }
+}
+
+// column vector of N elements
+#[derive(Debug, Clone)]
+pub struct SVector<T: Scalar, const N: usize> {
+    elements: [T; N],
+}
+
+#[derive(Debug, Clone)]
+pub struct SMatrix<T: Scalar, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+impl<T: Scalar, const N: usize> SVector<T, N> {
+    pub fn from_array(elements: [T; N]) -> Self {
+        SVector { elements }
+    }
+
+    pub fn from_fn<G>(function: G) -> Self
+    where
+        G: Fn(usize) -> T,
+    {
+        SVector {
+            elements: std::array::from_fn(function),
+        }
+    }
+
+    pub fn zeros() -> Self {
+        SVector::from_fn(|_| T::zero())
+    }
+
+    pub fn basis(index: usize) -> Result<Self, &'static str> {
+        // unit vector e_index
+        if index >= N {
+            return Err("Index of basis vector has to be smaller than its size");
+        }
+        Ok(SVector::from_fn(|position| if position == index { T::one() } else { T::zero() }))
+    }
+
+    pub fn size(&self) -> usize {
+        N
+    }
+
+    pub fn as_array(&self) -> &[T; N] {
+        &self.elements
+    }
+
+    pub fn get_element(&self, index: usize) -> Option<&T> {
+        self.elements.get(index)
+    }
+
+    pub fn set_element(&mut self, index: usize, element: T) -> Result<(), &'static str> {
+        match self.elements.get_mut(index) {
+            Some(x) => {
+                *x = element;
+                Ok(())
+            }
+            None => Err("Trying to replace value outside of vector bounds"),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        SVector::from_fn(|index| self.elements[index].addition(&other.elements[index]))
+    }
+
+    pub fn subtract(&self, other: &Self) -> Self {
+        SVector::from_fn(|index| self.elements[index].subtraction(&other.elements[index]))
+    }
+
+    pub fn scale(&self, factor: &T) -> Self {
+        SVector::from_fn(|index| factor.multiplication(&self.elements[index]))
+    }
+
+    pub fn conjugate(&self) -> Self {
+        SVector::from_fn(|index| {
+            let mut element = self.elements[index].clone();
+            element.conjugate();
+            element
+        })
+    }
+
+    pub fn inner_product(&self, other: &Self) -> T {
+        // sum of x_k y_k without conjugation, as for Vector
+        self.elements
+            .iter()
+            .zip(&other.elements)
+            .fold(T::zero(), |sum, (x, y)| sum.addition(&x.multiplication(y)))
+    }
+
+    pub fn outer_product<const M: usize>(&self, other: &SVector<T, M>) -> SMatrix<T, N, M> {
+        SMatrix::from_fn(|row_index, column_index| {
+            self.elements[row_index].multiplication(&other.elements[column_index])
+        })
+    }
+
+    pub fn to_column(&self) -> SMatrix<T, N, 1> {
+        SMatrix::from_fn(|row_index, _| self.elements[row_index].clone())
+    }
+
+    pub fn to_row(&self) -> SMatrix<T, 1, N> {
+        SMatrix::from_fn(|_, column_index| self.elements[column_index].clone())
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        SMatrix { rows }
+    }
+
+    pub fn from_fn<G>(function: G) -> Self
+    where
+        G: Fn(usize, usize) -> T,
+    {
+        // element (i, j) of the result is function(i, j)
+        SMatrix {
+            rows: std::array::from_fn(|row_index| std::array::from_fn(|column_index| function(row_index, column_index))),
+        }
+    }
+
+    pub fn zeros() -> Self {
+        SMatrix::from_fn(|_, _| T::zero())
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    pub fn row(&self, row_index: usize) -> Option<SVector<T, C>> {
+        self.rows.get(row_index).map(|row| SVector::from_array(row.clone()))
+    }
+
+    pub fn column(&self, column_index: usize) -> Option<SVector<T, R>> {
+        if column_index >= C {
+            return None;
+        }
+        Some(SVector::from_fn(|row_index| self.rows[row_index][column_index].clone()))
+    }
+
+    pub fn get_element(&self, row_index: usize, column_index: usize) -> Option<&T> {
+        self.rows.get(row_index).and_then(|row| row.get(column_index))
+    }
+
+    pub fn set_element(&mut self, row_index: usize, column_index: usize, element: T) -> Result<(), &'static str> {
+        match self.rows.get_mut(row_index).and_then(|row| row.get_mut(column_index)) {
+            Some(x) => {
+                *x = element;
+                Ok(())
+            }
+            None => Err("Supplied indices cannot be outside of matrix boundaries"),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        SMatrix::from_fn(|i, j| self.rows[i][j].addition(&other.rows[i][j]))
+    }
+
+    pub fn subtract(&self, other: &Self) -> Self {
+        SMatrix::from_fn(|i, j| self.rows[i][j].subtraction(&other.rows[i][j]))
+    }
+
+    pub fn scale(&self, factor: &T) -> Self {
+        SMatrix::from_fn(|i, j| factor.multiplication(&self.rows[i][j]))
+    }
+
+    pub fn conjugate(&self) -> Self {
+        SMatrix::from_fn(|i, j| {
+            let mut element = self.rows[i][j].clone();
+            element.conjugate();
+            element
+        })
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R> {
+        SMatrix::from_fn(|i, j| self.rows[j][i].clone())
+    }
+
+    pub fn adjoint(&self) -> SMatrix<T, C, R> {
+        self.conjugate().transpose()
+    }
+
+    pub fn multiply<const K: usize>(&self, other: &SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+        SMatrix::from_fn(|i, j| {
+            (0..C).fold(T::zero(), |sum, index| {
+                sum.addition(&self.rows[i][index].multiplication(&other.rows[index][j]))
+            })
+        })
+    }
+
+    pub fn multiply_vector(&self, vector: &SVector<T, C>) -> SVector<T, R> {
+        SVector::from_fn(|row_index| {
+            self.rows[row_index]
+                .iter()
+                .zip(vector.as_array())
+                .fold(T::zero(), |sum, (x, y)| sum.addition(&x.multiplication(y)))
+        })
+    }
+}
+
+impl<T: Scalar, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> Self {
+        SMatrix::from_fn(|i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    pub fn trace(&self) -> T {
+        (0..N).fold(T::zero(), |sum, index| sum.addition(&self.rows[index][index]))
+    }
+}
+
+impl<const N: usize> SVector<ComplexNum, N> {
+    pub fn to_vector(&self) -> Vector {
+        // column vector with the same elements
+        Vector::from_array(&self.elements)
+    }
+}
+
+impl<const R: usize, const C: usize> SMatrix<ComplexNum, R, C> {
+    pub fn to_matrix(&self) -> Matrix {
+        Matrix::from_fn(R, C, |row_index, column_index| self.rows[row_index][column_index].clone())
+    }
+}
+
+impl<const N: usize> From<SVector<ComplexNum, N>> for Vector {
+    fn from(vector: SVector<ComplexNum, N>) -> Self {
+        vector.to_vector()
+    }
+}
+
+impl<const N: usize> TryFrom<&Vector> for SVector<ComplexNum, N> {
+    type Error = &'static str;
+
+    fn try_from(vector: &Vector) -> Result<Self, Self::Error> {
+        // row and column vectors are both accepted
+        if vector.size() != N {
+            return Err("Size of the vector has to match the fixed size");
+        }
+        Ok(SVector::from_fn(|index| vector.as_slice()[index].clone()))
+    }
+}
+
+impl<const R: usize, const C: usize> From<SMatrix<ComplexNum, R, C>> for Matrix {
+    fn from(matrix: SMatrix<ComplexNum, R, C>) -> Self {
+        matrix.to_matrix()
+    }
+}
+
+impl<const R: usize, const C: usize> TryFrom<&Matrix> for SMatrix<ComplexNum, R, C> {
+    type Error = &'static str;
+
+    fn try_from(matrix: &Matrix) -> Result<Self, Self::Error> {
+        if (matrix.num_rows, matrix.num_columns) != (R, C) {
+            return Err("Dimensions of the matrix have to match the fixed dimensions");
+        }
+        Ok(SMatrix::from_fn(|row_index, column_index| matrix.row(row_index)[column_index].clone()))
+    }
+}
@@ -0,0 +1,88 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::binary::*;
+use crate::csv::*;
+use crate::matrix::*;
+use crate::matrix_market::*;
+use crate::npy::*;
+use crate::vector::*;
+
+// Loading and saving matrices in the file format given by the extension
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    // .mtx
+    MatrixMarket,
+    // .npy
+    Npy,
+    // .csv
+    Csv,
+    // .tsv and .txt, whitespace delimited
+    Tsv,
+    // .bin, see the binary module
+    Binary,
+}
+
+impl FileFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("mtx") => Ok(FileFormat::MatrixMarket),
+            Some("npy") => Ok(FileFormat::Npy),
+            Some("csv") => Ok(FileFormat::Csv),
+            Some("tsv") | Some("txt") => Ok(FileFormat::Tsv),
+            Some("bin") => Ok(FileFormat::Binary),
+            _ => Err("Unknown file format, expected .mtx, .npy, .csv, .tsv, .txt or .bin".into()),
+        }
+    }
+}
+
+pub fn load_matrix<P: AsRef<Path>>(path: P) -> Result<Matrix, Box<dyn Error>> {
+    // files holding vectors are read as n x 1 or 1 x n matrices
+    let format = FileFormat::from_path(&path)?;
+    let vector = match format {
+        FileFormat::MatrixMarket => return Ok(Matrix::read_matrix_market_file(path)?),
+        FileFormat::Csv => return Ok(Matrix::load_csv(path, &CsvOptions::csv())?),
+        FileFormat::Tsv => return Ok(Matrix::load_csv(path, &CsvOptions::whitespace())?),
+        FileFormat::Npy => match Matrix::load_npy(&path) {
+            Ok(matrix) => return Ok(matrix),
+            Err(_) => Vector::load_npy(path)?,
+        },
+        FileFormat::Binary => match Matrix::load_binary(&path) {
+            Ok(matrix) => return Ok(matrix),
+            Err(_) => Vector::load_binary(path)?,
+        },
+    };
+    let elements: Vec<_> = vector.iter().collect();
+    let matrix = match vector.get_type() {
+        VectorType::COLUMN_VECTOR => Matrix::from_array(vector.size(), 1, &elements)?,
+        VectorType::ROW_VECTOR => Matrix::from_array(1, vector.size(), &elements)?,
+    };
+    Ok(matrix)
+}
+
+pub fn save_matrix<P: AsRef<Path>>(matrix: &Matrix, path: P) -> Result<(), Box<dyn Error>> {
+    // complex single precision wherever the format distinguishes types
+    match FileFormat::from_path(&path)? {
+        FileFormat::MatrixMarket => {
+            let header = MatrixMarketHeader::new(
+                MatrixMarketFormat::Array,
+                MatrixMarketField::Complex,
+                MatrixMarketSymmetry::General,
+            )?;
+            matrix.write_matrix_market_file(path, &header)?;
+        }
+        FileFormat::Npy => matrix.save_npy(path, NpyDtype::Complex64)?,
+        FileFormat::Csv => matrix.save_csv(path, &CsvOptions::csv())?,
+        FileFormat::Tsv => matrix.save_csv(path, &CsvOptions::tsv())?,
+        FileFormat::Binary => matrix.write_binary(BufWriter::new(File::create(path)?), BinaryScalar::Complex64)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use crate::complex::*;
+use crate::matrix::*;
+use crate::parallel::*;
+use crate::simd::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * General matrix multiplication C = alpha op(A) op(B) + beta C
+ *
+ * Follows the blocked scheme of GotoBLAS: a KC x NC panel of op(B) and an
+ * MC x KC block of op(A) are packed into contiguous buffers with separate
+ * real and imaginary parts, so that the micro kernel streams through
+ * memory that stays in cache. The micro kernel keeps an MR x NR tile of C
+ * in registers for the whole KC long inner loop. Transposes and adjoints
+ * are applied while packing and never materialized.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    // the matrix as it is
+    Normal,
+    Transpose,
+    // conjugate transpose
+    Adjoint,
+}
+
+impl Op {
+    fn dimensions(&self, matrix: &Matrix) -> (usize, usize) {
+        match self {
+            Op::Normal => (matrix.num_rows, matrix.num_columns),
+            Op::Transpose | Op::Adjoint => (matrix.num_columns, matrix.num_rows),
+        }
+    }
+
+    fn element(&self, matrix: &Matrix, row_index: usize, column_index: usize) -> (FloatType, FloatType) {
+        // element (row_index, column_index) of op(matrix) as real and imaginary part
+        match self {
+            Op::Normal => {
+                let element = &matrix.row(row_index)[column_index];
+                (element.real, element.imaginary)
+            }
+            Op::Transpose => {
+                let element = &matrix.row(column_index)[row_index];
+                (element.real, element.imaginary)
+            }
+            Op::Adjoint => {
+                let element = &matrix.row(column_index)[row_index];
+                (element.real, -element.imaginary)
+            }
+        }
+    }
+}
+
+// tile of C held in registers by the micro kernel
+pub(crate) const MR: usize = 4;
+pub(crate) const NR: usize = 8;
+// block sizes for the packed panels of A (MC x KC) and B (KC x NC)
+const KC: usize = 256;
+const MC: usize = 128;
+const NC: usize = 2048;
+
+#[derive(Debug, Default)]
+struct Panel {
+    real: Vec<FloatType>,
+    imaginary: Vec<FloatType>,
+    // real + imaginary part, only filled for the 3M kernel
+    sum: Vec<FloatType>,
+}
+
+impl Panel {
+    fn with_capacity(capacity: usize, three_m: bool) -> Self {
+        Panel {
+            real: vec![0.0; capacity],
+            imaginary: vec![0.0; capacity],
+            sum: vec![0.0; if three_m { capacity } else { 0 }],
+        }
+    }
+
+    fn set(&mut self, index: usize, (real, imaginary): (FloatType, FloatType)) {
+        self.real[index] = real;
+        self.imaginary[index] = imaginary;
+        if !self.sum.is_empty() {
+            self.sum[index] = real + imaginary;
+        }
+    }
+
+    fn sliver(&self, range: std::ops::Range<usize>) -> (&[FloatType], &[FloatType], &[FloatType]) {
+        let sum = if self.sum.is_empty() { &self.sum[..] } else { &self.sum[range.clone()] };
+        (&self.real[range.clone()], &self.imaginary[range], sum)
+    }
+}
+
+fn pack_a(panel: &mut Panel, a: &Matrix, op: Op, rows: (usize, usize), depth: (usize, usize)) {
+    // slivers of MR rows, stored column by column, padded with zeros
+    let (row_start, row_count) = rows;
+    let (depth_start, depth_count) = depth;
+    for sliver in 0..row_count.div_ceil(MR) {
+        let offset = sliver * MR * depth_count;
+        for i in 0..MR {
+            let row_index = sliver * MR + i;
+            for p in 0..depth_count {
+                let element = if row_index < row_count {
+                    op.element(a, row_start + row_index, depth_start + p)
+                } else {
+                    (0.0, 0.0)
+                };
+                panel.set(offset + p * MR + i, element);
+            }
+        }
+    }
+}
+
+fn pack_b(panel: &mut Panel, b: &Matrix, op: Op, depth: (usize, usize), columns: (usize, usize)) {
+    // slivers of NR columns, stored row by row, padded with zeros
+    let (depth_start, depth_count) = depth;
+    let (column_start, column_count) = columns;
+    for sliver in 0..column_count.div_ceil(NR) {
+        let offset = sliver * NR * depth_count;
+        for p in 0..depth_count {
+            for j in 0..NR {
+                let column_index = sliver * NR + j;
+                let element = if column_index < column_count {
+                    op.element(b, depth_start + p, column_start + column_index)
+                } else {
+                    (0.0, 0.0)
+                };
+                panel.set(offset + p * NR + j, element);
+            }
+        }
+    }
+}
+
+pub(crate) type Tile = [[FloatType; NR]; MR];
+
+fn scale_rows(c: &mut Matrix, beta: &ComplexNum) {
+    // beta = 0 overwrites C, so that NaNs in it do not propagate
+    if beta.real == 1.0 && beta.imaginary == 0.0 {
+        return;
+    }
+    let overwrite = beta.real == 0.0 && beta.imaginary == 0.0;
+    for row_index in 0..c.num_rows {
+        let row = c.row_mut(row_index);
+        if overwrite {
+            row.iter_mut().for_each(|element| *element = Complex::new());
+        } else {
+            scale(beta, row);
+        }
+    }
+}
+
+fn check_dimensions(a: (usize, usize), b: (usize, usize), c: (usize, usize)) -> Result<(), &'static str> {
+    if a.1 != b.0 {
+        return Err("Number of columns of the first matrix must match the number of rows of the second matrix");
+    }
+    if c != (a.0, b.1) {
+        return Err("Dimensions of the result matrix must match the product");
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn macro_kernel(
+    (level, three_m): (SimdLevel, bool),
+    alpha: &ComplexNum,
+    a_panel: &Panel,
+    b_panel: &Panel,
+    rows: &mut [Vector],
+    column_start: usize,
+    column_count: usize,
+    depth_count: usize,
+) {
+    // rows += alpha A B for the packed block of A and panel of B, where
+    // rows are the rows of C covered by the block of A
+    let row_count = rows.len();
+    for column_sliver in 0..column_count.div_ceil(NR) {
+        let b_range = column_sliver * NR * depth_count..(column_sliver + 1) * NR * depth_count;
+        for row_sliver in 0..row_count.div_ceil(MR) {
+            let a_range = row_sliver * MR * depth_count..(row_sliver + 1) * MR * depth_count;
+            let (a_sliver, b_sliver) = (a_panel.sliver(a_range), b_panel.sliver(b_range.clone()));
+            let (real, imaginary) = if three_m {
+                micro_kernel_3m(level, a_sliver, b_sliver)
+            } else {
+                micro_kernel(level, a_sliver.0, a_sliver.1, b_sliver.0, b_sliver.1)
+            };
+
+            // add the valid part of the tile to C
+            let row_offset = row_sliver * MR;
+            let column_offset = column_start + column_sliver * NR;
+            let tile_rows = MR.min(row_count - row_offset);
+            let tile_columns = NR.min(column_start + column_count - column_offset);
+            for i in 0..tile_rows {
+                let row = &mut rows[row_offset + i].as_mut_slice()[column_offset..column_offset + tile_columns];
+                for (j, element) in row.iter_mut().enumerate() {
+                    let product = Complex::from(real[i][j], imaginary[i][j]).multiplication(alpha);
+                    element.real += product.real;
+                    element.imaginary += product.imaginary;
+                }
+            }
+        }
+    }
+}
+
+fn gemm(alpha: &ComplexNum, a: &Matrix, op_a: Op, b: &Matrix, op_b: Op, c: &mut Matrix) {
+    // C += alpha op(A) op(B), dimensions are checked by the caller
+    blocked_gemm(alpha, a, op_a, b, op_b, c, false);
+}
+
+pub(crate) fn blocked_gemm(alpha: &ComplexNum, a: &Matrix, op_a: Op, b: &Matrix, op_b: Op, c: &mut Matrix, three_m: bool) {
+    // three_m selects the kernel with three real products per complex one
+    let (m, k) = op_a.dimensions(a);
+    let n = op_b.dimensions(b).1;
+    if m == 0 || n == 0 || k == 0 {
+        return;
+    }
+    let level = SimdLevel::detect();
+    let mut b_panel = Panel::with_capacity(NC.min(n).div_ceil(NR) * NR * KC.min(k), three_m);
+
+    for column_start in (0..n).step_by(NC) {
+        let column_count = NC.min(n - column_start);
+        for depth_start in (0..k).step_by(KC) {
+            let depth_count = KC.min(k - depth_start);
+            pack_b(&mut b_panel, b, op_b, (depth_start, depth_count), (column_start, column_count));
+
+            // the MC row blocks of C are independent, each packs its own block of A
+            let work = m * column_count * depth_count;
+            for_each_chunk_mut(c.rows_mut(), MC, work, |row_start, rows| {
+                let mut a_panel = Panel::with_capacity(rows.len().div_ceil(MR) * MR * depth_count, three_m);
+                pack_a(&mut a_panel, a, op_a, (row_start, rows.len()), (depth_start, depth_count));
+                macro_kernel((level, three_m), alpha, &a_panel, &b_panel, rows, column_start, column_count, depth_count);
+            });
+        }
+    }
+}
+
+impl Matrix {
+    pub fn gemm(
+        &mut self,
+        alpha: ComplexNum,
+        a: &Matrix,
+        op_a: Op,
+        b: &Matrix,
+        op_b: Op,
+        beta: ComplexNum,
+    ) -> Result<(), &'static str> {
+        // self = alpha op(A) op(B) + beta self
+        check_dimensions(op_a.dimensions(a), op_b.dimensions(b), (self.num_rows, self.num_columns))?;
+        scale_rows(self, &beta);
+        gemm(&alpha, a, op_a, b, op_b, self);
+        Ok(())
+    }
+
+    pub fn multiply_op(&self, op_self: Op, other: &Matrix, op_other: Op) -> Result<Matrix, &'static str> {
+        // op(self) op(other) without forming the transposes
+        let (num_rows, inner) = op_self.dimensions(self);
+        let (other_rows, num_cols) = op_other.dimensions(other);
+        check_dimensions((num_rows, inner), (other_rows, num_cols), (num_rows, num_cols))?;
+        let mut result = Matrix::zeros(num_rows, num_cols);
+        gemm(&Complex::from(1.0, 0.0), self, op_self, other, op_other, &mut result);
+        Ok(result)
+    }
+}
@@ -0,0 +1,388 @@
+use crate::complex::*;
+use crate::matrix::*;
+use crate::parallel::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * Lazy elementwise expressions on vectors and matrices
+ *
+ * Combining expressions only records the operation in a tree of small
+ * structs, the operands are borrowed and nothing is computed. Evaluating
+ * the tree computes every element of the result in one pass over the
+ * destination, so a.lazy().scale(2.0).add(b.lazy())?.subtract(c.lazy())?
+ * needs no temporaries. Shapes are checked when the tree is built, a
+ * tree that exists can always be evaluated into a destination of its
+ * shape.
+ *
+ * The borrows of the operands keep the destination from being one of
+ * them, so in place updates go through evaluate and an assignment.
+ */
+pub trait VectorExpression: Sized {
+    fn size(&self) -> usize;
+
+    fn vector_type(&self) -> VectorType;
+
+    // element at index, only called with index < size
+    fn element(&self, index: usize) -> ComplexNum;
+
+    fn add<R: VectorExpression>(self, other: R) -> Result<Sum<Self, R>, &'static str> {
+        check_vector_shapes(&self, &other)?;
+        Ok(Sum(self, other))
+    }
+
+    fn subtract<R: VectorExpression>(self, other: R) -> Result<Difference<Self, R>, &'static str> {
+        check_vector_shapes(&self, &other)?;
+        Ok(Difference(self, other))
+    }
+
+    fn scale(self, factor: FloatType) -> Scaled<Self> {
+        Scaled(self, factor)
+    }
+
+    fn conjugate(self) -> Conjugated<Self> {
+        Conjugated(self)
+    }
+
+    fn transpose(self) -> Transposed<Self> {
+        Transposed(self)
+    }
+
+    fn adjoint(self) -> Conjugated<Transposed<Self>> {
+        Conjugated(Transposed(self))
+    }
+
+    fn map<G: Fn(ComplexNum) -> ComplexNum>(self, function: G) -> Mapped<Self, G> {
+        Mapped(self, function)
+    }
+
+    fn evaluate(&self) -> Vector
+    where
+        Self: Sync,
+    {
+        let mut result = Vector::zeros(self.size());
+        if self.vector_type() == VectorType::ROW_VECTOR {
+            result.transpose();
+        }
+        self.evaluate_into(&mut result).unwrap();
+        result
+    }
+
+    fn evaluate_into(&self, destination: &mut Vector) -> Result<(), &'static str>
+    where
+        Self: Sync,
+    {
+        // destination <- self in one pass
+        if destination.size() != self.size() || destination.get_type() != self.vector_type() {
+            return Err("Destination has to have the size and type of the expression");
+        }
+        for_each_mut(destination.as_mut_slice(), self.size(), |index, x| *x = self.element(index));
+        Ok(())
+    }
+}
+
+pub trait MatrixExpression: Sized {
+    // number of rows and columns
+    fn dimensions(&self) -> (usize, usize);
+
+    // element at (row_index, column_index), only called within the dimensions
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum;
+
+    fn add<R: MatrixExpression>(self, other: R) -> Result<Sum<Self, R>, &'static str> {
+        check_matrix_shapes(&self, &other)?;
+        Ok(Sum(self, other))
+    }
+
+    fn subtract<R: MatrixExpression>(self, other: R) -> Result<Difference<Self, R>, &'static str> {
+        check_matrix_shapes(&self, &other)?;
+        Ok(Difference(self, other))
+    }
+
+    fn scale(self, factor: FloatType) -> Scaled<Self> {
+        Scaled(self, factor)
+    }
+
+    fn conjugate(self) -> Conjugated<Self> {
+        Conjugated(self)
+    }
+
+    fn transpose(self) -> Transposed<Self> {
+        Transposed(self)
+    }
+
+    fn adjoint(self) -> Conjugated<Transposed<Self>> {
+        Conjugated(Transposed(self))
+    }
+
+    fn map<G: Fn(ComplexNum) -> ComplexNum>(self, function: G) -> Mapped<Self, G> {
+        Mapped(self, function)
+    }
+
+    fn evaluate(&self) -> Matrix
+    where
+        Self: Sync,
+    {
+        let (num_rows, num_columns) = self.dimensions();
+        let mut result = Matrix::zeros(num_rows, num_columns);
+        self.evaluate_into(&mut result).unwrap();
+        result
+    }
+
+    fn evaluate_into(&self, destination: &mut Matrix) -> Result<(), &'static str>
+    where
+        Self: Sync,
+    {
+        // destination <- self in one pass, row by row
+        let (num_rows, num_columns) = self.dimensions();
+        if (destination.num_rows, destination.num_columns) != (num_rows, num_columns) {
+            return Err("Destination has to have the dimensions of the expression");
+        }
+        for_each_mut(destination.rows_mut(), num_rows * num_columns, |row_index, row| {
+            for (column_index, x) in row.as_mut_slice().iter_mut().enumerate() {
+                *x = self.element(row_index, column_index);
+            }
+        });
+        Ok(())
+    }
+}
+
+fn check_vector_shapes<L: VectorExpression, R: VectorExpression>(first: &L, second: &R) -> Result<(), &'static str> {
+    if first.vector_type() != second.vector_type() {
+        Err("Combining vector expressions of differing types is not possible")
+    } else if first.size() != second.size() {
+        Err("Combining vector expressions of differing sizes is not possible")
+    } else {
+        Ok(())
+    }
+}
+
+fn check_matrix_shapes<L: MatrixExpression, R: MatrixExpression>(first: &L, second: &R) -> Result<(), &'static str> {
+    if first.dimensions() != second.dimensions() {
+        Err("Dimensions of the matrix expressions have to match")
+    } else {
+        Ok(())
+    }
+}
+
+// leaf of an expression tree, borrows a vector or a matrix
+#[derive(Debug, Clone, Copy)]
+pub struct Operand<'a, T>(&'a T);
+
+// elementwise first + second
+#[derive(Debug, Clone, Copy)]
+pub struct Sum<L, R>(L, R);
+
+// elementwise first - second
+#[derive(Debug, Clone, Copy)]
+pub struct Difference<L, R>(L, R);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Scaled<E>(E, FloatType);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Conjugated<E>(E);
+
+// flips the type of a vector, swaps rows and columns of a matrix
+#[derive(Debug, Clone, Copy)]
+pub struct Transposed<E>(E);
+
+// function applied to every element
+#[derive(Debug, Clone, Copy)]
+pub struct Mapped<E, G>(E, G);
+
+impl Vector {
+    pub fn lazy(&self) -> Operand<'_, Vector> {
+        Operand(self)
+    }
+}
+
+impl Matrix {
+    pub fn lazy(&self) -> Operand<'_, Matrix> {
+        Operand(self)
+    }
+}
+
+impl VectorExpression for Operand<'_, Vector> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.get_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        self.0.as_slice()[index].clone()
+    }
+}
+
+impl MatrixExpression for Operand<'_, Matrix> {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.0.num_rows, self.0.num_columns)
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        self.0.row(row_index)[column_index].clone()
+    }
+}
+
+impl<L: VectorExpression, R: VectorExpression> VectorExpression for Sum<L, R> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.vector_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        self.0.element(index).addition(&self.1.element(index))
+    }
+}
+
+impl<L: MatrixExpression, R: MatrixExpression> MatrixExpression for Sum<L, R> {
+    fn dimensions(&self) -> (usize, usize) {
+        self.0.dimensions()
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        self.0
+            .element(row_index, column_index)
+            .addition(&self.1.element(row_index, column_index))
+    }
+}
+
+impl<L: VectorExpression, R: VectorExpression> VectorExpression for Difference<L, R> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.vector_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        self.0.element(index).subtraction(&self.1.element(index))
+    }
+}
+
+impl<L: MatrixExpression, R: MatrixExpression> MatrixExpression for Difference<L, R> {
+    fn dimensions(&self) -> (usize, usize) {
+        self.0.dimensions()
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        self.0
+            .element(row_index, column_index)
+            .subtraction(&self.1.element(row_index, column_index))
+    }
+}
+
+impl<E: VectorExpression> VectorExpression for Scaled<E> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.vector_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        let mut element = self.0.element(index);
+        element.scale(self.1);
+        element
+    }
+}
+
+impl<E: MatrixExpression> MatrixExpression for Scaled<E> {
+    fn dimensions(&self) -> (usize, usize) {
+        self.0.dimensions()
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        let mut element = self.0.element(row_index, column_index);
+        element.scale(self.1);
+        element
+    }
+}
+
+impl<E: VectorExpression> VectorExpression for Conjugated<E> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.vector_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        let mut element = self.0.element(index);
+        element.conjugate();
+        element
+    }
+}
+
+impl<E: MatrixExpression> MatrixExpression for Conjugated<E> {
+    fn dimensions(&self) -> (usize, usize) {
+        self.0.dimensions()
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        let mut element = self.0.element(row_index, column_index);
+        element.conjugate();
+        element
+    }
+}
+
+impl<E: VectorExpression> VectorExpression for Transposed<E> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        match self.0.vector_type() {
+            VectorType::ROW_VECTOR => VectorType::COLUMN_VECTOR,
+            VectorType::COLUMN_VECTOR => VectorType::ROW_VECTOR,
+        }
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        self.0.element(index)
+    }
+}
+
+impl<E: MatrixExpression> MatrixExpression for Transposed<E> {
+    fn dimensions(&self) -> (usize, usize) {
+        let (num_rows, num_columns) = self.0.dimensions();
+        (num_columns, num_rows)
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        self.0.element(column_index, row_index)
+    }
+}
+
+impl<E: VectorExpression, G: Fn(ComplexNum) -> ComplexNum> VectorExpression for Mapped<E, G> {
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn vector_type(&self) -> VectorType {
+        self.0.vector_type()
+    }
+
+    fn element(&self, index: usize) -> ComplexNum {
+        (self.1)(self.0.element(index))
+    }
+}
+
+impl<E: MatrixExpression, G: Fn(ComplexNum) -> ComplexNum> MatrixExpression for Mapped<E, G> {
+    fn dimensions(&self) -> (usize, usize) {
+        self.0.dimensions()
+    }
+
+    fn element(&self, row_index: usize, column_index: usize) -> ComplexNum {
+        (self.1)(self.0.element(row_index, column_index))
+    }
+}
@@ -0,0 +1,27 @@
+pub mod vector;
+pub mod complex;
+pub mod matrix;
+pub mod gemm;
+pub mod strassen;
+pub mod lazy;
+pub mod fixed;
+mod parallel;
+pub mod simd;
+pub mod decomposition;
+pub mod matrix_functions;
+pub mod quantum;
+pub mod density_matrix;
+pub mod random;
+pub mod parse;
+pub mod matrix_market;
+pub mod npy;
+pub mod binary;
+pub mod csv;
+pub mod markup;
+#[cfg(feature = "serde")]
+mod serde_support;
+pub mod expression;
+pub mod formats;
+pub mod repl;
+pub mod cli;
+mod test;
@@ -1,11 +1,54 @@
-// Get cargo to look for  modules
-mod vector;
-mod complex;
-mod matrix;
-mod test;
+use std::env;
+use std::path::PathBuf;
+use std::process;
 
-use crate::vector::*;
+use linalg::cli;
+use linalg::repl::Session;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
 
-fn main() {
-    println!("test");
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".linalg_history"))
+}
+
+fn main() -> rustyline::Result<()> {
+    // with arguments the binary runs a single command, otherwise the calculator
+    if env::args().len() > 1 {
+        process::exit(cli::run_from_env());
+    }
+
+    let mut editor = DefaultEditor::new()?;
+    let history = history_path();
+    if let Some(path) = &history {
+        // a missing history file is expected on the first start
+        let _ = editor.load_history(path);
+    }
+    println!("linalg calculator, type help for the syntax and quit to leave");
+
+    let mut session = Session::new();
+    loop {
+        let line = match editor.readline("linalg> ") {
+            Ok(line) => line,
+            // Ctrl-C discards the current line, Ctrl-D leaves
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(error),
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+        match session.execute(&line) {
+            Ok(Some(output)) => println!("{}", output),
+            Ok(None) => {}
+            Err(error) => eprintln!("error: {}", error),
+        }
+    }
+
+    if let Some(path) = &history {
+        let _ = editor.save_history(path);
+    }
+    Ok(())
 }
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use num::traits::FloatConst;
+use num::Float;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+// LaTeX and MathML export for papers and web reports
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixDelimiters {
+    // pmatrix in LaTeX
+    Parentheses,
+    // bmatrix in LaTeX
+    Brackets,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImaginaryUnit {
+    // upright i, \mathrm{i}
+    I,
+    // engineering notation j
+    J,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupOptions {
+    pub delimiters: MatrixDelimiters,
+    pub imaginary_unit: ImaginaryUnit,
+    // digits after the decimal point, shortest representation if None
+    pub precision: Option<usize>,
+    // write real numbers without the + 0i
+    pub hide_zero_imaginary: bool,
+}
+
+impl Default for MarkupOptions {
+    fn default() -> Self {
+        MarkupOptions {
+            delimiters: MatrixDelimiters::Parentheses,
+            imaginary_unit: ImaginaryUnit::I,
+            precision: None,
+            hide_zero_imaginary: true,
+        }
+    }
+}
+
+const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+fn format_number<F: Float + fmt::Display>(value: F, options: &MarkupOptions) -> String {
+    match options.precision {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => format!("{}", value),
+    }
+}
+
+fn is_zero<F: Float + fmt::Display>(value: F, options: &MarkupOptions) -> bool {
+    // zero after rounding to the displayed precision
+    let formatted = format_number(value.abs(), options);
+    formatted.parse::<f64>().is_ok_and(|value| value == 0.0)
+}
+
+impl<F: Float + FloatConst + fmt::Display> Complex<F> {
+    pub fn to_latex(&self, options: &MarkupOptions) -> String {
+        let real = format_number(self.real, options);
+        if options.hide_zero_imaginary && is_zero(self.imaginary, options) {
+            return real;
+        }
+        let sign = if self.imaginary.is_sign_negative() { '-' } else { '+' };
+        let unit = match options.imaginary_unit {
+            ImaginaryUnit::I => "\\mathrm{i}",
+            ImaginaryUnit::J => "j",
+        };
+        format!("{} {} {}{}", real, sign, format_number(self.imaginary.abs(), options), unit)
+    }
+
+    fn mathml_content(&self, options: &MarkupOptions) -> String {
+        let number = |value: F| {
+            let formatted = format_number(value.abs(), options);
+            if value.is_sign_negative() && !is_zero(value, options) {
+                format!("<mo>-</mo><mn>{}</mn>", formatted)
+            } else {
+                format!("<mn>{}</mn>", formatted)
+            }
+        };
+        if options.hide_zero_imaginary && is_zero(self.imaginary, options) {
+            return format!("<mrow>{}</mrow>", number(self.real));
+        }
+        let sign = if self.imaginary.is_sign_negative() { '-' } else { '+' };
+        let unit = match options.imaginary_unit {
+            ImaginaryUnit::I => "<mi mathvariant=\"normal\">i</mi>",
+            ImaginaryUnit::J => "<mi>j</mi>",
+        };
+        format!(
+            "<mrow>{}<mo>{}</mo><mn>{}</mn>{}</mrow>",
+            number(self.real),
+            sign,
+            format_number(self.imaginary.abs(), options),
+            unit
+        )
+    }
+
+    pub fn to_mathml(&self, options: &MarkupOptions) -> String {
+        format!("<math xmlns=\"{}\">{}</math>", MATHML_NAMESPACE, self.mathml_content(options))
+    }
+}
+
+fn latex_environment(rows: &[Vec<Complex<FloatType>>], options: &MarkupOptions) -> String {
+    let environment = match options.delimiters {
+        MatrixDelimiters::Parentheses => "pmatrix",
+        MatrixDelimiters::Brackets => "bmatrix",
+    };
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|element| element.to_latex(options))
+                .collect::<Vec<_>>()
+                .join(" & ")
+        })
+        .collect();
+    format!("\\begin{{{0}}}\n{1}\n\\end{{{0}}}", environment, lines.join(" \\\\\n"))
+}
+
+fn mathml_table(rows: &[Vec<Complex<FloatType>>], options: &MarkupOptions) -> String {
+    let (open, close) = match options.delimiters {
+        MatrixDelimiters::Parentheses => ("(", ")"),
+        MatrixDelimiters::Brackets => ("[", "]"),
+    };
+    let table: String = rows
+        .iter()
+        .map(|row| {
+            let cells: String = row
+                .iter()
+                .map(|element| format!("<mtd>{}</mtd>", element.mathml_content(options)))
+                .collect();
+            format!("<mtr>{}</mtr>", cells)
+        })
+        .collect();
+    format!(
+        "<math xmlns=\"{}\"><mrow><mo>{}</mo><mtable>{}</mtable><mo>{}</mo></mrow></math>",
+        MATHML_NAMESPACE, open, table, close
+    )
+}
+
+impl Vector {
+    fn markup_rows(&self) -> Vec<Vec<Complex<FloatType>>> {
+        // column vectors are written vertically, row vectors horizontally
+        match self.get_type() {
+            VectorType::COLUMN_VECTOR => self.iter().map(|element| vec![element]).collect(),
+            VectorType::ROW_VECTOR => vec![self.iter().collect()],
+        }
+    }
+
+    pub fn to_latex(&self, options: &MarkupOptions) -> String {
+        latex_environment(&self.markup_rows(), options)
+    }
+
+    pub fn to_mathml(&self, options: &MarkupOptions) -> String {
+        mathml_table(&self.markup_rows(), options)
+    }
+}
+
+impl Matrix {
+    fn markup_rows(&self) -> Vec<Vec<Complex<FloatType>>> {
+        (0..self.num_rows)
+            .map(|row_index| {
+                (0..self.num_columns)
+                    .map(|column_index| self.get_element(row_index, column_index).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn to_latex(&self, options: &MarkupOptions) -> String {
+        latex_environment(&self.markup_rows(), options)
+    }
+
+    pub fn to_mathml(&self, options: &MarkupOptions) -> String {
+        mathml_table(&self.markup_rows(), options)
+    }
+}
@@ -1,4 +1,8 @@
+use std::fmt;
+
 use crate::complex::*;
+use crate::gemm::*;
+use crate::parallel::*;
 use crate::vector::*;
 
 
@@ -13,6 +17,7 @@ type ComplexNum = Complex<FloatType>;
  * => number of columns = vector size
  * => number of rows = amount of contained vectors
  */
+#[derive(Debug, Clone)]
 pub struct Matrix {
     pub num_rows: usize,
     pub num_columns: usize,
@@ -33,6 +38,16 @@ impl Matrix {
         }
     }
 
+    pub fn identity(size: usize) -> Self {
+        let mut result = Matrix::zeros(size, size);
+        for index in 0..size {
+            result.elements[index]
+                .set_element(index, Complex::from(1.0, 0.0))
+                .unwrap();
+        }
+        result
+    }
+
     pub fn from_array(
         num_rows: usize,
         num_cols: usize,
@@ -61,10 +76,183 @@ impl Matrix {
         }
     }
 
+    pub fn from_diagonal(diagonal: &Vector) -> Self {
+        let mut result = Matrix::zeros(diagonal.size(), diagonal.size());
+        for (index, element) in diagonal.iter().enumerate() {
+            result.elements[index].set_element(index, element).unwrap();
+        }
+        result
+    }
+
+    pub fn from_rows(rows: &[Vector]) -> Result<Self, &'static str> {
+        let num_cols = match rows.first() {
+            Some(row) => row.size(),
+            None => return Err("At least one row is required"),
+        };
+        if rows.iter().any(|row| row.size() != num_cols) {
+            return Err("All rows must have the same size");
+        }
+        Ok(Matrix {
+            num_rows: rows.len(),
+            num_columns: num_cols,
+            elements: rows
+                .iter()
+                .map(|row| Vector::from_vec(row.iter().collect()))
+                .collect(),
+        })
+    }
+
+    pub fn from_columns(columns: &[Vector]) -> Result<Self, &'static str> {
+        let mut result = Matrix::from_rows(columns)
+            .map_err(|_| "All columns must have the same size")?;
+        result.transpose();
+        Ok(result)
+    }
+
+    pub fn from_fn<G>(num_rows: usize, num_cols: usize, function: G) -> Self
+    where
+        G: Fn(usize, usize) -> ComplexNum,
+    {
+        // element (i, j) of the result is function(i, j)
+        let elements = (0..num_rows)
+            .map(|row_index| {
+                Vector::from_vec(
+                    (0..num_cols)
+                        .map(|column_index| function(row_index, column_index))
+                        .collect(),
+                )
+            })
+            .collect();
+        Matrix {
+            num_rows,
+            num_columns: num_cols,
+            elements,
+        }
+    }
+
+    pub fn block(blocks: &[&[&Matrix]]) -> Result<Self, &'static str> {
+        // assembles a block matrix; all blocks in a block row need the
+        // same number of rows, all blocks in a block column the same
+        // number of columns
+        let first_row = match blocks.first() {
+            Some(block_row) if !block_row.is_empty() => block_row,
+            _ => return Err("At least one block is required"),
+        };
+        let mut block_rows = Vec::new();
+        for block_row in blocks {
+            if block_row.len() != first_row.len() {
+                return Err("All block rows must have the same number of blocks");
+            }
+            for (block, reference) in block_row.iter().zip(first_row.iter()) {
+                if block.num_columns != reference.num_columns {
+                    return Err("All blocks in a block column must have the same number of columns");
+                }
+            }
+            block_rows.push(Matrix::hstack(block_row)?);
+        }
+        let block_rows: Vec<&Matrix> = block_rows.iter().collect();
+        Matrix::vstack(&block_rows)
+    }
+
+    pub fn hstack(matrices: &[&Matrix]) -> Result<Self, &'static str> {
+        // places the matrices next to each other
+        let num_rows = match matrices.first() {
+            Some(matrix) => matrix.num_rows,
+            None => return Err("At least one matrix is required"),
+        };
+        if matrices.iter().any(|matrix| matrix.num_rows != num_rows) {
+            return Err("Horizontally stacked matrices must have the same number of rows");
+        }
+        let elements = (0..num_rows)
+            .map(|row_index| {
+                Vector::from_vec(
+                    matrices
+                        .iter()
+                        .flat_map(|matrix| matrix.elements[row_index].iter())
+                        .collect(),
+                )
+            })
+            .collect();
+        Ok(Matrix {
+            num_rows,
+            num_columns: matrices.iter().map(|matrix| matrix.num_columns).sum(),
+            elements,
+        })
+    }
+
+    pub fn vstack(matrices: &[&Matrix]) -> Result<Self, &'static str> {
+        // places the matrices below each other
+        let num_cols = match matrices.first() {
+            Some(matrix) => matrix.num_columns,
+            None => return Err("At least one matrix is required"),
+        };
+        if matrices.iter().any(|matrix| matrix.num_columns != num_cols) {
+            return Err("Vertically stacked matrices must have the same number of columns");
+        }
+        Ok(Matrix {
+            num_rows: matrices.iter().map(|matrix| matrix.num_rows).sum(),
+            num_columns: num_cols,
+            elements: matrices
+                .iter()
+                .flat_map(|matrix| matrix.elements.iter().cloned())
+                .collect(),
+        })
+    }
+
     pub fn is_quadratic(&self) -> bool {
         self.num_columns == self.num_rows
     }
 
+    pub fn is_normal(&self) -> bool {
+        // a matrix is normal iff it commutes with its adjoint
+        if !self.is_quadratic() {
+            return false;
+        }
+        let mut adjoint = self.clone();
+        adjoint.adjoint();
+        let mut commutator = adjoint.multiply(self).unwrap();
+        commutator.scale(-1.0);
+        let commutator = self
+            .multiply(&adjoint)
+            .unwrap()
+            .add(&commutator)
+            .unwrap();
+
+        let tolerance = 10.0
+            * self.num_rows as FloatType
+            * FloatType::EPSILON
+            * FloatType::powi(self.norm_frobenius(), 2);
+        commutator.norm_frobenius() <= tolerance
+    }
+
+    pub fn is_hermitian(&self) -> bool {
+        let mut adjoint = self.clone();
+        adjoint.adjoint();
+        self.is_close_to(&adjoint)
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        let mut transpose = self.clone();
+        transpose.transpose();
+        self.is_close_to(&transpose)
+    }
+
+    fn is_close_to(&self, other: &Self) -> bool {
+        // equal up to rounding, relative to the size of the entries
+        if !self.is_quadratic() {
+            return false;
+        }
+        let mut difference = other.clone();
+        difference.scale(-1.0);
+        let difference = self.add(&difference).unwrap();
+
+        let tolerance = 10.0
+            * self.num_rows as FloatType
+            * FloatType::EPSILON
+            * self.norm_frobenius();
+        difference.norm_frobenius() <= tolerance
+    }
+
     pub fn get_element(
         &self,
         row_index: usize,
@@ -79,6 +267,19 @@ impl Matrix {
         }
     }
 
+    pub(crate) fn row(&self, row_index: usize) -> &[ComplexNum] {
+        // contiguous elements of a row for kernels that skip the bounds checks
+        self.elements[row_index].as_slice()
+    }
+
+    pub(crate) fn row_mut(&mut self, row_index: usize) -> &mut [ComplexNum] {
+        self.elements[row_index].as_mut_slice()
+    }
+
+    pub(crate) fn rows_mut(&mut self) -> &mut [Vector] {
+        &mut self.elements
+    }
+
     pub fn set_element(
         &mut self,
         row_index: usize,
@@ -97,48 +298,92 @@ impl Matrix {
         if self.num_rows != other.num_rows || self.num_columns != other.num_columns {
             None
         } else {
-            let mut result = Matrix::zeros(self.num_rows, self.num_columns);
-            for row_index in 0..self.num_rows {
-                for col_index in 0..self.num_columns {
-                    let num1 = self.get_element(row_index, col_index).unwrap();
-                    let num2 = other.get_element(row_index, col_index).unwrap();
-                    let number = num1.addition(&num2);
-                    if let Err(_) = result.set_element(row_index, col_index, number)  {
-                        return None;
-                    } 
-                }
-            }
-            Some(result)
+            let work = self.num_rows * self.num_columns;
+            let elements = map_collect(self.num_rows, work, |row_index| {
+                let sum = self
+                    .row(row_index)
+                    .iter()
+                    .zip(other.row(row_index))
+                    .map(|(num1, num2)| num1.addition(num2))
+                    .collect();
+                Vector::from_vec(sum)
+            });
+            Some(Matrix {
+                num_rows: self.num_rows,
+                num_columns: self.num_columns,
+                elements,
+            })
         }
     }
 
-    pub fn transpose(&mut self) -> () {
-        // primitive transpose algorithm
-        let mut matrix_collector = Vec::new();
-        for row_index in 0..self.num_rows {
-            // iterate over column of non-transposed array to 
-            // create rows of transposed array
-            let mut transposed_row = Vec::new();
-            for column_index in 0..self.num_columns {
-                let element = self
-                    .get_element(row_index, column_index)
-                    .unwrap();
-                transposed_row.push(element);
+    fn check_same_shape(&self, other: &Self) -> Result<(), &'static str> {
+        if self.num_rows != other.num_rows || self.num_columns != other.num_columns {
+            Err("Dimensions of the matrices have to match")
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn add_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        // self <- self + other without allocating
+        self.check_same_shape(other)?;
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(&mut self.elements, work, |row_index, row| {
+            for (element, other_element) in row.as_mut_slice().iter_mut().zip(other.row(row_index)) {
+                *element = element.addition(other_element);
             }
-            let transposed_row = Vector::from_vec(transposed_row);
-            matrix_collector.push(transposed_row);
+        });
+        Ok(())
+    }
+
+    pub fn sub_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        // self <- self - other without allocating
+        self.check_same_shape(other)?;
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(&mut self.elements, work, |row_index, row| {
+            for (element, other_element) in row.as_mut_slice().iter_mut().zip(other.row(row_index)) {
+                *element = element.subtraction(other_element);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn transpose(&mut self) -> () {
+        // quadratic matrices swap their elements across the diagonal in
+        // place; rectangular ones change the length of their rows, so
+        // the columns are copied into newly allocated rows, transpose_into
+        // writes them into a matrix of the caller instead
+        if self.is_quadratic() {
+            self.transpose_square().unwrap();
+            return;
         }
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        let rows = &self.elements;
+        self.elements = map_collect(num_columns, num_rows * num_columns, |column_index| {
+            Vector::from_vec(rows.iter().map(|row| row.as_slice()[column_index].clone()).collect())
+        });
+        self.num_rows = num_columns;
+        self.num_columns = num_rows;
+    }
 
-        let temp_rows = self.num_rows;
-        self.num_rows = self.num_columns;
-        self.num_columns = temp_rows;
-        self.elements = matrix_collector;
+    pub fn transpose_square(&mut self) -> Result<(), &'static str> {
+        // in place transpose of a quadratic matrix
+        if !self.is_quadratic() {
+            return Err("In place transpose requires a quadratic matrix");
+        }
+        for row_index in 1..self.num_rows {
+            let (upper, lower) = self.elements.split_at_mut(row_index);
+            let row = lower[0].as_mut_slice();
+            for (column_index, element) in row[..row_index].iter_mut().enumerate() {
+                std::mem::swap(element, &mut upper[column_index].as_mut_slice()[row_index]);
+            }
+        }
+        Ok(())
     }
 
     pub fn conjugate(&mut self) -> () {
-        self.elements
-        .iter_mut()
-        .for_each(|x| x.conjugate());
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(&mut self.elements, work, |_, x| x.conjugate());
     }
 
     pub fn adjoint(&mut self) -> () {
@@ -147,57 +392,87 @@ impl Matrix {
     }
 
     pub fn multiply(&self, other: &Self) -> Result<Self, &'static str> {
-        // primitive matrix multiplication algorithm
-        // multiplying self as lhs and other as rhs
-        if self.num_columns != other.num_rows {
-            return Err("Number of columns of first matrix must match number
-            of rows of second matrix");
-        }
-        let mut matrix_collector = Vec::new();
-        for lhs_row_index in 0..self.num_rows {
-            let mut row_vector = Vec::new();
-            for rhs_col_index in 0..other.num_columns {
-                let mut matrix_element = Complex::from(0.0, 0.0);
-                for inner_iteration_index in 0..self.num_columns {
-                    let lhs_element = self
-                    .get_element(lhs_row_index, inner_iteration_index)
-                    .unwrap();
-                    let rhs_element = other
-                    .get_element(inner_iteration_index, rhs_col_index)
-                    .unwrap();
-
-                    let result = Complex::multiplication(&lhs_element, &rhs_element);
-                    matrix_element = matrix_element.addition(&result);
-                }
-                row_vector.push(matrix_element);
-            }
-            let matrix_row = Vector::from_vec(row_vector);
-            matrix_collector.push(matrix_row);
-        }
-        Ok(Matrix {
-            num_rows: self.num_rows,
-            num_columns: self.num_columns,
-            elements: matrix_collector
-        })
+        // multiplying self as lhs and other as rhs, see the gemm module
+        self.multiply_op(Op::Normal, other, Op::Normal)
     }
 
     pub fn multiply_vector(&self, vector: &Vector) -> Result<Vector, &'static str> {
+        let mut result = Vector::zeros(self.num_rows);
+        self.gemv_into(vector, &mut result)?;
+        Ok(result)
+    }
 
+    pub fn transpose_into(&self, result: &mut Matrix) -> Result<(), &'static str> {
+        // result <- self^T, reusing the storage of result
+        if (result.num_rows, result.num_columns) != (self.num_columns, self.num_rows) {
+            return Err("Dimensions of the result must be those of the transpose");
+        }
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(result.rows_mut(), work, |row_index, row| {
+            for (element, source_row) in row.as_mut_slice().iter_mut().zip(&self.elements) {
+                *element = source_row.as_slice()[row_index].clone();
+            }
+        });
+        Ok(())
+    }
+
+    pub fn gemv_into(&self, vector: &Vector, result: &mut Vector) -> Result<(), &'static str> {
+        // result <- self vector, reusing the storage of result
         if self.num_columns != vector.size() {
             return Err("Matrix dimension does not match vector dimension");
         }
+        if self.num_rows != result.size() {
+            return Err("Dimension of the result vector must match the number of rows");
+        }
 
-        let mut vector_collector: Vec<ComplexNum> = Vec::new();
-
-        self.elements
-            .iter()
-            .for_each(|vec| vector_collector.push(vec.inner_product(vector).unwrap()));
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(result.as_mut_slice(), work, |row_index, element| {
+            *element = self.elements[row_index].inner_product(vector).unwrap();
+        });
+        Ok(())
+    }
 
-        Ok(Vector::from_vec(vector_collector))
+    pub fn gemm_into(&self, other: &Self, result: &mut Matrix) -> Result<(), &'static str> {
+        // result <- self other, reusing the storage of result
+        result.gemm(Complex::from(1.0, 0.0), self, Op::Normal, other, Op::Normal, Complex::new())
     }
 
     pub fn scale(&mut self, factor: FloatType) -> () {
-        self.elements.iter_mut().for_each(|vec| vec.scale(factor));
+        let work = self.num_rows * self.num_columns;
+        for_each_mut(&mut self.elements, work, |_, vec| vec.scale(factor));
+    }
+
+    pub fn trace(&self) -> Result<ComplexNum, &'static str> {
+        if !self.is_quadratic() {
+            return Err("Trace is only defined for quadratic matrices");
+        }
+        let mut result = Complex::new();
+        for (index, row) in self.elements.iter().enumerate() {
+            result = result.addition(row.get_element(index).unwrap());
+        }
+        Ok(result)
+    }
+
+    pub fn norm_one(&self) -> FloatType {
+        // maximum absolute column sum
+        let mut maximum: FloatType = 0.0;
+        for column_index in 0..self.num_columns {
+            let mut sum: FloatType = 0.0;
+            for row in &self.elements {
+                sum += row.get_element(column_index).unwrap().norm();
+            }
+            maximum = FloatType::max(maximum, sum);
+        }
+        maximum
+    }
+
+    pub fn norm_frobenius(&self) -> FloatType {
+        let sum: FloatType = self
+            .elements
+            .iter()
+            .map(|row| FloatType::powi(row.norm_l2(), 2))
+            .sum();
+        FloatType::sqrt(sum)
     }
 
     pub fn kronecker_product(&self, other: &Self) -> Result<Self, &'static str> {
@@ -211,49 +486,64 @@ impl Matrix {
             return Err("Matrix dimensions may not be 0");
         }
 
-        let mut result = Matrix::zeros(row_dimension, column_dimension);
-
-        for i in 0..row_dimension {
-
-            for j in 0..column_dimension {
-
-                // kronecker product definition:
-                // result[i][j] = self[ceil(i/p)][ceil(j/q)] * other [((i-1) % p) + 1][((j-1) % q) + 1]
-                // avoid casting to and from float using different form of ceiling function:
-                // https://stackoverflow.com/questions/17944/how-to-round-up-the-result-of-integer-division
-                let lhs_row = ((i+1) / other.num_rows) + 1;
-                let lhs_col = ((j+1) / other.num_columns) + 1;
+        // the result is an m x n block matrix whose block (i, j) is
+        // self[i][j] * other; every row of the result combines one row
+        // of self with one row of other
+        let work = row_dimension * column_dimension;
+        let matrix_collector = map_collect(row_dimension, work, |row_index| {
+            let lhs_row = &self.elements[row_index / other.num_rows];
+            let rhs_row = other.row(row_index % other.num_rows);
+            let mut row_vector = Vec::with_capacity(column_dimension);
+            for lhs in lhs_row.as_slice() {
+                for rhs in rhs_row {
+                    row_vector.push(lhs.multiplication(rhs));
+                }
+            }
+            Vector::from_vec(row_vector)
+        });
 
-                let rhs_row = ((i-1 ) % other.num_rows) + 1;
-                let rhs_col = ((j-1) % other.num_columns) + 1;
+        Ok(Matrix {
+            num_rows: row_dimension,
+            num_columns: column_dimension,
+            elements: matrix_collector,
+        })
+    }
 
-                let lhs = self.get_element(lhs_row, lhs_col)?;
-                let rhs = other.get_element(rhs_row, rhs_col)?;
+    pub fn kronecker_sum(&self, other: &Self) -> Result<Self, &'static str> {
+        // A (+) B = A (x) I_p + I_m (x) B for A of dimension m x m
+        // and B of dimension p x p
+        if !self.is_quadratic() || !other.is_quadratic() {
+            return Err("Kronecker sum is only defined for quadratic matrices");
+        }
 
-                let element = Complex::multiplication(&lhs, &rhs);
-                result.set_element(i, j, element)?;
-            }
+        let lhs = self.kronecker_product(&Matrix::identity(other.num_rows))?;
+        let rhs = Matrix::identity(self.num_rows).kronecker_product(other)?;
+        lhs.add(&rhs)
+            .ok_or("Kronecker sum could not add the expanded matrices")
+    }
 
-        }
-        Ok(result)
+    pub fn kron_all(matrices: &[Matrix]) -> Result<Self, &'static str> {
+        // n-fold kronecker product M_1 (x) M_2 (x) ... (x) M_n
+        let (first, rest) = matrices
+            .split_first()
+            .ok_or("Kronecker product requires at least one matrix")?;
 
+        rest.iter()
+            .try_fold(first.clone(), |product, matrix| product.kronecker_product(matrix))
     }
 
     pub fn calculate_eigenvalues(&self) -> Result<Vec<ComplexNum>, &'static str> {
-        // QR-Algorithm for eigenvalue calculation
-        let max_iterations = 100;
-
         // only quadratic matrices can have eigenvalues
         if !self.is_quadratic() {
             return Err("Eigenvalues can only be calculated for quadratic matrices");
         }
 
-
-
-
-
-        Ok(vec![Complex::from(0.0, 0.0)])
-        
+        // the eigenvalues are the diagonal elements of the schur form,
+        // which is computed by the shifted QR-Algorithm
+        let (_, triangular) = self.schur_decomposition()?;
+        (0..self.num_rows)
+            .map(|index| triangular.get_element(index, index))
+            .collect()
     }
 
     pub fn calculate_eigenvectors(&self) -> () {
@@ -286,19 +576,6 @@ impl Matrix {
         Matrix::zeros(2, 2)
     }
 
-    pub fn QR_decomposition(&self) -> Self {
-
-        // from columns of given matrix A
-
-        // form unitary (orthonormal) matrix Q with gram-schmidt-decomp
-
-        // use matrix solving to create upper triang matrix
-
-        // return Q
-
-        Matrix::zeros(1, 1)
-    }
-
     // TODO: should be in another module later on
     pub fn gram_schmidt_decomposition(vectors: Vec<Vector>) -> Result<Vec<Vector>, &'static str> {
         // TODO modify for numerical stability
@@ -332,3 +609,55 @@ impl Matrix {
 
 }
 
+impl fmt::Display for Matrix {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // one line per row with right aligned columns; large matrices
+        // only show their outer rows and columns around an ellipsis
+        if self.num_rows == 0 || self.num_columns == 0 {
+            return write!(f, "[]");
+        }
+        let row_indices = display_indices(self.num_rows);
+        let column_indices = display_indices(self.num_columns);
+
+        let cells: Vec<Vec<String>> = row_indices
+            .iter()
+            .map(|row_index| {
+                column_indices
+                    .iter()
+                    .map(|column_index| match (row_index, column_index) {
+                        (Some(row_index), Some(column_index)) => self.elements[*row_index]
+                            .get_element(*column_index)
+                            .unwrap()
+                            .format_with_precision(f.precision()),
+                        _ => String::from("..."),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..column_indices.len())
+            .map(|column| {
+                cells
+                    .iter()
+                    .map(|row| row[column].chars().count())
+                    .chain(f.width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let lines: Vec<String> = cells
+            .iter()
+            .map(|row| {
+                let row: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                    .collect();
+                format!("[{}]", row.join("  "))
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
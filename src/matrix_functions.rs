@@ -0,0 +1,438 @@
+use std::ops::Range;
+
+use num::traits::FloatConst;
+
+use crate::complex::*;
+use crate::decomposition::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// degree of the diagonal pade approximant used for the exponential
+const PADE_DEGREE: usize = 6;
+
+// the scaled matrix of the exponential has at most this 1-norm
+const EXPM_NORM_BOUND: FloatType = 0.5;
+
+// square roots are taken until ||T - I|| is below this bound
+const LOGM_NORM_BOUND: FloatType = 0.25;
+
+const MAX_SQUARE_ROOTS: usize = 40;
+
+// eigenvalues closer than this end up in the same schur-parlett block
+const BLOCKING_DISTANCE: FloatType = 0.1;
+
+// quadrature nodes on the circle around a block of close eigenvalues
+const CONTOUR_NODES: usize = 64;
+
+// relative deviation of the diagonal of a contour integral from the
+// function values at the eigenvalues, beyond which f is taken to be
+// not analytic on the disk
+const CONTOUR_TOLERANCE: FloatType = 1e-3;
+
+// gauss-legendre nodes and weights on [-1, 1]
+const GAUSS_LEGENDRE: [(FloatType, FloatType); 6] = [
+    (-0.932_469_5, 0.171_324_49),
+    (-0.661_209_4, 0.360_761_57),
+    (-0.238_619_2, 0.467_913_93),
+    (0.238_619_2, 0.467_913_93),
+    (0.661_209_4, 0.360_761_57),
+    (0.932_469_5, 0.171_324_49),
+];
+
+impl Matrix {
+    pub fn expm(&self) -> Result<Matrix, &'static str> {
+        // scaling and squaring: exp(A) = exp(A / 2^s)^(2^s), where
+        // exp(A / 2^s) is approximated by a diagonal pade approximant
+        if !self.is_quadratic() {
+            return Err("The matrix exponential is only defined for quadratic matrices");
+        }
+        let size = self.num_rows;
+
+        let norm = self.norm_one();
+        let squarings = if norm > EXPM_NORM_BOUND {
+            FloatType::ceil(FloatType::log2(norm / EXPM_NORM_BOUND)) as i32
+        } else {
+            0
+        };
+        let mut scaled_matrix = self.clone();
+        scaled_matrix.scale(FloatType::powi(2.0, -squarings));
+
+        // numerator N = sum c_k X^k and denominator D = sum (-1)^k c_k X^k
+        let mut numerator = Matrix::identity(size);
+        let mut denominator = Matrix::identity(size);
+        let mut power = Matrix::identity(size);
+        let mut coefficient: FloatType = 1.0;
+        let degree = PADE_DEGREE as FloatType;
+        for k in 1..=PADE_DEGREE {
+            let k_float = k as FloatType;
+            coefficient *= (degree - k_float + 1.0) / (k_float * (2.0 * degree - k_float + 1.0));
+            power = power.multiply(&scaled_matrix)?;
+
+            let mut term = power.clone();
+            term.scale(coefficient);
+            numerator = sum(&numerator, &term)?;
+            if k % 2 == 1 {
+                term.scale(-1.0);
+            }
+            denominator = sum(&denominator, &term)?;
+        }
+
+        let mut result = denominator.solve(&numerator)?;
+        for _ in 0..squarings {
+            result = result.multiply(&result)?;
+        }
+        Ok(result)
+    }
+
+    pub fn logm(&self) -> Result<Matrix, &'static str> {
+        // inverse scaling and squaring on the schur form:
+        // log(A) = 2^k log(T^(1/2^k)) where T^(1/2^k) is close to I
+        if !self.is_quadratic() {
+            return Err("The matrix logarithm is only defined for quadratic matrices");
+        }
+        let size = self.num_rows;
+        let (unitary, mut triangular) = self.schur_decomposition()?;
+        if has_eigenvalue_on_negative_axis(&triangular, self.norm_frobenius())? {
+            return Err("Matrix has no principal logarithm, an eigenvalue lies on the closed negative real axis");
+        }
+
+        let identity = Matrix::identity(size);
+        let mut minus_identity = identity.clone();
+        minus_identity.scale(-1.0);
+
+        let mut square_roots = 0;
+        let mut difference = sum(&triangular, &minus_identity)?;
+        while difference.norm_one() > LOGM_NORM_BOUND {
+            if square_roots == MAX_SQUARE_ROOTS {
+                return Err("Inverse scaling and squaring did not converge");
+            }
+            triangular = sqrt_upper_triangular(&triangular)?;
+            difference = sum(&triangular, &minus_identity)?;
+            square_roots += 1;
+        }
+
+        // log(I + X) = integral over [0, 1] of X (I + t X)^-1 dt,
+        // evaluated by gauss-legendre quadrature
+        let mut logarithm = Matrix::zeros(size, size);
+        for (node, weight) in GAUSS_LEGENDRE {
+            let mut system = difference.clone();
+            system.scale((node + 1.0) / 2.0);
+            let system = sum(&identity, &system)?;
+
+            let mut term = system.solve(&difference)?;
+            term.scale(weight / 2.0);
+            logarithm = sum(&logarithm, &term)?;
+        }
+        logarithm.scale(FloatType::powi(2.0, square_roots as i32));
+
+        transform_back(&unitary, &logarithm)
+    }
+
+    pub fn sqrtm(&self) -> Result<Matrix, &'static str> {
+        // schur method: the square root of the triangular factor is
+        // computed by a recurrence over its superdiagonals
+        if !self.is_quadratic() {
+            return Err("The matrix square root is only defined for quadratic matrices");
+        }
+        let (unitary, triangular) = self.schur_decomposition()?;
+        if has_eigenvalue_on_negative_axis(&triangular, self.norm_frobenius())? {
+            return Err("Matrix has no principal square root, an eigenvalue lies on the closed negative real axis");
+        }
+        let root = sqrt_upper_triangular(&triangular)?;
+        transform_back(&unitary, &root)
+    }
+
+    pub fn sinm(&self) -> Result<Matrix, &'static str> {
+        // sin(A) = (exp(iA) - exp(-iA)) / 2i
+        let (positive, mut negative) = self.imaginary_exponentials()?;
+        negative.scale(-1.0);
+        scaled(&sum(&positive, &negative)?, &Complex::from(0.0, -0.5))
+    }
+
+    pub fn cosm(&self) -> Result<Matrix, &'static str> {
+        // cos(A) = (exp(iA) + exp(-iA)) / 2
+        let (positive, negative) = self.imaginary_exponentials()?;
+        let mut result = sum(&positive, &negative)?;
+        result.scale(0.5);
+        Ok(result)
+    }
+
+    pub fn funm<G>(&self, function: G) -> Result<Matrix, &'static str>
+    where
+        G: Fn(ComplexNum) -> ComplexNum,
+    {
+        // applies a scalar function to the matrix; normal matrices are
+        // handled by their eigendecomposition, all others by the
+        // schur-parlett method. Eigenvalues closer than the blocking
+        // distance are evaluated by a contour integral on a disk around
+        // them, so function has to be analytic on that disk, e.g. a
+        // branch cut of sqrt or ln may not pass between close eigenvalues.
+        // This is checked on the diagonal and reported as an error
+        if !self.is_quadratic() {
+            return Err("Matrix functions are only defined for quadratic matrices");
+        }
+        let size = self.num_rows;
+        let (mut unitary, mut triangular) = self.schur_decomposition()?;
+
+        if self.is_normal() {
+            // the schur form of a normal matrix is diagonal,
+            // so Q T Q^H already is an eigendecomposition
+            let mut diagonal = Matrix::zeros(size, size);
+            for index in 0..size {
+                diagonal.set_element(index, index, function(triangular.get_element(index, index)?))?;
+            }
+            return transform_back(&unitary, &diagonal);
+        }
+
+        let blocks = reorder_into_blocks(&mut unitary, &mut triangular)?;
+        let result = schur_parlett(&triangular, &blocks, &function)?;
+        transform_back(&unitary, &result)
+    }
+
+    fn imaginary_exponentials(&self) -> Result<(Matrix, Matrix), &'static str> {
+        // returns (exp(iA), exp(-iA))
+        let mut imaginary = scaled(self, &Complex::from(0.0, 1.0))?;
+        let positive = imaginary.expm()?;
+        imaginary.scale(-1.0);
+        let negative = imaginary.expm()?;
+        Ok((positive, negative))
+    }
+}
+
+fn sum(lhs: &Matrix, rhs: &Matrix) -> Result<Matrix, &'static str> {
+    lhs.add(rhs).ok_or("Matrix dimensions have to match for addition")
+}
+
+fn scaled(matrix: &Matrix, factor: &ComplexNum) -> Result<Matrix, &'static str> {
+    let mut result = matrix.clone();
+    for row_index in 0..matrix.num_rows {
+        for column_index in 0..matrix.num_columns {
+            let element = matrix.get_element(row_index, column_index)?.multiplication(factor);
+            result.set_element(row_index, column_index, element)?;
+        }
+    }
+    Ok(result)
+}
+
+fn transform_back(unitary: &Matrix, matrix: &Matrix) -> Result<Matrix, &'static str> {
+    // computes Q M Q^H
+    let mut adjoint = unitary.clone();
+    adjoint.adjoint();
+    unitary.multiply(matrix)?.multiply(&adjoint)
+}
+
+fn sub_matrix(matrix: &Matrix, block: &Range<usize>) -> Result<Matrix, &'static str> {
+    let mut result = Matrix::zeros(block.len(), block.len());
+    for (row_offset, row_index) in block.clone().enumerate() {
+        for (column_offset, column_index) in block.clone().enumerate() {
+            result.set_element(row_offset, column_offset, matrix.get_element(row_index, column_index)?)?;
+        }
+    }
+    Ok(result)
+}
+
+fn has_eigenvalue_on_negative_axis(triangular: &Matrix, norm: FloatType) -> Result<bool, &'static str> {
+    let tolerance = 100.0 * FloatType::EPSILON * norm;
+    for index in 0..triangular.num_rows {
+        let eigenvalue = triangular.get_element(index, index)?;
+        if eigenvalue.norm() <= tolerance {
+            return Ok(true);
+        }
+        if eigenvalue.real < 0.0 && FloatType::abs(eigenvalue.imaginary) <= tolerance {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn sqrt_upper_triangular(triangular: &Matrix) -> Result<Matrix, &'static str> {
+    // U_ii = sqrt(T_ii) and
+    // U_ij = (T_ij - sum_{k=i+1}^{j-1} U_ik U_kj) / (U_ii + U_jj)
+    let size = triangular.num_rows;
+    let mut root = Matrix::zeros(size, size);
+    for index in 0..size {
+        root.set_element(index, index, triangular.get_element(index, index)?.sqrt())?;
+    }
+    for column_index in 1..size {
+        for row_index in (0..column_index).rev() {
+            let mut element = triangular.get_element(row_index, column_index)?;
+            for inner_index in (row_index + 1)..column_index {
+                let product = root
+                    .get_element(row_index, inner_index)?
+                    .multiplication(&root.get_element(inner_index, column_index)?);
+                element = element.subtraction(&product);
+            }
+            let denominator = root
+                .get_element(row_index, row_index)?
+                .addition(&root.get_element(column_index, column_index)?);
+            root.set_element(row_index, column_index, element.division(&denominator)?)?;
+        }
+    }
+    Ok(root)
+}
+
+fn swap_schur_entries(unitary: &mut Matrix, triangular: &mut Matrix, index: usize) -> Result<(), &'static str> {
+    // swaps the diagonal entries index and index + 1 of the schur form
+    // with a single givens rotation while keeping A = Q T Q^H
+    let size = triangular.num_rows;
+    let first = triangular.get_element(index, index)?;
+    let second = triangular.get_element(index + 1, index + 1)?;
+    let rotation = givens_rotation(
+        &triangular.get_element(index, index + 1)?,
+        &second.subtraction(&first),
+    );
+    rotate_rows(triangular, &rotation, index, index + 1, (index + 2)..size)?;
+    rotate_columns(triangular, &rotation, index, index + 1, 0..index)?;
+    triangular.set_element(index, index, second)?;
+    triangular.set_element(index + 1, index + 1, first)?;
+    rotate_columns(unitary, &rotation, index, index + 1, 0..size)
+}
+
+fn reorder_into_blocks(unitary: &mut Matrix, triangular: &mut Matrix) -> Result<Vec<Range<usize>>, &'static str> {
+    // groups eigenvalues which are transitively closer than the blocking
+    // distance into clusters, then reorders the schur form so that every
+    // cluster forms a contiguous diagonal block
+    let size = triangular.num_rows;
+    let mut eigenvalues = Vec::new();
+    for index in 0..size {
+        eigenvalues.push(triangular.get_element(index, index)?);
+    }
+
+    // every eigenvalue is labeled by the smallest index in its cluster
+    let mut labels: Vec<usize> = (0..size).collect();
+    for first in 0..size {
+        for second in (first + 1)..size {
+            if eigenvalues[first].subtraction(&eigenvalues[second]).norm() <= BLOCKING_DISTANCE {
+                let keep = usize::min(labels[first], labels[second]);
+                let replace = usize::max(labels[first], labels[second]);
+                labels.iter_mut().filter(|label| **label == replace).for_each(|label| *label = keep);
+            }
+        }
+    }
+
+    // bubble sort the labels, each exchange is a swap in the schur form
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for index in 0..size.saturating_sub(1) {
+            if labels[index] > labels[index + 1] {
+                swap_schur_entries(unitary, triangular, index)?;
+                labels.swap(index, index + 1);
+                swapped = true;
+            }
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for index in 1..=size {
+        if index == size || labels[index] != labels[start] {
+            blocks.push(start..index);
+            start = index;
+        }
+    }
+    Ok(blocks)
+}
+
+fn evaluate_block<G>(triangular: &Matrix, block: &Range<usize>, function: &G) -> Result<Matrix, &'static str>
+where
+    G: Fn(ComplexNum) -> ComplexNum,
+{
+    // f(T) = 1 / (2 pi i) * contour integral of f(z) (zI - T)^-1 dz over a
+    // circle around the eigenvalues of the block, evaluated with the
+    // trapezoidal rule, which only requires values of f itself
+    let block_matrix = sub_matrix(triangular, block)?;
+    let size = block.len();
+    if size == 1 {
+        return Matrix::from_array(1, 1, &[function(block_matrix.get_element(0, 0)?)]);
+    }
+
+    let mut center = Complex::new();
+    for index in 0..size {
+        center = center.addition(&block_matrix.get_element(index, index)?);
+    }
+    center.scale(1.0 / size as FloatType);
+    let mut radius: FloatType = 0.0;
+    for index in 0..size {
+        let distance = block_matrix.get_element(index, index)?.subtraction(&center).norm();
+        radius = FloatType::max(radius, distance);
+    }
+    radius += BLOCKING_DISTANCE;
+
+    let identity = Matrix::identity(size);
+    let mut negative_block = block_matrix.clone();
+    negative_block.scale(-1.0);
+
+    let mut result = Matrix::zeros(size, size);
+    for node in 0..CONTOUR_NODES {
+        let angle = 2.0 * FloatType::PI() * node as FloatType / CONTOUR_NODES as FloatType;
+        let offset = Complex::from_polar(radius, angle);
+        let point = center.addition(&offset);
+
+        let shifted = sum(&scaled(&identity, &point)?, &negative_block)?;
+        let resolvent = shifted.inverse()?;
+
+        let mut weight = function(point).multiplication(&offset);
+        weight.scale(1.0 / CONTOUR_NODES as FloatType);
+        result = sum(&result, &scaled(&resolvent, &weight)?)?;
+    }
+
+    // the diagonal of f(T) is f at the eigenvalues, which the integral
+    // only reproduces if f is analytic on the disk
+    for index in 0..size {
+        let expected = function(block_matrix.get_element(index, index)?);
+        let deviation = result.get_element(index, index)?.subtraction(&expected).norm();
+        if deviation > CONTOUR_TOLERANCE * FloatType::max(1.0, expected.norm()) {
+            return Err("Function is not analytic around a cluster of close eigenvalues");
+        }
+    }
+    Ok(result)
+}
+
+fn schur_parlett<G>(triangular: &Matrix, blocks: &[Range<usize>], function: &G) -> Result<Matrix, &'static str>
+where
+    G: Fn(ComplexNum) -> ComplexNum,
+{
+    let size = triangular.num_rows;
+    let mut result = Matrix::zeros(size, size);
+    let mut block_of = vec![0; size];
+    for (block_index, block) in blocks.iter().enumerate() {
+        let evaluated = evaluate_block(triangular, block, function)?;
+        for (row_offset, row_index) in block.clone().enumerate() {
+            block_of[row_index] = block_index;
+            for (column_offset, column_index) in block.clone().enumerate() {
+                result.set_element(row_index, column_index, evaluated.get_element(row_offset, column_offset)?)?;
+            }
+        }
+    }
+
+    // parlett recurrence for all entries outside of the diagonal blocks:
+    // F_ij (T_ii - T_jj) = F_ii T_ij - T_ij F_jj + sum_{k=i+1}^{j-1} (F_ik T_kj - T_ik F_kj)
+    for column_index in 0..size {
+        for row_index in (0..column_index).rev() {
+            if block_of[row_index] == block_of[column_index] {
+                continue;
+            }
+            let mut element = Complex::new();
+            for inner_index in row_index..column_index {
+                let product = result
+                    .get_element(row_index, inner_index)?
+                    .multiplication(&triangular.get_element(inner_index, column_index)?);
+                element = element.addition(&product);
+            }
+            for inner_index in (row_index + 1)..=column_index {
+                let product = triangular
+                    .get_element(row_index, inner_index)?
+                    .multiplication(&result.get_element(inner_index, column_index)?);
+                element = element.subtraction(&product);
+            }
+            let denominator = triangular
+                .get_element(row_index, row_index)?
+                .subtraction(&triangular.get_element(column_index, column_index)?);
+            result.set_element(row_index, column_index, element.division(&denominator)?)?;
+        }
+    }
+    Ok(result)
+}
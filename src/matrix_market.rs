@@ -0,0 +1,430 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Reading and writing the Matrix Market exchange format
+// https://math.nist.gov/MatrixMarket/formats.html
+// Files are always read into a dense matrix
+
+// upper bound of rows times columns when reading, 2 GiB of elements
+const MAX_DENSE_ELEMENTS: usize = 1 << 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixMarketFormat {
+    // list of (row, column, value) entries
+    Coordinate,
+    // all entries in column major order
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixMarketField {
+    Real,
+    Complex,
+    Integer,
+    // only the positions of the non zero entries, which are read as 1
+    Pattern,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixMarketSymmetry {
+    General,
+    Symmetric,
+    Hermitian,
+    SkewSymmetric,
+}
+
+/**
+ * Contents of the %%MatrixMarket banner line
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixMarketHeader {
+    pub format: MatrixMarketFormat,
+    pub field: MatrixMarketField,
+    pub symmetry: MatrixMarketSymmetry,
+}
+
+#[derive(Debug)]
+pub enum MatrixMarketError {
+    Io(io::Error),
+    // malformed input, line numbers start at 1
+    Parse { line: usize, reason: &'static str },
+    // the matrix cannot be represented with the requested header
+    Write(&'static str),
+}
+
+impl fmt::Display for MatrixMarketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatrixMarketError::Io(error) => write!(f, "{}", error),
+            MatrixMarketError::Parse { line, reason } => write!(f, "{} in line {}", reason, line),
+            MatrixMarketError::Write(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for MatrixMarketError {}
+
+impl From<io::Error> for MatrixMarketError {
+    fn from(error: io::Error) -> Self {
+        MatrixMarketError::Io(error)
+    }
+}
+
+fn parse_error(line: usize, reason: &'static str) -> MatrixMarketError {
+    MatrixMarketError::Parse { line, reason }
+}
+
+impl MatrixMarketHeader {
+    pub fn new(
+        format: MatrixMarketFormat,
+        field: MatrixMarketField,
+        symmetry: MatrixMarketSymmetry,
+    ) -> Result<Self, &'static str> {
+        let header = MatrixMarketHeader {
+            format,
+            field,
+            symmetry,
+        };
+        header.validate()?;
+        Ok(header)
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        // combinations forbidden by the specification
+        if self.field == MatrixMarketField::Pattern {
+            if self.format == MatrixMarketFormat::Array {
+                return Err("Pattern matrices have to use the coordinate format");
+            }
+            if self.symmetry == MatrixMarketSymmetry::Hermitian
+                || self.symmetry == MatrixMarketSymmetry::SkewSymmetric
+            {
+                return Err("Pattern matrices can only be general or symmetric");
+            }
+        }
+        if self.symmetry == MatrixMarketSymmetry::Hermitian && self.field != MatrixMarketField::Complex {
+            return Err("Hermitian matrices need a complex field");
+        }
+        Ok(())
+    }
+
+    fn parse(line: &str) -> Result<Self, &'static str> {
+        let tokens: Vec<String> = line.split_whitespace().map(|token| token.to_lowercase()).collect();
+        if tokens.first().map(String::as_str) != Some("%%matrixmarket") {
+            return Err("Missing %%MatrixMarket banner");
+        }
+        if tokens.len() != 5 {
+            return Err("Banner has to consist of object, format, field and symmetry");
+        }
+        if tokens[1] != "matrix" {
+            return Err("Only matrix objects are supported");
+        }
+        let format = match tokens[2].as_str() {
+            "coordinate" => MatrixMarketFormat::Coordinate,
+            "array" => MatrixMarketFormat::Array,
+            _ => return Err("Unknown format, expected coordinate or array"),
+        };
+        let field = match tokens[3].as_str() {
+            "real" | "double" => MatrixMarketField::Real,
+            "complex" => MatrixMarketField::Complex,
+            "integer" => MatrixMarketField::Integer,
+            "pattern" => MatrixMarketField::Pattern,
+            _ => return Err("Unknown field, expected real, complex, integer or pattern"),
+        };
+        let symmetry = match tokens[4].as_str() {
+            "general" => MatrixMarketSymmetry::General,
+            "symmetric" => MatrixMarketSymmetry::Symmetric,
+            "hermitian" => MatrixMarketSymmetry::Hermitian,
+            "skew-symmetric" => MatrixMarketSymmetry::SkewSymmetric,
+            _ => return Err("Unknown symmetry, expected general, symmetric, hermitian or skew-symmetric"),
+        };
+        MatrixMarketHeader::new(format, field, symmetry)
+    }
+
+    fn banner(&self) -> String {
+        let format = match self.format {
+            MatrixMarketFormat::Coordinate => "coordinate",
+            MatrixMarketFormat::Array => "array",
+        };
+        let field = match self.field {
+            MatrixMarketField::Real => "real",
+            MatrixMarketField::Complex => "complex",
+            MatrixMarketField::Integer => "integer",
+            MatrixMarketField::Pattern => "pattern",
+        };
+        let symmetry = match self.symmetry {
+            MatrixMarketSymmetry::General => "general",
+            MatrixMarketSymmetry::Symmetric => "symmetric",
+            MatrixMarketSymmetry::Hermitian => "hermitian",
+            MatrixMarketSymmetry::SkewSymmetric => "skew-symmetric",
+        };
+        format!("%%MatrixMarket matrix {} {} {}", format, field, symmetry)
+    }
+
+    fn mirror(&self, element: &ComplexNum) -> ComplexNum {
+        // element at (j, i) implied by the element at (i, j)
+        let mut mirrored = element.clone();
+        match self.symmetry {
+            MatrixMarketSymmetry::General | MatrixMarketSymmetry::Symmetric => {}
+            MatrixMarketSymmetry::Hermitian => mirrored.conjugate(),
+            MatrixMarketSymmetry::SkewSymmetric => mirrored.scale(-1.0),
+        }
+        mirrored
+    }
+}
+
+fn parse_value(tokens: &[&str], field: MatrixMarketField, line: usize) -> Result<ComplexNum, MatrixMarketError> {
+    let expected = match field {
+        MatrixMarketField::Real | MatrixMarketField::Integer => 1,
+        MatrixMarketField::Complex => 2,
+        MatrixMarketField::Pattern => 0,
+    };
+    if tokens.len() != expected {
+        return Err(parse_error(line, "Number of values does not match the field"));
+    }
+    let real = |token: &str| -> Result<FloatType, MatrixMarketError> {
+        token
+            .parse::<f64>()
+            .map(|value| value as FloatType)
+            .map_err(|_| parse_error(line, "Invalid real number"))
+    };
+    match field {
+        MatrixMarketField::Real => Ok(Complex::from(real(tokens[0])?, 0.0)),
+        MatrixMarketField::Complex => Ok(Complex::from(real(tokens[0])?, real(tokens[1])?)),
+        MatrixMarketField::Integer => tokens[0]
+            .parse::<i64>()
+            .map(|value| Complex::from(value as FloatType, 0.0))
+            .map_err(|_| parse_error(line, "Invalid integer")),
+        MatrixMarketField::Pattern => Ok(Complex::from(1.0, 0.0)),
+    }
+}
+
+fn parse_index(token: &str, size: usize, line: usize) -> Result<usize, MatrixMarketError> {
+    // indices in the file start at 1
+    match token.parse::<usize>() {
+        Ok(index) if index >= 1 && index <= size => Ok(index - 1),
+        Ok(_) => Err(parse_error(line, "Index is out of bounds")),
+        Err(_) => Err(parse_error(line, "Invalid index")),
+    }
+}
+
+pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<(MatrixMarketHeader, Matrix), MatrixMarketError> {
+    // comment lines start with % and blank lines are ignored
+    let mut lines = reader.lines().enumerate().map(|(index, line)| (index + 1, line));
+    let header = match lines.next() {
+        Some((number, line)) => MatrixMarketHeader::parse(&line?).map_err(|reason| parse_error(number, reason))?,
+        None => return Err(parse_error(1, "Missing %%MatrixMarket banner")),
+    };
+
+    let mut data_lines = Vec::new();
+    for (number, line) in lines {
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('%') {
+            data_lines.push((number, trimmed.to_string()));
+        }
+    }
+    let mut data_lines = data_lines.into_iter();
+
+    let (size_line, size) = data_lines.next().ok_or(parse_error(1, "Missing size line"))?;
+    let sizes: Vec<usize> = size
+        .split_whitespace()
+        .map(|token| token.parse::<usize>().map_err(|_| parse_error(size_line, "Invalid size")))
+        .collect::<Result<_, _>>()?;
+    let expected_sizes = match header.format {
+        MatrixMarketFormat::Coordinate => 3,
+        MatrixMarketFormat::Array => 2,
+    };
+    if sizes.len() != expected_sizes {
+        return Err(parse_error(size_line, "Size line has the wrong number of entries"));
+    }
+    let (num_rows, num_cols) = (sizes[0], sizes[1]);
+    let symmetric = header.symmetry != MatrixMarketSymmetry::General;
+    if symmetric && num_rows != num_cols {
+        return Err(parse_error(size_line, "Symmetric matrices have to be quadratic"));
+    }
+
+    // the matrix is stored densely, so the size line is checked before
+    // anything is allocated; array files have to provide every value
+    let count = num_rows
+        .checked_mul(num_cols)
+        .filter(|count| *count <= MAX_DENSE_ELEMENTS)
+        .ok_or(parse_error(size_line, "Matrix is too large to be stored densely"))?;
+    let stored = match header.symmetry {
+        MatrixMarketSymmetry::General => count,
+        MatrixMarketSymmetry::SkewSymmetric => num_rows * num_rows.saturating_sub(1) / 2,
+        _ => num_rows * (num_rows + 1) / 2,
+    };
+    if header.format == MatrixMarketFormat::Array && stored > data_lines.len() {
+        return Err(parse_error(size_line, "Fewer entries than expected"));
+    }
+
+    let mut matrix = Matrix::zeros(num_rows, num_cols);
+    let mut last_line = size_line;
+    match header.format {
+        MatrixMarketFormat::Coordinate => {
+            for _ in 0..sizes[2] {
+                let (number, line) = data_lines.next().ok_or(parse_error(last_line, "Fewer entries than announced"))?;
+                last_line = number;
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                if tokens.len() < 2 {
+                    return Err(parse_error(number, "Entry is missing its indices"));
+                }
+                let row_index = parse_index(tokens[0], num_rows, number)?;
+                let column_index = parse_index(tokens[1], num_cols, number)?;
+                let element = parse_value(&tokens[2..], header.field, number)?;
+                if symmetric && column_index > row_index {
+                    return Err(parse_error(number, "Symmetric matrices may only store the lower triangle"));
+                }
+                if header.symmetry == MatrixMarketSymmetry::SkewSymmetric && row_index == column_index {
+                    return Err(parse_error(number, "Skew-symmetric matrices may not store the diagonal"));
+                }
+                if symmetric && row_index != column_index {
+                    matrix.set_element(column_index, row_index, header.mirror(&element)).unwrap();
+                }
+                matrix.set_element(row_index, column_index, element).unwrap();
+            }
+        }
+        MatrixMarketFormat::Array => {
+            // column major, symmetric matrices store the lower triangle and
+            // skew-symmetric matrices the part below the diagonal
+            for column_index in 0..num_cols {
+                let first_row = match header.symmetry {
+                    MatrixMarketSymmetry::General => 0,
+                    MatrixMarketSymmetry::SkewSymmetric => column_index + 1,
+                    _ => column_index,
+                };
+                for row_index in first_row..num_rows {
+                    let (number, line) = data_lines.next().ok_or(parse_error(last_line, "Fewer entries than expected"))?;
+                    last_line = number;
+                    let tokens: Vec<&str> = line.split_whitespace().collect();
+                    let element = parse_value(&tokens, header.field, number)?;
+                    if symmetric && row_index != column_index {
+                        matrix.set_element(column_index, row_index, header.mirror(&element)).unwrap();
+                    }
+                    matrix.set_element(row_index, column_index, element).unwrap();
+                }
+            }
+        }
+    }
+    if let Some((number, _)) = data_lines.next() {
+        return Err(parse_error(number, "More entries than expected"));
+    }
+    Ok((header, matrix))
+}
+
+fn format_value(element: &ComplexNum, field: MatrixMarketField) -> Result<String, MatrixMarketError> {
+    if field != MatrixMarketField::Complex && element.imaginary != 0.0 {
+        return Err(MatrixMarketError::Write("Complex elements need a complex field"));
+    }
+    match field {
+        MatrixMarketField::Real => Ok(format!("{}", element.real)),
+        MatrixMarketField::Complex => Ok(format!("{} {}", element.real, element.imaginary)),
+        MatrixMarketField::Integer => {
+            if element.real.fract() != 0.0 {
+                return Err(MatrixMarketError::Write("Integer fields need integral elements"));
+            }
+            Ok(format!("{}", element.real as i64))
+        }
+        MatrixMarketField::Pattern => Ok(String::new()),
+    }
+}
+
+pub fn write_matrix_market<W: Write>(
+    matrix: &Matrix,
+    header: &MatrixMarketHeader,
+    writer: &mut W,
+) -> Result<(), MatrixMarketError> {
+    // writes the matrix in the given representation; symmetric headers
+    // require the matrix to have that symmetry since only the lower
+    // triangle is written
+    header.validate().map_err(MatrixMarketError::Write)?;
+    let symmetric = header.symmetry != MatrixMarketSymmetry::General;
+    if symmetric && !matrix.is_quadratic() {
+        return Err(MatrixMarketError::Write("Symmetric matrices have to be quadratic"));
+    }
+
+    // stored entries in column major order
+    let mut entries = Vec::new();
+    for column_index in 0..matrix.num_columns {
+        let first_row = match header.symmetry {
+            MatrixMarketSymmetry::General => 0,
+            MatrixMarketSymmetry::SkewSymmetric => column_index + 1,
+            _ => column_index,
+        };
+        for row_index in first_row..matrix.num_rows {
+            let element = matrix.get_element(row_index, column_index).unwrap();
+            if symmetric {
+                let mirrored = header.mirror(&element);
+                let transposed = matrix.get_element(column_index, row_index).unwrap();
+                if mirrored.real != transposed.real || mirrored.imaginary != transposed.imaginary {
+                    return Err(MatrixMarketError::Write("Matrix does not have the symmetry of the header"));
+                }
+            }
+            entries.push((row_index, column_index, element));
+        }
+    }
+    if header.symmetry == MatrixMarketSymmetry::SkewSymmetric {
+        for index in 0..matrix.num_rows {
+            if matrix.get_element(index, index).unwrap().norm() != 0.0 {
+                return Err(MatrixMarketError::Write("Skew-symmetric matrices need a zero diagonal"));
+            }
+        }
+    }
+    if header.field == MatrixMarketField::Pattern
+        && entries.iter().any(|(_, _, element)| element.real != 0.0 && element.real != 1.0)
+    {
+        return Err(MatrixMarketError::Write("Pattern matrices may only contain zeros and ones"));
+    }
+
+    // all entries are formatted before anything is written, so a failing
+    // element leaves the writer untouched
+    if header.format == MatrixMarketFormat::Coordinate {
+        entries.retain(|(_, _, element)| element.norm() != 0.0);
+    }
+    let mut lines = Vec::with_capacity(entries.len());
+    for (row_index, column_index, element) in &entries {
+        let value = format_value(element, header.field)?;
+        lines.push(match header.format {
+            MatrixMarketFormat::Coordinate if value.is_empty() => format!("{} {}", row_index + 1, column_index + 1),
+            MatrixMarketFormat::Coordinate => format!("{} {} {}", row_index + 1, column_index + 1, value),
+            MatrixMarketFormat::Array => value,
+        });
+    }
+
+    writeln!(writer, "{}", header.banner())?;
+    match header.format {
+        MatrixMarketFormat::Coordinate => writeln!(writer, "{} {} {}", matrix.num_rows, matrix.num_columns, lines.len())?,
+        MatrixMarketFormat::Array => writeln!(writer, "{} {}", matrix.num_rows, matrix.num_columns)?,
+    }
+    for line in &lines {
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+impl Matrix {
+    pub fn read_matrix_market_file<P: AsRef<Path>>(path: P) -> Result<Self, MatrixMarketError> {
+        let (_, matrix) = read_matrix_market(BufReader::new(File::open(path)?))?;
+        Ok(matrix)
+    }
+
+    pub fn write_matrix_market_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        header: &MatrixMarketHeader,
+    ) -> Result<(), MatrixMarketError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_matrix_market(self, header, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,469 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Reading and writing the NumPy .npy format and reading arrays from
+// uncompressed .npz archives
+// https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+// the header of version 1 files is padded so the data starts at a
+// multiple of this alignment
+const HEADER_ALIGNMENT: usize = 64;
+// elements read at once, so that a corrupt shape can not allocate
+// more memory than the file provides
+const CHUNK_ELEMENTS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpyDtype {
+    Float32,
+    Float64,
+    Complex64,
+    Complex128,
+}
+
+impl NpyDtype {
+    fn parse(descr: &str) -> Result<(Self, bool), NpyError> {
+        // returns the dtype and whether it is stored big endian;
+        // = means native byte order
+        let (order, kind) = descr.split_at(descr.len().min(1));
+        let big_endian = match order {
+            "<" => false,
+            ">" => true,
+            "=" => cfg!(target_endian = "big"),
+            _ => return Err(NpyError::Format("Byte order of the dtype is missing")),
+        };
+        let dtype = match kind {
+            "f4" => NpyDtype::Float32,
+            "f8" => NpyDtype::Float64,
+            "c8" => NpyDtype::Complex64,
+            "c16" => NpyDtype::Complex128,
+            _ => return Err(NpyError::Format("Unsupported dtype, expected f4, f8, c8 or c16")),
+        };
+        Ok((dtype, big_endian))
+    }
+
+    fn descr(&self) -> &'static str {
+        // arrays are always written little endian
+        match self {
+            NpyDtype::Float32 => "<f4",
+            NpyDtype::Float64 => "<f8",
+            NpyDtype::Complex64 => "<c8",
+            NpyDtype::Complex128 => "<c16",
+        }
+    }
+
+    fn item_size(&self) -> usize {
+        match self {
+            NpyDtype::Float32 => 4,
+            NpyDtype::Float64 | NpyDtype::Complex64 => 8,
+            NpyDtype::Complex128 => 16,
+        }
+    }
+
+    fn is_complex(&self) -> bool {
+        matches!(self, NpyDtype::Complex64 | NpyDtype::Complex128)
+    }
+}
+
+#[derive(Debug)]
+pub enum NpyError {
+    Io(io::Error),
+    // malformed or unsupported file contents
+    Format(&'static str),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NpyError::Io(error) => write!(f, "{}", error),
+            NpyError::Format(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for NpyError {}
+
+impl From<io::Error> for NpyError {
+    fn from(error: io::Error) -> Self {
+        NpyError::Io(error)
+    }
+}
+
+// array as stored in a file, elements in row major order
+struct NpyArray {
+    shape: Vec<usize>,
+    elements: Vec<ComplexNum>,
+}
+
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    // text following 'key': in the python dict literal of the header
+    let quoted = [format!("'{}'", key), format!("\"{}\"", key)];
+    let position = quoted
+        .iter()
+        .find_map(|quoted| header.find(quoted.as_str()).map(|position| position + quoted.len()))
+        .ok_or(NpyError::Format("Header is missing a key"))?;
+    header[position..]
+        .trim_start()
+        .strip_prefix(':')
+        .map(str::trim_start)
+        .ok_or(NpyError::Format("Header is not a valid dictionary"))
+}
+
+fn parse_header(header: &str) -> Result<(NpyDtype, bool, bool, Vec<usize>), NpyError> {
+    // returns dtype, big endian, fortran order and shape
+    let descr = header_value(header, "descr")?;
+    let quote = descr.chars().next().filter(|c| *c == '\'' || *c == '"');
+    let descr = quote
+        .and_then(|quote| descr[1..].split(quote).next())
+        .ok_or(NpyError::Format("Dtype has to be a string"))?;
+    let (dtype, big_endian) = NpyDtype::parse(descr)?;
+
+    let fortran_value = header_value(header, "fortran_order")?;
+    let fortran_order = if fortran_value.starts_with("True") {
+        true
+    } else if fortran_value.starts_with("False") {
+        false
+    } else {
+        return Err(NpyError::Format("Fortran order has to be True or False"));
+    };
+
+    let shape_value = header_value(header, "shape")?;
+    let shape = shape_value
+        .strip_prefix('(')
+        .and_then(|shape| shape.split(')').next())
+        .ok_or(NpyError::Format("Shape has to be a tuple"))?;
+    let shape: Vec<usize> = shape
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse::<usize>().map_err(|_| NpyError::Format("Invalid dimension in shape")))
+        .collect::<Result<_, _>>()?;
+    Ok((dtype, big_endian, fortran_order, shape))
+}
+
+fn read_float<const N: usize>(bytes: &[u8], big_endian: bool) -> [u8; N] {
+    let mut buffer = [0u8; N];
+    buffer.copy_from_slice(bytes);
+    if big_endian {
+        buffer.reverse();
+    }
+    buffer
+}
+
+fn decode_element(bytes: &[u8], dtype: NpyDtype, big_endian: bool) -> ComplexNum {
+    // converts to little endian before decoding
+    let f32_at = |offset: usize| f32::from_le_bytes(read_float::<4>(&bytes[offset..offset + 4], big_endian));
+    let f64_at = |offset: usize| f64::from_le_bytes(read_float::<8>(&bytes[offset..offset + 8], big_endian));
+    match dtype {
+        NpyDtype::Float32 => Complex::from(f32_at(0), 0.0),
+        NpyDtype::Float64 => Complex::from(f64_at(0) as FloatType, 0.0),
+        NpyDtype::Complex64 => Complex::from(f32_at(0), f32_at(4)),
+        NpyDtype::Complex128 => Complex::from(f64_at(0) as FloatType, f64_at(8) as FloatType),
+    }
+}
+
+fn read_array<R: Read>(reader: &mut R) -> Result<NpyArray, NpyError> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..6] != MAGIC {
+        return Err(NpyError::Format("Missing NumPy magic string"));
+    }
+    let header_length = match magic[6] {
+        1 => {
+            let mut length = [0u8; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_le_bytes(length) as usize
+        }
+        2 | 3 => {
+            let mut length = [0u8; 4];
+            reader.read_exact(&mut length)?;
+            u32::from_le_bytes(length) as usize
+        }
+        _ => return Err(NpyError::Format("Unsupported format version")),
+    };
+    let mut header = vec![0u8; header_length];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header).map_err(|_| NpyError::Format("Header is not valid text"))?;
+    let (dtype, big_endian, fortran_order, shape) = parse_header(&header)?;
+
+    let count = shape
+        .iter()
+        .try_fold(1usize, |count, length| count.checked_mul(*length))
+        .filter(|count| count.checked_mul(dtype.item_size()).is_some())
+        .ok_or(NpyError::Format("Shape is too large"))?;
+    let mut elements = Vec::with_capacity(count.min(CHUNK_ELEMENTS));
+    let mut buffer = vec![0u8; CHUNK_ELEMENTS * dtype.item_size()];
+    let mut remaining = count;
+    while remaining > 0 {
+        let chunk = remaining.min(CHUNK_ELEMENTS);
+        let bytes = &mut buffer[..chunk * dtype.item_size()];
+        reader.read_exact(bytes)?;
+        elements.extend(
+            bytes
+                .chunks_exact(dtype.item_size())
+                .map(|bytes| decode_element(bytes, dtype, big_endian)),
+        );
+        remaining -= chunk;
+    }
+
+    // fortran order stores the first index fastest, which is the
+    // transpose of row major order for matrices
+    if fortran_order && shape.len() == 2 {
+        let (num_rows, num_cols) = (shape[0], shape[1]);
+        elements = (0..count)
+            .map(|index| elements[(index % num_cols) * num_rows + index / num_cols].clone())
+            .collect();
+    } else if fortran_order && shape.len() > 2 {
+        return Err(NpyError::Format("Arrays with more than two dimensions are not supported"));
+    }
+    Ok(NpyArray { shape, elements })
+}
+
+fn write_array<W: Write>(writer: &mut W, shape: &[usize], elements: &[ComplexNum], dtype: NpyDtype) -> Result<(), NpyError> {
+    // version 1.0, little endian and row major; checked before writing
+    // so that no partial array is written
+    if !dtype.is_complex() && elements.iter().any(|element| element.imaginary != 0.0) {
+        return Err(NpyError::Format("Complex elements need a complex dtype"));
+    }
+    let shape = match shape {
+        [length] => format!("({},)", length),
+        _ => format!("({})", shape.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", dtype.descr(), shape);
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((HEADER_ALIGNMENT - unpadded % HEADER_ALIGNMENT) % HEADER_ALIGNMENT));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for element in elements {
+        match dtype {
+            NpyDtype::Float32 => writer.write_all(&element.real.to_le_bytes())?,
+            NpyDtype::Float64 => writer.write_all(&(element.real as f64).to_le_bytes())?,
+            NpyDtype::Complex64 => {
+                writer.write_all(&element.real.to_le_bytes())?;
+                writer.write_all(&element.imaginary.to_le_bytes())?;
+            }
+            NpyDtype::Complex128 => {
+                writer.write_all(&(element.real as f64).to_le_bytes())?;
+                writer.write_all(&(element.imaginary as f64).to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Matrix {
+    pub fn read_npy<R: Read>(reader: &mut R) -> Result<Self, NpyError> {
+        let array = read_array(reader)?;
+        match array.shape[..] {
+            [num_rows, num_cols] => Ok(Matrix::from_array(num_rows, num_cols, &array.elements).unwrap()),
+            _ => Err(NpyError::Format("Matrices have to be stored as two dimensional arrays")),
+        }
+    }
+
+    pub fn write_npy<W: Write>(&self, writer: &mut W, dtype: NpyDtype) -> Result<(), NpyError> {
+        let elements: Vec<ComplexNum> = (0..self.num_rows)
+            .flat_map(|row_index| (0..self.num_columns).map(move |column_index| (row_index, column_index)))
+            .map(|(row_index, column_index)| self.get_element(row_index, column_index).unwrap())
+            .collect();
+        write_array(writer, &[self.num_rows, self.num_columns], &elements, dtype)
+    }
+
+    pub fn load_npy<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        Matrix::read_npy(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn save_npy<P: AsRef<Path>>(&self, path: P, dtype: NpyDtype) -> Result<(), NpyError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer, dtype)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Vector {
+    pub fn read_npy<R: Read>(reader: &mut R) -> Result<Self, NpyError> {
+        // one dimensional arrays and n x 1 arrays are read as column
+        // vectors, 1 x n arrays as row vectors
+        let array = read_array(reader)?;
+        match array.shape[..] {
+            [_] | [_, 1] => Ok(Vector::from(&array.elements, VectorType::COLUMN_VECTOR)),
+            [1, _] => Ok(Vector::from(&array.elements, VectorType::ROW_VECTOR)),
+            _ => Err(NpyError::Format("Vectors have to be stored as one dimensional arrays")),
+        }
+    }
+
+    pub fn write_npy<W: Write>(&self, writer: &mut W, dtype: NpyDtype) -> Result<(), NpyError> {
+        // written as a one dimensional array
+        let elements: Vec<ComplexNum> = self.iter().collect();
+        write_array(writer, &[self.size()], &elements, dtype)
+    }
+
+    pub fn load_npy<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        Vector::read_npy(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn save_npy<P: AsRef<Path>>(&self, path: P, dtype: NpyDtype) -> Result<(), NpyError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer, dtype)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// .npz archives are zip files with one .npy file per array, as
+// written by numpy.savez; compressed archives are not supported
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_ENTRY: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+const ZIP64_EXTRA_FIELD: u16 = 0x0001;
+// sizes and offsets with this value are stored in the zip64 extra field
+const ZIP64_MARKER: u32 = 0xffff_ffff;
+
+fn bytes_at(data: &[u8], offset: usize, length: usize) -> Result<&[u8], NpyError> {
+    // offsets come from the archive, so the end is computed with a checked add
+    offset
+        .checked_add(length)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(NpyError::Format("Archive is truncated"))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Result<u16, NpyError> {
+    bytes_at(data, offset, 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32, NpyError> {
+    bytes_at(data, offset, 4).map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Result<u64, NpyError> {
+    Ok(u32_at(data, offset)? as u64 | (u32_at(data, offset + 4)? as u64) << 32)
+}
+
+struct NpzEntry {
+    name: String,
+    start: usize,
+    size: usize,
+}
+
+pub struct NpzArchive {
+    data: Vec<u8>,
+    entries: Vec<NpzEntry>,
+}
+
+impl NpzArchive {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        NpzArchive::from_bytes(data)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, NpyError> {
+        // the end of central directory record is at the end of the file,
+        // followed by a comment of at most 65535 bytes
+        let end = (0..=data.len().saturating_sub(22))
+            .rev()
+            .take(22 + 0xffff)
+            .find(|offset| u32_at(&data, *offset).ok() == Some(END_OF_CENTRAL_DIRECTORY))
+            .ok_or(NpyError::Format("Not a zip archive"))?;
+        let num_entries = u16_at(&data, end + 10)? as usize;
+        let mut offset = u32_at(&data, end + 16)? as usize;
+
+        let mut entries = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            if u32_at(&data, offset)? != CENTRAL_DIRECTORY_ENTRY {
+                return Err(NpyError::Format("Corrupt central directory"));
+            }
+            let method = u16_at(&data, offset + 10)?;
+            let mut compressed_size = u32_at(&data, offset + 20)? as u64;
+            let mut size = u32_at(&data, offset + 24)? as u64;
+            let name_length = u16_at(&data, offset + 28)? as usize;
+            let extra_length = u16_at(&data, offset + 30)? as usize;
+            let comment_length = u16_at(&data, offset + 32)? as usize;
+            let mut local_offset = u32_at(&data, offset + 42)? as u64;
+            let name = data
+                .get(offset + 46..offset + 46 + name_length)
+                .ok_or(NpyError::Format("Archive is truncated"))?;
+            let name = String::from_utf8_lossy(name).into_owned();
+
+            // the zip64 extra field holds the values marked in the entry,
+            // in the order uncompressed size, compressed size, offset
+            let mut extra = offset + 46 + name_length;
+            let extra_end = extra + extra_length;
+            while extra + 4 <= extra_end {
+                let id = u16_at(&data, extra)?;
+                let length = u16_at(&data, extra + 2)? as usize;
+                if id == ZIP64_EXTRA_FIELD {
+                    let mut field = extra + 4;
+                    for value in [&mut size, &mut compressed_size, &mut local_offset] {
+                        if *value == ZIP64_MARKER as u64 {
+                            *value = u64_at(&data, field)?;
+                            field += 8;
+                        }
+                    }
+                }
+                extra += 4 + length;
+            }
+            if method != 0 || compressed_size != size {
+                return Err(NpyError::Format("Compressed npz archives are not supported"));
+            }
+
+            // zip64 values are not bounded by the archive, u32_at rejects
+            // offsets past its end before anything is added to them
+            let local_offset = usize::try_from(local_offset).map_err(|_| NpyError::Format("Archive is truncated"))?;
+            if u32_at(&data, local_offset)? != LOCAL_FILE_HEADER {
+                return Err(NpyError::Format("Corrupt local file header"));
+            }
+            let start = local_offset
+                + 30
+                + u16_at(&data, local_offset + 26)? as usize
+                + u16_at(&data, local_offset + 28)? as usize;
+            let size = usize::try_from(size).map_err(|_| NpyError::Format("Archive is truncated"))?;
+            if start.checked_add(size).is_none_or(|end| end > data.len()) {
+                return Err(NpyError::Format("Archive is truncated"));
+            }
+            entries.push(NpzEntry { name, start, size });
+            offset += 46 + name_length + extra_length + comment_length;
+        }
+        Ok(NpzArchive { data, entries })
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        // names of the arrays without the .npy extension
+        self.entries
+            .iter()
+            .map(|entry| entry.name.strip_suffix(".npy").unwrap_or(&entry.name))
+            .collect()
+    }
+
+    fn entry_data(&self, name: &str) -> Result<&[u8], NpyError> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name || entry.name.strip_suffix(".npy") == Some(name))
+            .ok_or(NpyError::Format("Archive has no array of that name"))?;
+        Ok(&self.data[entry.start..entry.start + entry.size])
+    }
+
+    pub fn matrix(&self, name: &str) -> Result<Matrix, NpyError> {
+        Matrix::read_npy(&mut self.entry_data(name)?)
+    }
+
+    pub fn vector(&self, name: &str) -> Result<Vector, NpyError> {
+        Vector::read_npy(&mut self.entry_data(name)?)
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Loops over independent items, run on the rayon thread pool if the
+// parallel feature is enabled and the work is large enough to pay for
+// the threads. Every item is computed exactly as in the serial loop, so
+// both paths give bit-identical results.
+
+// number of scalar operations below which loops stay serial
+#[cfg(feature = "parallel")]
+pub(crate) const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+#[cfg(feature = "parallel")]
+fn is_parallel(work: usize) -> bool {
+    work >= PARALLEL_THRESHOLD && rayon::current_num_threads() > 1
+}
+
+pub(crate) fn for_each_mut<T, G>(items: &mut [T], work: usize, function: G)
+where
+    T: Send,
+    G: Fn(usize, &mut T) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if is_parallel(work) {
+        items.par_iter_mut().enumerate().for_each(|(index, item)| function(index, item));
+        return;
+    }
+    let _ = work;
+    items.iter_mut().enumerate().for_each(|(index, item)| function(index, item));
+}
+
+pub(crate) fn for_each_chunk_mut<T, G>(items: &mut [T], chunk_size: usize, work: usize, function: G)
+where
+    T: Send,
+    G: Fn(usize, &mut [T]) + Sync + Send,
+{
+    // function receives the index of the first item of the chunk
+    #[cfg(feature = "parallel")]
+    if is_parallel(work) {
+        items
+            .par_chunks_mut(chunk_size)
+            .enumerate()
+            .for_each(|(index, chunk)| function(index * chunk_size, chunk));
+        return;
+    }
+    let _ = work;
+    items
+        .chunks_mut(chunk_size)
+        .enumerate()
+        .for_each(|(index, chunk)| function(index * chunk_size, chunk));
+}
+
+pub(crate) fn map_collect<R, G>(count: usize, work: usize, function: G) -> Vec<R>
+where
+    R: Send,
+    G: Fn(usize) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if is_parallel(work) {
+        return (0..count).into_par_iter().map(function).collect();
+    }
+    let _ = work;
+    (0..count).map(function).collect()
+}
@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use num::traits::FloatConst;
+use num::Float;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * Error of parsing a number, vector or matrix from text
+ * The position is the index of the offending character in the input
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub reason: &'static str,
+}
+
+impl ParseError {
+    fn new(position: usize, reason: &'static str) -> Self {
+        ParseError { position, reason }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.reason, self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+// Complex numbers
+// Accepted forms are a real part, an imaginary part or both joined by
+// a sign (3, -2.5i, 1+2i, 1.5e-3 - 4j, -i) and the polar form 2∠0.5
+
+impl<F: Float + FloatConst> FromStr for Complex<F> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = text.chars().collect();
+        parse_complex(&chars, 0)
+    }
+}
+
+fn is_operator(c: char) -> bool {
+    c == '+' || c == '-' || c == '∠'
+}
+
+fn trim(chars: &[char], offset: usize) -> (&[char], usize) {
+    // strips surrounding whitespace and moves the offset accordingly
+    let start = chars.iter().take_while(|c| c.is_whitespace()).count();
+    let end = chars.len() - chars[start..].iter().rev().take_while(|c| c.is_whitespace()).count();
+    (&chars[start..end], offset + start)
+}
+
+fn scan_number(chars: &[char], start: usize) -> usize {
+    // end of the unsigned decimal number starting at start, including
+    // an exponent; the number itself is validated by the float parser
+    let mut index = start;
+    while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+        index += 1;
+    }
+    if index > start && index < chars.len() && (chars[index] == 'e' || chars[index] == 'E') {
+        let mut exponent = index + 1;
+        if exponent < chars.len() && (chars[exponent] == '+' || chars[exponent] == '-') {
+            exponent += 1;
+        }
+        if exponent < chars.len() && chars[exponent].is_ascii_digit() {
+            index = exponent;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+        }
+    }
+    index
+}
+
+fn parse_real<F: Float>(chars: &[char], offset: usize) -> Result<F, ParseError> {
+    let (chars, offset) = trim(chars, offset);
+    if chars.is_empty() {
+        return Err(ParseError::new(offset, "Expected a number"));
+    }
+    let start = if chars[0] == '+' || chars[0] == '-' { 1 } else { 0 };
+    let end = scan_number(chars, start);
+    if end == start {
+        return Err(ParseError::new(offset + start, "Expected a number"));
+    }
+    if end < chars.len() {
+        return Err(ParseError::new(offset + end, "Unexpected character"));
+    }
+    let number: f64 = chars.iter().collect::<String>().parse()
+        .map_err(|_| ParseError::new(offset, "Invalid number"))?;
+    F::from(number).ok_or(ParseError::new(offset, "Number is out of range"))
+}
+
+fn parse_complex<F: Float + FloatConst>(chars: &[char], offset: usize) -> Result<Complex<F>, ParseError> {
+    let (chars, offset) = trim(chars, offset);
+    if chars.is_empty() {
+        return Err(ParseError::new(offset, "Expected a number"));
+    }
+    if let Some(angle_index) = chars.iter().position(|c| *c == '∠') {
+        let norm = parse_real(&chars[..angle_index], offset)?;
+        let angle = parse_real(&chars[angle_index + 1..], offset + angle_index + 1)?;
+        return Ok(Complex::from_polar(norm, angle));
+    }
+
+    let mut real: Option<F> = None;
+    let mut imaginary: Option<F> = None;
+    let mut index = 0;
+    while index < chars.len() {
+        // every term but the first has to start with a sign
+        let term_start = index;
+        let negative = chars[index] == '-';
+        if chars[index] == '+' || chars[index] == '-' {
+            index += 1;
+            while index < chars.len() && chars[index].is_whitespace() {
+                index += 1;
+            }
+        } else if term_start > 0 {
+            return Err(ParseError::new(offset + index, "Expected '+' or '-'"));
+        }
+
+        let number_start = index;
+        index = scan_number(chars, index);
+        let mut value = if index > number_start {
+            let number: f64 = chars[number_start..index].iter().collect::<String>().parse()
+                .map_err(|_| ParseError::new(offset + number_start, "Invalid number"))?;
+            F::from(number).ok_or(ParseError::new(offset + number_start, "Number is out of range"))?
+        } else {
+            F::one()
+        };
+        if negative {
+            value = -value;
+        }
+
+        let is_imaginary = index < chars.len() && (chars[index] == 'i' || chars[index] == 'j');
+        if is_imaginary {
+            index += 1;
+        } else if index == number_start {
+            return Err(ParseError::new(offset + index, "Expected a number"));
+        }
+
+        let part = if is_imaginary { &mut imaginary } else { &mut real };
+        if part.is_some() {
+            let reason = if is_imaginary { "Duplicate imaginary part" } else { "Duplicate real part" };
+            return Err(ParseError::new(offset + term_start, reason));
+        }
+        *part = Some(value);
+
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+    }
+    Ok(Complex::from(real.unwrap_or(F::zero()), imaginary.unwrap_or(F::zero())))
+}
+
+// Matrix and vector literals
+// MATLAB style: [1 2; 3 4i], elements are separated by commas or
+// whitespace and rows by semicolons or line breaks. As in MATLAB a
+// sign with whitespace on both sides joins its neighbours, so
+// [1 + 2i] has one element while [1 +2i] has two
+
+fn split_elements(chars: &[char], offset: usize) -> Result<Vec<ComplexNum>, ParseError> {
+    let mut elements = Vec::new();
+    let skip_whitespace = |mut index: usize| {
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+        index
+    };
+
+    let mut index = skip_whitespace(0);
+    while index < chars.len() {
+        let start = index;
+        while index < chars.len() && chars[index] != ',' {
+            if chars[index].is_whitespace() {
+                let next = skip_whitespace(index);
+                let continues = next < chars.len()
+                    && (is_operator(chars[index - 1])
+                        || chars[next] == '∠'
+                        || (is_operator(chars[next]) && next + 1 < chars.len() && chars[next + 1].is_whitespace()));
+                if !continues {
+                    break;
+                }
+                index = next;
+            } else {
+                index += 1;
+            }
+        }
+        if index == start {
+            return Err(ParseError::new(offset + index, "Expected an element"));
+        }
+        elements.push(parse_complex(&chars[start..index], offset + start)?);
+
+        index = skip_whitespace(index);
+        if index < chars.len() && chars[index] == ',' {
+            index = skip_whitespace(index + 1);
+            if index == chars.len() {
+                return Err(ParseError::new(offset + index, "Expected an element"));
+            }
+        }
+    }
+    Ok(elements)
+}
+
+fn parse_rows(text: &str) -> Result<Vec<Vec<ComplexNum>>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let (chars, offset) = trim(&chars, 0);
+    if chars.first() != Some(&'[') {
+        return Err(ParseError::new(offset, "Expected '['"));
+    }
+    if chars.len() < 2 || chars[chars.len() - 1] != ']' {
+        return Err(ParseError::new(offset + chars.len(), "Expected ']'"));
+    }
+    let content = &chars[1..chars.len() - 1];
+    if let Some(index) = content.iter().position(|c| *c == '[' || *c == ']') {
+        return Err(ParseError::new(offset + 1 + index, "Nested brackets are not supported"));
+    }
+
+    let mut rows: Vec<Vec<ComplexNum>> = Vec::new();
+    let mut row_start = 0;
+    for index in 0..=content.len() {
+        if index < content.len() && content[index] != ';' && content[index] != '\n' {
+            continue;
+        }
+        let row = split_elements(&content[row_start..index], offset + 1 + row_start)?;
+        // blank lines and trailing separators do not form rows
+        if !row.is_empty() {
+            if let Some(first) = rows.first() {
+                if first.len() != row.len() {
+                    return Err(ParseError::new(offset + 1 + row_start, "Rows have different numbers of elements"));
+                }
+            }
+            rows.push(row);
+        }
+        row_start = index + 1;
+    }
+    Ok(rows)
+}
+
+impl FromStr for Matrix {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let rows = parse_rows(text)?;
+        let num_rows = rows.len();
+        let num_cols = rows.first().map_or(0, |row| row.len());
+        let elements: Vec<ComplexNum> = rows.into_iter().flatten().collect();
+        Ok(Matrix::from_array(num_rows, num_cols, &elements).unwrap())
+    }
+}
+
+impl FromStr for Vector {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // [1, 2, 3] is a row vector and [1; 2; 3] a column vector,
+        // a single element gives a column vector
+        let rows = parse_rows(text)?;
+        match rows.len() {
+            0 => Ok(Vector::from(&[], VectorType::COLUMN_VECTOR)),
+            1 if rows[0].len() > 1 => Ok(Vector::from(&rows[0], VectorType::ROW_VECTOR)),
+            _ if rows.iter().all(|row| row.len() == 1) => {
+                let elements: Vec<ComplexNum> = rows.into_iter().flatten().collect();
+                Ok(Vector::from(&elements, VectorType::COLUMN_VECTOR))
+            }
+            _ => Err(ParseError::new(0, "Vector literal has to be a single row or column")),
+        }
+    }
+}
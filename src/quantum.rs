@@ -0,0 +1,313 @@
+use rand::Rng;
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// tolerance for the norm of a state vector
+const NORMALIZATION_TOLERANCE: FloatType = 1e-4;
+
+// Standard quantum gates as matrices
+// Multi qubit gates use the ordering of the kronecker product,
+// i.e. the first qubit corresponds to the most significant bit
+// of the basis index: CNOT = |0><0| (x) I + |1><1| (x) X
+fn gate_2x2(elements: [ComplexNum; 4]) -> Matrix {
+    Matrix::from_array(2, 2, &elements).unwrap()
+}
+
+pub fn pauli_x() -> Matrix {
+    gate_2x2([
+        Complex::from(0.0, 0.0),
+        Complex::from(1.0, 0.0),
+        Complex::from(1.0, 0.0),
+        Complex::from(0.0, 0.0),
+    ])
+}
+
+pub fn pauli_y() -> Matrix {
+    gate_2x2([
+        Complex::from(0.0, 0.0),
+        Complex::from(0.0, -1.0),
+        Complex::from(0.0, 1.0),
+        Complex::from(0.0, 0.0),
+    ])
+}
+
+pub fn pauli_z() -> Matrix {
+    gate_2x2([
+        Complex::from(1.0, 0.0),
+        Complex::from(0.0, 0.0),
+        Complex::from(0.0, 0.0),
+        Complex::from(-1.0, 0.0),
+    ])
+}
+
+pub fn hadamard() -> Matrix {
+    let factor = FloatType::sqrt(0.5);
+    gate_2x2([
+        Complex::from(factor, 0.0),
+        Complex::from(factor, 0.0),
+        Complex::from(factor, 0.0),
+        Complex::from(-factor, 0.0),
+    ])
+}
+
+pub fn phase(angle: FloatType) -> Matrix {
+    // diag(1, e^(i angle))
+    gate_2x2([
+        Complex::from(1.0, 0.0),
+        Complex::from(0.0, 0.0),
+        Complex::from(0.0, 0.0),
+        Complex::from_polar(1.0, angle),
+    ])
+}
+
+pub fn s_gate() -> Matrix {
+    phase(std::f32::consts::FRAC_PI_2)
+}
+
+pub fn t_gate() -> Matrix {
+    phase(std::f32::consts::FRAC_PI_4)
+}
+
+pub fn rotation_x(angle: FloatType) -> Matrix {
+    // exp(-i angle X / 2)
+    let (sin, cos) = FloatType::sin_cos(angle / 2.0);
+    gate_2x2([
+        Complex::from(cos, 0.0),
+        Complex::from(0.0, -sin),
+        Complex::from(0.0, -sin),
+        Complex::from(cos, 0.0),
+    ])
+}
+
+pub fn rotation_y(angle: FloatType) -> Matrix {
+    // exp(-i angle Y / 2)
+    let (sin, cos) = FloatType::sin_cos(angle / 2.0);
+    gate_2x2([
+        Complex::from(cos, 0.0),
+        Complex::from(-sin, 0.0),
+        Complex::from(sin, 0.0),
+        Complex::from(cos, 0.0),
+    ])
+}
+
+pub fn rotation_z(angle: FloatType) -> Matrix {
+    // exp(-i angle Z / 2)
+    gate_2x2([
+        Complex::from_polar(1.0, -angle / 2.0),
+        Complex::from(0.0, 0.0),
+        Complex::from(0.0, 0.0),
+        Complex::from_polar(1.0, angle / 2.0),
+    ])
+}
+
+pub fn controlled(gate: &Matrix) -> Result<Matrix, &'static str> {
+    // |0><0| (x) I + |1><1| (x) gate, the control is the first qubit
+    if !gate.is_quadratic() {
+        return Err("Only quadratic gates can be controlled");
+    }
+    let size = gate.num_rows;
+    let mut result = Matrix::identity(2 * size);
+    for row_index in 0..size {
+        for column_index in 0..size {
+            result.set_element(size + row_index, size + column_index, gate.get_element(row_index, column_index)?)?;
+        }
+    }
+    Ok(result)
+}
+
+pub fn cnot() -> Matrix {
+    controlled(&pauli_x()).unwrap()
+}
+
+pub fn toffoli() -> Matrix {
+    controlled(&cnot()).unwrap()
+}
+
+pub fn swap() -> Matrix {
+    let mut result = Matrix::zeros(4, 4);
+    for (row_index, column_index) in [(0, 0), (1, 2), (2, 1), (3, 3)] {
+        result.set_element(row_index, column_index, Complex::from(1.0, 0.0)).unwrap();
+    }
+    result
+}
+
+/**
+ * State vector of a register of qubits
+ *
+ * The amplitude of the basis state |b_0 b_1 ... b_(n-1)> is stored at
+ * the index whose binary representation is b_0 b_1 ... b_(n-1),
+ * i.e. qubit 0 is the most significant bit
+ */
+#[derive(Debug, Clone)]
+pub struct QuantumState {
+    num_qubits: usize,
+    amplitudes: Vector,
+}
+
+impl QuantumState {
+    pub fn new(num_qubits: usize) -> Self {
+        // all qubits in state |0>
+        let mut amplitudes = Vector::zeros(1 << num_qubits);
+        amplitudes.set_element(0, Complex::from(1.0, 0.0)).unwrap();
+        QuantumState {
+            num_qubits,
+            amplitudes,
+        }
+    }
+
+    pub fn from_basis_state(num_qubits: usize, index: usize) -> Result<Self, &'static str> {
+        if index >= 1 << num_qubits {
+            return Err("Basis state index exceeds the number of qubits");
+        }
+        let mut amplitudes = Vector::zeros(1 << num_qubits);
+        amplitudes.set_element(index, Complex::from(1.0, 0.0))?;
+        Ok(QuantumState {
+            num_qubits,
+            amplitudes,
+        })
+    }
+
+    pub fn from_vector(amplitudes: Vector) -> Result<Self, &'static str> {
+        let size = amplitudes.size();
+        if size == 0 || !size.is_power_of_two() {
+            return Err("Size of a state vector has to be a power of two");
+        }
+        if FloatType::abs(amplitudes.norm_l2() - 1.0) > NORMALIZATION_TOLERANCE {
+            return Err("State vector has to be normalized");
+        }
+        Ok(QuantumState {
+            num_qubits: size.trailing_zeros() as usize,
+            amplitudes,
+        })
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn amplitudes(&self) -> &Vector {
+        &self.amplitudes
+    }
+
+    pub fn apply_gate(&mut self, gate: &Matrix, targets: &[usize]) -> Result<(), &'static str> {
+        // applies a gate acting on the given qubits without building the
+        // full operator: for every assignment of the remaining qubits the
+        // 2^k amplitudes spanned by the targets are multiplied by the gate.
+        // targets[0] corresponds to the most significant bit of the gate
+        let gate_size = 1 << targets.len();
+        if gate.num_rows != gate_size || gate.num_columns != gate_size {
+            return Err("Gate dimension has to match the number of target qubits");
+        }
+        for (index, target) in targets.iter().enumerate() {
+            if *target >= self.num_qubits {
+                return Err("Target qubit exceeds the number of qubits");
+            }
+            if targets[..index].contains(target) {
+                return Err("Target qubits have to be distinct");
+            }
+        }
+
+        let masks: Vec<usize> = targets
+            .iter()
+            .map(|target| 1 << (self.num_qubits - 1 - target))
+            .collect();
+        let target_mask: usize = masks.iter().sum();
+
+        // basis index within the state for every basis index of the gate
+        let offsets: Vec<usize> = (0..gate_size)
+            .map(|gate_index| {
+                masks
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| gate_index & (1 << (targets.len() - 1 - bit)) != 0)
+                    .map(|(_, mask)| mask)
+                    .sum()
+            })
+            .collect();
+
+        let mut local_amplitudes = Vec::with_capacity(gate_size);
+        for base in 0..self.amplitudes.size() {
+            if base & target_mask != 0 {
+                continue;
+            }
+            local_amplitudes.clear();
+            for offset in &offsets {
+                local_amplitudes.push(self.amplitudes.get_element(base + offset).unwrap().clone());
+            }
+            for (row_index, offset) in offsets.iter().enumerate() {
+                let mut amplitude = Complex::new();
+                for (column_index, local) in local_amplitudes.iter().enumerate() {
+                    let product = gate.get_element(row_index, column_index)?.multiplication(local);
+                    amplitude = amplitude.addition(&product);
+                }
+                self.amplitudes.set_element(base + offset, amplitude)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn probabilities(&self) -> Vec<FloatType> {
+        // probability of measuring each basis state
+        self.amplitudes
+            .iter()
+            .map(|amplitude| FloatType::powi(amplitude.norm(), 2))
+            .collect()
+    }
+
+    pub fn qubit_probability(&self, qubit: usize) -> Result<FloatType, &'static str> {
+        // probability of measuring the given qubit in state |1>
+        if qubit >= self.num_qubits {
+            return Err("Qubit exceeds the number of qubits");
+        }
+        let mask = 1 << (self.num_qubits - 1 - qubit);
+        Ok(self
+            .probabilities()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index & mask != 0)
+            .map(|(_, probability)| probability)
+            .sum())
+    }
+
+    pub fn measure_qubit<R: Rng>(&mut self, qubit: usize, rng: &mut R) -> Result<bool, &'static str> {
+        // projective measurement of a single qubit; the state collapses
+        // onto the measured outcome and is renormalized
+        let probability_one = self.qubit_probability(qubit)?;
+        let outcome = rng.gen::<FloatType>() < probability_one;
+        let probability = if outcome { probability_one } else { 1.0 - probability_one };
+
+        let mask = 1 << (self.num_qubits - 1 - qubit);
+        for index in 0..self.amplitudes.size() {
+            if (index & mask != 0) != outcome {
+                self.amplitudes.set_element(index, Complex::new())?;
+            }
+        }
+        self.amplitudes.scale(1.0 / FloatType::sqrt(probability));
+        Ok(outcome)
+    }
+
+    pub fn sample<R: Rng>(&self, shots: usize, rng: &mut R) -> Vec<usize> {
+        // draws basis states according to their probabilities
+        // without altering the state
+        let mut cumulative = Vec::with_capacity(self.amplitudes.size());
+        let mut total: FloatType = 0.0;
+        for probability in self.probabilities() {
+            total += probability;
+            cumulative.push(total);
+        }
+
+        (0..shots)
+            .map(|_| {
+                let value = rng.gen::<FloatType>() * total;
+                cumulative
+                    .iter()
+                    .position(|bound| value < *bound)
+                    .unwrap_or(cumulative.len() - 1)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,122 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Seeded random matrix ensembles
+// Every constructor creates its own generator from the given seed,
+// so equal seeds always produce equal matrices
+
+fn standard_normal<R: Rng>(rng: &mut R) -> FloatType {
+    // box-muller transform, 1 - u keeps the logarithm finite
+    let radius = FloatType::sqrt(-2.0 * FloatType::ln(1.0 - rng.gen::<FloatType>()));
+    let angle = 2.0 * std::f32::consts::PI * rng.gen::<FloatType>();
+    radius * FloatType::cos(angle)
+}
+
+fn complex_normal<R: Rng>(rng: &mut R) -> ComplexNum {
+    // standard complex normal distribution with E|z|^2 = 1
+    let factor = FloatType::sqrt(0.5);
+    Complex::from(factor * standard_normal(rng), factor * standard_normal(rng))
+}
+
+fn ginibre<R: Rng>(num_rows: usize, num_cols: usize, rng: &mut R) -> Matrix {
+    let elements: Vec<ComplexNum> = (0..num_rows * num_cols)
+        .map(|_| complex_normal(rng))
+        .collect();
+    Matrix::from_array(num_rows, num_cols, &elements).unwrap()
+}
+
+fn hermitian_part(matrix: &Matrix) -> Matrix {
+    // (A + A^H) / 2
+    let mut adjoint = matrix.clone();
+    adjoint.adjoint();
+    let mut result = matrix.add(&adjoint).unwrap();
+    result.scale(0.5);
+    result
+}
+
+fn gram_matrix(matrix: &Matrix) -> Result<Matrix, &'static str> {
+    // G G^H of an n x m matrix G
+    let mut result = Matrix::zeros(matrix.num_rows, matrix.num_rows);
+    for row_index in 0..matrix.num_rows {
+        for column_index in 0..matrix.num_rows {
+            let mut element = Complex::new();
+            for inner_index in 0..matrix.num_columns {
+                let mut rhs = matrix.get_element(column_index, inner_index)?;
+                rhs.conjugate();
+                element = element.addition(&matrix.get_element(row_index, inner_index)?.multiplication(&rhs));
+            }
+            result.set_element(row_index, column_index, element)?;
+        }
+    }
+    Ok(result)
+}
+
+impl Matrix {
+    pub fn random_gaussian(num_rows: usize, num_cols: usize, seed: u64) -> Self {
+        // ginibre matrix with independent standard complex normal elements
+        ginibre(num_rows, num_cols, &mut StdRng::seed_from_u64(seed))
+    }
+
+    pub fn random_unitary(size: usize, seed: u64) -> Result<Self, &'static str> {
+        // haar distributed unitary: Q of the QR decomposition of a ginibre
+        // matrix, with the phases of the diagonal of R moved into Q
+        let (unitary, triangular) = Matrix::random_gaussian(size, size, seed).qr_decomposition()?;
+        let mut phases = Matrix::zeros(size, size);
+        for index in 0..size {
+            let mut phase = triangular.get_element(index, index)?;
+            let norm = phase.norm();
+            if norm == 0.0 {
+                phase = Complex::from(1.0, 0.0);
+            } else {
+                phase.scale(1.0 / norm);
+            }
+            phases.set_element(index, index, phase)?;
+        }
+        unitary.multiply(&phases)
+    }
+
+    pub fn random_gue(size: usize, seed: u64) -> Self {
+        // gaussian unitary ensemble: (G + G^H) / 2 for a ginibre matrix G
+        hermitian_part(&Matrix::random_gaussian(size, size, seed))
+    }
+
+    pub fn random_goe(size: usize, seed: u64) -> Self {
+        // gaussian orthogonal ensemble: (A + A^T) / 2 for a real gaussian matrix A
+        let mut rng = StdRng::seed_from_u64(seed);
+        let elements: Vec<ComplexNum> = (0..size * size)
+            .map(|_| Complex::from(standard_normal(&mut rng), 0.0))
+            .collect();
+        hermitian_part(&Matrix::from_array(size, size, &elements).unwrap())
+    }
+
+    pub fn random_wishart(size: usize, degrees_of_freedom: usize, seed: u64) -> Result<Self, &'static str> {
+        // complex wishart matrix G G^H for an n x m ginibre matrix G
+        gram_matrix(&Matrix::random_gaussian(size, degrees_of_freedom, seed))
+    }
+
+    pub fn random_positive_definite(size: usize, seed: u64) -> Result<Self, &'static str> {
+        // G G^H / n + I, whose eigenvalues are at least 1
+        let mut result = gram_matrix(&Matrix::random_gaussian(size, size, seed))?;
+        result.scale(1.0 / size as FloatType);
+        result
+            .add(&Matrix::identity(size))
+            .ok_or("Matrix dimensions have to match for addition")
+    }
+}
+
+impl Vector {
+    pub fn random_unit(size: usize, seed: u64) -> Self {
+        // a normalized complex gaussian vector is uniformly
+        // distributed on the unit sphere
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut result = Vector::from_vec((0..size).map(|_| complex_normal(&mut rng)).collect());
+        result.normalize();
+        result
+    }
+}
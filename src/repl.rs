@@ -0,0 +1,169 @@
+use crate::expression::*;
+use crate::formats::*;
+
+const HELP: &str = "\
+Statements
+  name = expression     assign a variable
+  expression            evaluate and store the result in ans
+Values
+  2.5  -1e-3  2i  1+2j  [1, 2+i, 3]  [1; 2]  [1 2; 3 4i]
+Operators
+  +  -  *  /  x' (adjoint)  ( )
+Functions
+  kron(a, b)  adjoint(x) or adj(x)  transpose(x)  conj(x)  eig(m)  solve(a, b)
+  inv(m)  trace(m) or tr(m)  norm(x)  expm(m)  logm(m)  sqrtm(m)  eye(n)  zeros(r, c)
+Commands
+  load name path        read a matrix from a .mtx, .npy, .csv, .tsv, .txt or .bin file
+  save name path        write a variable to a file
+  vars                  list the variables
+  clear [name]          remove one or all variables
+  history               show the previous lines
+  help                  show this text
+  quit                  leave the calculator";
+
+/**
+ * State of an interactive calculator session
+ * Every line is executed on its own, errors are returned as text so
+ * that the session stays usable
+ */
+#[derive(Debug, Default)]
+pub struct Session {
+    variables: Bindings,
+    history: Vec<String>,
+}
+
+fn is_name(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Scalar(_) => String::from("scalar"),
+        Value::Vector(_) => {
+            let (num_rows, num_cols) = value.shape();
+            let kind = if num_cols == 1 { "column vector" } else { "row vector" };
+            format!("{} of size {}", kind, num_rows.max(num_cols))
+        }
+        Value::Matrix(_) => {
+            let (num_rows, num_cols) = value.shape();
+            format!("{}x{} matrix", num_rows, num_cols)
+        }
+    }
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Option<String>, String> {
+        // returns the text to show for the line, if any
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        self.history.push(line.to_string());
+
+        let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        if let Some((name, source)) = line.split_once('=') {
+            if is_name(name.trim()) {
+                return self.assign(name.trim(), source);
+            }
+        }
+        match first {
+            "help" if rest.is_empty() => Ok(Some(String::from(HELP))),
+            "vars" if rest.is_empty() => Ok(Some(self.list_variables())),
+            "history" if rest.is_empty() => Ok(Some(self.list_history())),
+            "clear" => self.clear(rest),
+            "load" => self.load(rest),
+            "save" => self.save(rest),
+            _ => {
+                let value = self.evaluate(line)?;
+                let output = format!("ans = {}", Self::format_value(&value));
+                self.variables.bind("ans", value);
+                Ok(Some(output))
+            }
+        }
+    }
+
+    fn evaluate(&self, source: &str) -> Result<Value, String> {
+        self.variables.evaluate(source).map_err(|error| error.render(source))
+    }
+
+    fn format_value(value: &Value) -> String {
+        // vectors and matrices start on a new line to keep their columns aligned
+        match value {
+            Value::Scalar(_) => format!("{}", value),
+            _ => format!("\n{}", value),
+        }
+    }
+
+    fn assign(&mut self, name: &str, source: &str) -> Result<Option<String>, String> {
+        let value = self.evaluate(source)?;
+        let output = format!("{} = {}", name, Self::format_value(&value));
+        self.variables.bind(name, value);
+        Ok(Some(output))
+    }
+
+    fn list_variables(&self) -> String {
+        self.variables
+            .names()
+            .iter()
+            .map(|name| format!("{}: {}", name, describe(self.variables.get(name).unwrap())))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn list_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(index, line)| format!("{:>4}  {}", index + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn clear(&mut self, name: &str) -> Result<Option<String>, String> {
+        if name.is_empty() {
+            self.variables.clear();
+        } else if self.variables.remove(name).is_none() {
+            return Err(format!("Unknown variable '{}'", name));
+        }
+        Ok(None)
+    }
+
+    fn load(&mut self, arguments: &str) -> Result<Option<String>, String> {
+        let (name, path) = arguments
+            .split_once(char::is_whitespace)
+            .filter(|(name, _)| is_name(name))
+            .ok_or("Usage: load name path")?;
+        let matrix = load_matrix(path.trim()).map_err(|error| error.to_string())?;
+        let value = Value::Matrix(matrix);
+        let output = format!("{}: {}", name, describe(&value));
+        self.variables.bind(name, value);
+        Ok(Some(output))
+    }
+
+    fn save(&mut self, arguments: &str) -> Result<Option<String>, String> {
+        let (name, path) = arguments
+            .split_once(char::is_whitespace)
+            .ok_or("Usage: save name path")?;
+        let value = self
+            .variables
+            .get(name)
+            .ok_or(format!("Unknown variable '{}'", name))?;
+        save_matrix(&value.to_matrix(), path.trim()).map_err(|error| error.to_string())?;
+        Ok(None)
+    }
+}
@@ -0,0 +1,75 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::complex::*;
+use crate::matrix::*;
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+// Serialization of vectors and matrices through plain data structs,
+// so that deserialized values always have consistent dimensions
+
+#[derive(Serialize, Deserialize)]
+struct VectorData {
+    vector_type: VectorType,
+    elements: Vec<ComplexNum>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MatrixData {
+    num_rows: usize,
+    num_columns: usize,
+    // one entry per row
+    elements: Vec<Vec<ComplexNum>>,
+}
+
+impl Serialize for Vector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VectorData {
+            vector_type: self.get_type(),
+            elements: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Vector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // the size is given by the number of elements
+        let data = VectorData::deserialize(deserializer)?;
+        Ok(Vector::from(&data.elements, data.vector_type))
+    }
+}
+
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let elements = (0..self.num_rows)
+            .map(|row_index| {
+                (0..self.num_columns)
+                    .map(|column_index| self.get_element(row_index, column_index).unwrap())
+                    .collect()
+            })
+            .collect();
+        MatrixData {
+            num_rows: self.num_rows,
+            num_columns: self.num_columns,
+            elements,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = MatrixData::deserialize(deserializer)?;
+        if data.elements.len() != data.num_rows {
+            return Err(D::Error::custom("Number of rows does not match num_rows"));
+        }
+        if data.elements.iter().any(|row| row.len() != data.num_columns) {
+            return Err(D::Error::custom("Length of every row has to match num_columns"));
+        }
+        let elements: Vec<ComplexNum> = data.elements.into_iter().flatten().collect();
+        Matrix::from_array(data.num_rows, data.num_columns, &elements).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,589 @@
+use std::sync::OnceLock;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use crate::complex::*;
+use crate::gemm::{Tile, MR, NR};
+use crate::vector::*;
+
+type ComplexNum = Complex<FloatType>;
+
+/**
+ * Kernels for the complex inner loops with explicit SIMD code
+ *
+ * The instruction set is detected at runtime, so one binary runs on
+ * every x86_64 machine and uses the widest registers available. Dot
+ * products, axpy and scaling work on interleaved storage, i.e. directly
+ * on slices of Complex. The GEMM micro kernel works on the split real
+ * and imaginary panels packed by the gemm module. Other architectures
+ * use the scalar kernels.
+ *
+ * The SIMD kernels sum in a different order and use fused multiply-add
+ * from AVX2 on, so their results differ from the scalar ones by rounding.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimdLevel {
+    Scalar,
+    // 4 floats per register
+    Sse2,
+    // 8 floats per register, with fused multiply-add
+    Avx2,
+    // 16 floats per register
+    Avx512,
+}
+
+impl SimdLevel {
+    pub fn detect() -> Self {
+        // the widest level supported by this cpu, detected once
+        static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+        *LEVEL.get_or_init(|| {
+            #[cfg(target_arch = "x86_64")]
+            {
+                if is_x86_feature_detected!("avx512f")
+                    && is_x86_feature_detected!("avx2")
+                    && is_x86_feature_detected!("fma")
+                {
+                    return SimdLevel::Avx512;
+                }
+                if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                    return SimdLevel::Avx2;
+                }
+                if is_x86_feature_detected!("sse2") {
+                    return SimdLevel::Sse2;
+                }
+            }
+            SimdLevel::Scalar
+        })
+    }
+
+    pub fn available() -> Vec<Self> {
+        // every level up to the detected one
+        let detected = SimdLevel::detect();
+        [SimdLevel::Scalar, SimdLevel::Sse2, SimdLevel::Avx2, SimdLevel::Avx512]
+            .into_iter()
+            .filter(|level| *level <= detected)
+            .collect()
+    }
+}
+
+fn as_floats(numbers: &[ComplexNum]) -> &[FloatType] {
+    // Complex is repr(C), so n numbers are 2n interleaved floats
+    unsafe { std::slice::from_raw_parts(numbers.as_ptr() as *const FloatType, numbers.len() * 2) }
+}
+
+fn as_floats_mut(numbers: &mut [ComplexNum]) -> &mut [FloatType] {
+    unsafe { std::slice::from_raw_parts_mut(numbers.as_mut_ptr() as *mut FloatType, numbers.len() * 2) }
+}
+
+// Dot products
+// x^T y and x^H y for slices of equal length. The vector loops keep two
+// sums: products of the aligned floats (xr yr, xi yi) and of the floats
+// with swapped pairs (xr yi, xi yr), which are combined at the end
+
+fn dot_scalar(x: &[ComplexNum], y: &[ComplexNum], conjugate: bool) -> ComplexNum {
+    let mut result = Complex::new();
+    for (x, y) in x.iter().zip(y) {
+        let mut x = x.clone();
+        if conjugate {
+            x.conjugate();
+        }
+        result = result.addition(&x.multiplication(y));
+    }
+    result
+}
+
+fn combine_dot(aligned: &[FloatType], swapped: &[FloatType], conjugate: bool) -> (FloatType, FloatType) {
+    // sums of the even and odd lanes to the real and imaginary part
+    let even = |lanes: &[FloatType]| lanes.iter().step_by(2).sum::<FloatType>();
+    let odd = |lanes: &[FloatType]| lanes.iter().skip(1).step_by(2).sum::<FloatType>();
+    if conjugate {
+        (even(aligned) + odd(aligned), even(swapped) - odd(swapped))
+    } else {
+        (even(aligned) - odd(aligned), even(swapped) + odd(swapped))
+    }
+}
+
+fn finish_dot(
+    partial: (FloatType, FloatType),
+    x: &[ComplexNum],
+    y: &[ComplexNum],
+    conjugate: bool,
+) -> ComplexNum {
+    // adds the elements left over by the vector loop
+    let tail = dot_scalar(x, y, conjugate);
+    Complex::from(partial.0 + tail.real, partial.1 + tail.imaginary)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn dot_sse2(x: &[ComplexNum], y: &[ComplexNum], conjugate: bool) -> ComplexNum {
+    let (x_floats, y_floats) = (as_floats(x), as_floats(y));
+    let mut aligned = _mm_setzero_ps();
+    let mut swapped = _mm_setzero_ps();
+    let chunks = x.len() / 2;
+    for chunk in 0..chunks {
+        let x_vector = _mm_loadu_ps(x_floats.as_ptr().add(chunk * 4));
+        let y_vector = _mm_loadu_ps(y_floats.as_ptr().add(chunk * 4));
+        aligned = _mm_add_ps(aligned, _mm_mul_ps(x_vector, y_vector));
+        let y_swapped = _mm_shuffle_ps(y_vector, y_vector, 0b10_11_00_01);
+        swapped = _mm_add_ps(swapped, _mm_mul_ps(x_vector, y_swapped));
+    }
+    let mut lanes = [[0.0; 4]; 2];
+    _mm_storeu_ps(lanes[0].as_mut_ptr(), aligned);
+    _mm_storeu_ps(lanes[1].as_mut_ptr(), swapped);
+    let partial = combine_dot(&lanes[0], &lanes[1], conjugate);
+    finish_dot(partial, &x[chunks * 2..], &y[chunks * 2..], conjugate)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn dot_avx2(x: &[ComplexNum], y: &[ComplexNum], conjugate: bool) -> ComplexNum {
+    let (x_floats, y_floats) = (as_floats(x), as_floats(y));
+    let mut aligned = _mm256_setzero_ps();
+    let mut swapped = _mm256_setzero_ps();
+    let chunks = x.len() / 4;
+    for chunk in 0..chunks {
+        let x_vector = _mm256_loadu_ps(x_floats.as_ptr().add(chunk * 8));
+        let y_vector = _mm256_loadu_ps(y_floats.as_ptr().add(chunk * 8));
+        aligned = _mm256_fmadd_ps(x_vector, y_vector, aligned);
+        swapped = _mm256_fmadd_ps(x_vector, _mm256_permute_ps(y_vector, 0b10_11_00_01), swapped);
+    }
+    let mut lanes = [[0.0; 8]; 2];
+    _mm256_storeu_ps(lanes[0].as_mut_ptr(), aligned);
+    _mm256_storeu_ps(lanes[1].as_mut_ptr(), swapped);
+    let partial = combine_dot(&lanes[0], &lanes[1], conjugate);
+    finish_dot(partial, &x[chunks * 4..], &y[chunks * 4..], conjugate)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,fma")]
+unsafe fn dot_avx512(x: &[ComplexNum], y: &[ComplexNum], conjugate: bool) -> ComplexNum {
+    let (x_floats, y_floats) = (as_floats(x), as_floats(y));
+    let mut aligned = _mm512_setzero_ps();
+    let mut swapped = _mm512_setzero_ps();
+    let chunks = x.len() / 8;
+    for chunk in 0..chunks {
+        let x_vector = _mm512_loadu_ps(x_floats.as_ptr().add(chunk * 16));
+        let y_vector = _mm512_loadu_ps(y_floats.as_ptr().add(chunk * 16));
+        aligned = _mm512_fmadd_ps(x_vector, y_vector, aligned);
+        swapped = _mm512_fmadd_ps(x_vector, _mm512_permute_ps(y_vector, 0b10_11_00_01), swapped);
+    }
+    let mut lanes = [[0.0; 16]; 2];
+    _mm512_storeu_ps(lanes[0].as_mut_ptr(), aligned);
+    _mm512_storeu_ps(lanes[1].as_mut_ptr(), swapped);
+    let partial = combine_dot(&lanes[0], &lanes[1], conjugate);
+    finish_dot(partial, &x[chunks * 8..], &y[chunks * 8..], conjugate)
+}
+
+pub(crate) fn dot_with(level: SimdLevel, x: &[ComplexNum], y: &[ComplexNum], conjugate: bool) -> ComplexNum {
+    let length = x.len().min(y.len());
+    let (x, y) = (&x[..length], &y[..length]);
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { dot_sse2(x, y, conjugate) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { dot_avx2(x, y, conjugate) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { dot_avx512(x, y, conjugate) },
+        _ => dot_scalar(x, y, conjugate),
+    }
+}
+
+pub fn dot(x: &[ComplexNum], y: &[ComplexNum]) -> ComplexNum {
+    // sum of x_k y_k over the common length
+    dot_with(SimdLevel::detect(), x, y, false)
+}
+
+pub fn dot_conjugate(x: &[ComplexNum], y: &[ComplexNum]) -> ComplexNum {
+    // sum of x_k^* y_k over the common length
+    dot_with(SimdLevel::detect(), x, y, true)
+}
+
+// Axpy and scaling
+// alpha x for interleaved x is ar x + ai swap(x) * (-1, 1, -1, 1, ...)
+
+fn axpy_scalar(alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    for (x, y) in x.iter().zip(y) {
+        *y = y.addition(&alpha.multiplication(x));
+    }
+}
+
+fn scale_scalar(alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    for x in x {
+        *x = alpha.multiplication(x);
+    }
+}
+
+fn signed_imaginary<const LANES: usize>(alpha: &ComplexNum) -> [FloatType; LANES] {
+    let mut lanes = [alpha.imaginary; LANES];
+    lanes.iter_mut().step_by(2).for_each(|lane| *lane = -*lane);
+    lanes
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn axpy_sse2(alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    let chunks = x.len() / 2;
+    let real = _mm_set1_ps(alpha.real);
+    let imaginary = _mm_loadu_ps(signed_imaginary::<4>(alpha).as_ptr());
+    let (x_floats, y_floats) = (as_floats(x), as_floats_mut(y));
+    for chunk in 0..chunks {
+        let x_vector = _mm_loadu_ps(x_floats.as_ptr().add(chunk * 4));
+        let y_pointer = y_floats.as_mut_ptr().add(chunk * 4);
+        let x_swapped = _mm_shuffle_ps(x_vector, x_vector, 0b10_11_00_01);
+        let product = _mm_add_ps(_mm_mul_ps(x_vector, real), _mm_mul_ps(x_swapped, imaginary));
+        _mm_storeu_ps(y_pointer, _mm_add_ps(_mm_loadu_ps(y_pointer), product));
+    }
+    axpy_scalar(alpha, &x[chunks * 2..], &mut y[chunks * 2..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn axpy_avx2(alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    let chunks = x.len() / 4;
+    let real = _mm256_set1_ps(alpha.real);
+    let imaginary = _mm256_loadu_ps(signed_imaginary::<8>(alpha).as_ptr());
+    let (x_floats, y_floats) = (as_floats(x), as_floats_mut(y));
+    for chunk in 0..chunks {
+        let x_vector = _mm256_loadu_ps(x_floats.as_ptr().add(chunk * 8));
+        let y_pointer = y_floats.as_mut_ptr().add(chunk * 8);
+        let mut y_vector = _mm256_fmadd_ps(x_vector, real, _mm256_loadu_ps(y_pointer));
+        y_vector = _mm256_fmadd_ps(_mm256_permute_ps(x_vector, 0b10_11_00_01), imaginary, y_vector);
+        _mm256_storeu_ps(y_pointer, y_vector);
+    }
+    axpy_scalar(alpha, &x[chunks * 4..], &mut y[chunks * 4..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,fma")]
+unsafe fn axpy_avx512(alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    let chunks = x.len() / 8;
+    let real = _mm512_set1_ps(alpha.real);
+    let imaginary = _mm512_loadu_ps(signed_imaginary::<16>(alpha).as_ptr());
+    let (x_floats, y_floats) = (as_floats(x), as_floats_mut(y));
+    for chunk in 0..chunks {
+        let x_vector = _mm512_loadu_ps(x_floats.as_ptr().add(chunk * 16));
+        let y_pointer = y_floats.as_mut_ptr().add(chunk * 16);
+        let mut y_vector = _mm512_fmadd_ps(x_vector, real, _mm512_loadu_ps(y_pointer));
+        y_vector = _mm512_fmadd_ps(_mm512_permute_ps(x_vector, 0b10_11_00_01), imaginary, y_vector);
+        _mm512_storeu_ps(y_pointer, y_vector);
+    }
+    axpy_scalar(alpha, &x[chunks * 8..], &mut y[chunks * 8..]);
+}
+
+pub(crate) fn axpy_with(level: SimdLevel, alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    let length = x.len().min(y.len());
+    let (x, y) = (&x[..length], &mut y[..length]);
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { axpy_sse2(alpha, x, y) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { axpy_avx2(alpha, x, y) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { axpy_avx512(alpha, x, y) },
+        _ => axpy_scalar(alpha, x, y),
+    }
+}
+
+pub fn axpy(alpha: &ComplexNum, x: &[ComplexNum], y: &mut [ComplexNum]) {
+    // y <- alpha x + y over the common length
+    axpy_with(SimdLevel::detect(), alpha, x, y)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn scale_sse2(alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    let chunks = x.len() / 2;
+    let real = _mm_set1_ps(alpha.real);
+    let imaginary = _mm_loadu_ps(signed_imaginary::<4>(alpha).as_ptr());
+    let floats = as_floats_mut(x);
+    for chunk in 0..chunks {
+        let pointer = floats.as_mut_ptr().add(chunk * 4);
+        let x_vector = _mm_loadu_ps(pointer);
+        let x_swapped = _mm_shuffle_ps(x_vector, x_vector, 0b10_11_00_01);
+        _mm_storeu_ps(pointer, _mm_add_ps(_mm_mul_ps(x_vector, real), _mm_mul_ps(x_swapped, imaginary)));
+    }
+    scale_scalar(alpha, &mut x[chunks * 2..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn scale_avx2(alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    let chunks = x.len() / 4;
+    let real = _mm256_set1_ps(alpha.real);
+    let imaginary = _mm256_loadu_ps(signed_imaginary::<8>(alpha).as_ptr());
+    let floats = as_floats_mut(x);
+    for chunk in 0..chunks {
+        let pointer = floats.as_mut_ptr().add(chunk * 8);
+        let x_vector = _mm256_loadu_ps(pointer);
+        let product = _mm256_mul_ps(_mm256_permute_ps(x_vector, 0b10_11_00_01), imaginary);
+        _mm256_storeu_ps(pointer, _mm256_fmadd_ps(x_vector, real, product));
+    }
+    scale_scalar(alpha, &mut x[chunks * 4..]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,fma")]
+unsafe fn scale_avx512(alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    let chunks = x.len() / 8;
+    let real = _mm512_set1_ps(alpha.real);
+    let imaginary = _mm512_loadu_ps(signed_imaginary::<16>(alpha).as_ptr());
+    let floats = as_floats_mut(x);
+    for chunk in 0..chunks {
+        let pointer = floats.as_mut_ptr().add(chunk * 16);
+        let x_vector = _mm512_loadu_ps(pointer);
+        let product = _mm512_mul_ps(_mm512_permute_ps(x_vector, 0b10_11_00_01), imaginary);
+        _mm512_storeu_ps(pointer, _mm512_fmadd_ps(x_vector, real, product));
+    }
+    scale_scalar(alpha, &mut x[chunks * 8..]);
+}
+
+pub(crate) fn scale_with(level: SimdLevel, alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { scale_sse2(alpha, x) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { scale_avx2(alpha, x) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { scale_avx512(alpha, x) },
+        _ => scale_scalar(alpha, x),
+    }
+}
+
+pub fn scale(alpha: &ComplexNum, x: &mut [ComplexNum]) {
+    // x <- alpha x
+    scale_with(SimdLevel::detect(), alpha, x)
+}
+
+// GEMM micro kernels
+// MR x NR tile of the product of an MR sliver of A, stored column by
+// column, and an NR sliver of B, stored row by row, both split into
+// real and imaginary parts. The vector kernels hold one row of the tile
+// in a register (two for SSE2, real and imaginary part in one for AVX-512)
+
+const _: () = assert!(MR == 4 && NR == 8, "the SIMD micro kernels are written for 4 x 8 tiles");
+
+fn micro_kernel_scalar(a_real: &[FloatType], a_imaginary: &[FloatType], b_real: &[FloatType], b_imaginary: &[FloatType]) -> (Tile, Tile) {
+    let mut real = [[0.0; NR]; MR];
+    let mut imaginary = [[0.0; NR]; MR];
+    let a = a_real.chunks_exact(MR).zip(a_imaginary.chunks_exact(MR));
+    let b = b_real.chunks_exact(NR).zip(b_imaginary.chunks_exact(NR));
+    for ((a_real, a_imaginary), (b_real, b_imaginary)) in a.zip(b) {
+        for i in 0..MR {
+            let (x, y) = (a_real[i], a_imaginary[i]);
+            for j in 0..NR {
+                real[i][j] += x * b_real[j] - y * b_imaginary[j];
+                imaginary[i][j] += x * b_imaginary[j] + y * b_real[j];
+            }
+        }
+    }
+    (real, imaginary)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn micro_kernel_sse2(a_real: &[FloatType], a_imaginary: &[FloatType], b_real: &[FloatType], b_imaginary: &[FloatType]) -> (Tile, Tile) {
+    // index 0 holds columns 0 to 3 of the tile, index 1 columns 4 to 7
+    let mut real = [[_mm_setzero_ps(); 2]; MR];
+    let mut imaginary = [[_mm_setzero_ps(); 2]; MR];
+    for p in 0..a_real.len() / MR {
+        let b_pointer = (b_real.as_ptr().add(p * NR), b_imaginary.as_ptr().add(p * NR));
+        let b_re = [_mm_loadu_ps(b_pointer.0), _mm_loadu_ps(b_pointer.0.add(4))];
+        let b_im = [_mm_loadu_ps(b_pointer.1), _mm_loadu_ps(b_pointer.1.add(4))];
+        for i in 0..MR {
+            let x = _mm_set1_ps(*a_real.get_unchecked(p * MR + i));
+            let y = _mm_set1_ps(*a_imaginary.get_unchecked(p * MR + i));
+            for half in 0..2 {
+                let real_part = _mm_sub_ps(_mm_mul_ps(x, b_re[half]), _mm_mul_ps(y, b_im[half]));
+                let imaginary_part = _mm_add_ps(_mm_mul_ps(x, b_im[half]), _mm_mul_ps(y, b_re[half]));
+                real[i][half] = _mm_add_ps(real[i][half], real_part);
+                imaginary[i][half] = _mm_add_ps(imaginary[i][half], imaginary_part);
+            }
+        }
+    }
+    let mut tiles = ([[0.0; NR]; MR], [[0.0; NR]; MR]);
+    for i in 0..MR {
+        for half in 0..2 {
+            _mm_storeu_ps(tiles.0[i].as_mut_ptr().add(half * 4), real[i][half]);
+            _mm_storeu_ps(tiles.1[i].as_mut_ptr().add(half * 4), imaginary[i][half]);
+        }
+    }
+    tiles
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn micro_kernel_avx2(a_real: &[FloatType], a_imaginary: &[FloatType], b_real: &[FloatType], b_imaginary: &[FloatType]) -> (Tile, Tile) {
+    let mut real = [_mm256_setzero_ps(); MR];
+    let mut imaginary = [_mm256_setzero_ps(); MR];
+    for p in 0..a_real.len() / MR {
+        let b_re = _mm256_loadu_ps(b_real.as_ptr().add(p * NR));
+        let b_im = _mm256_loadu_ps(b_imaginary.as_ptr().add(p * NR));
+        for i in 0..MR {
+            let x = _mm256_set1_ps(*a_real.get_unchecked(p * MR + i));
+            let y = _mm256_set1_ps(*a_imaginary.get_unchecked(p * MR + i));
+            real[i] = _mm256_fnmadd_ps(y, b_im, _mm256_fmadd_ps(x, b_re, real[i]));
+            imaginary[i] = _mm256_fmadd_ps(y, b_re, _mm256_fmadd_ps(x, b_im, imaginary[i]));
+        }
+    }
+    let mut tiles = ([[0.0; NR]; MR], [[0.0; NR]; MR]);
+    for i in 0..MR {
+        _mm256_storeu_ps(tiles.0[i].as_mut_ptr(), real[i]);
+        _mm256_storeu_ps(tiles.1[i].as_mut_ptr(), imaginary[i]);
+    }
+    tiles
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,fma")]
+unsafe fn micro_kernel_avx512(a_real: &[FloatType], a_imaginary: &[FloatType], b_real: &[FloatType], b_imaginary: &[FloatType]) -> (Tile, Tile) {
+    // a row of the tile is (real parts | imaginary parts), updated by
+    // x (b_re | b_im) + y (-b_im | b_re)
+    let join = |low: __m256, high: __m256| {
+        let joined = _mm512_insertf64x4(_mm512_castpd256_pd512(_mm256_castps_pd(low)), _mm256_castps_pd(high), 1);
+        _mm512_castpd_ps(joined)
+    };
+    let mut rows = [_mm512_setzero_ps(); MR];
+    for p in 0..a_real.len() / MR {
+        let b_re = _mm256_loadu_ps(b_real.as_ptr().add(p * NR));
+        let b_im = _mm256_loadu_ps(b_imaginary.as_ptr().add(p * NR));
+        let b = join(b_re, b_im);
+        let b_swapped = join(_mm256_sub_ps(_mm256_setzero_ps(), b_im), b_re);
+        for (i, row) in rows.iter_mut().enumerate() {
+            let x = _mm512_set1_ps(*a_real.get_unchecked(p * MR + i));
+            let y = _mm512_set1_ps(*a_imaginary.get_unchecked(p * MR + i));
+            *row = _mm512_fmadd_ps(y, b_swapped, _mm512_fmadd_ps(x, b, *row));
+        }
+    }
+    let mut tiles = ([[0.0; NR]; MR], [[0.0; NR]; MR]);
+    for (i, row) in rows.iter().enumerate() {
+        let mut lanes = [0.0; 2 * NR];
+        _mm512_storeu_ps(lanes.as_mut_ptr(), *row);
+        tiles.0[i].copy_from_slice(&lanes[..NR]);
+        tiles.1[i].copy_from_slice(&lanes[NR..]);
+    }
+    tiles
+}
+
+pub(crate) fn micro_kernel(
+    level: SimdLevel,
+    a_real: &[FloatType],
+    a_imaginary: &[FloatType],
+    b_real: &[FloatType],
+    b_imaginary: &[FloatType],
+) -> (Tile, Tile) {
+    // the slivers have to be of equal depth, the vector kernels skip the bounds checks
+    assert!(a_real.len() / MR == b_real.len() / NR, "Slivers of A and B have to be of equal depth");
+    assert!(a_imaginary.len() >= a_real.len() && b_imaginary.len() >= b_real.len());
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { micro_kernel_sse2(a_real, a_imaginary, b_real, b_imaginary) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { micro_kernel_avx2(a_real, a_imaginary, b_real, b_imaginary) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { micro_kernel_avx512(a_real, a_imaginary, b_real, b_imaginary) },
+        _ => micro_kernel_scalar(a_real, a_imaginary, b_real, b_imaginary),
+    }
+}
+
+// 3M micro kernels
+// Same tiles as above from three real products: with sums s = re + im of
+// both slivers, re = ar br - ai bi and im = as bs - ar br - ai bi. The
+// AVX-512 level uses the AVX2 kernel, as a tile row fills a 256 bit register
+
+type Sliver<'a> = (&'a [FloatType], &'a [FloatType], &'a [FloatType]);
+
+fn combine_3m(products: [Tile; 3]) -> (Tile, Tile) {
+    let [real_products, imaginary_products, sum_products] = products;
+    let mut tiles = ([[0.0; NR]; MR], [[0.0; NR]; MR]);
+    for i in 0..MR {
+        for j in 0..NR {
+            tiles.0[i][j] = real_products[i][j] - imaginary_products[i][j];
+            tiles.1[i][j] = sum_products[i][j] - real_products[i][j] - imaginary_products[i][j];
+        }
+    }
+    tiles
+}
+
+fn micro_kernel_3m_scalar(a: Sliver, b: Sliver) -> [Tile; 3] {
+    let mut products = [[[0.0; NR]; MR]; 3];
+    let a_parts = [a.0, a.1, a.2];
+    let b_parts = [b.0, b.1, b.2];
+    for (product, (a, b)) in products.iter_mut().zip(a_parts.iter().zip(b_parts)) {
+        for (a, b) in a.chunks_exact(MR).zip(b.chunks_exact(NR)) {
+            for i in 0..MR {
+                for j in 0..NR {
+                    product[i][j] += a[i] * b[j];
+                }
+            }
+        }
+    }
+    products
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn micro_kernel_3m_sse2(a: Sliver, b: Sliver) -> [Tile; 3] {
+    let mut accumulators = [[[_mm_setzero_ps(); 2]; MR]; 3];
+    let parts = [(a.0, b.0), (a.1, b.1), (a.2, b.2)];
+    for p in 0..a.0.len() / MR {
+        for (accumulator, (a_part, b_part)) in accumulators.iter_mut().zip(parts) {
+            let b_pointer = b_part.as_ptr().add(p * NR);
+            let b_vectors = [_mm_loadu_ps(b_pointer), _mm_loadu_ps(b_pointer.add(4))];
+            for (i, row) in accumulator.iter_mut().enumerate() {
+                let x = _mm_set1_ps(*a_part.get_unchecked(p * MR + i));
+                for (half, b_vector) in row.iter_mut().zip(b_vectors) {
+                    *half = _mm_add_ps(*half, _mm_mul_ps(x, b_vector));
+                }
+            }
+        }
+    }
+    let mut products = [[[0.0; NR]; MR]; 3];
+    for (product, accumulator) in products.iter_mut().zip(&accumulators) {
+        for (row, halves) in product.iter_mut().zip(accumulator) {
+            _mm_storeu_ps(row.as_mut_ptr(), halves[0]);
+            _mm_storeu_ps(row.as_mut_ptr().add(4), halves[1]);
+        }
+    }
+    products
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn micro_kernel_3m_avx2(a: Sliver, b: Sliver) -> [Tile; 3] {
+    let mut accumulators = [[_mm256_setzero_ps(); MR]; 3];
+    let parts = [(a.0, b.0), (a.1, b.1), (a.2, b.2)];
+    for p in 0..a.0.len() / MR {
+        for (accumulator, (a_part, b_part)) in accumulators.iter_mut().zip(parts) {
+            let b_vector = _mm256_loadu_ps(b_part.as_ptr().add(p * NR));
+            for (i, row) in accumulator.iter_mut().enumerate() {
+                let x = _mm256_set1_ps(*a_part.get_unchecked(p * MR + i));
+                *row = _mm256_fmadd_ps(x, b_vector, *row);
+            }
+        }
+    }
+    let mut products = [[[0.0; NR]; MR]; 3];
+    for (product, accumulator) in products.iter_mut().zip(&accumulators) {
+        for (row, vector) in product.iter_mut().zip(accumulator) {
+            _mm256_storeu_ps(row.as_mut_ptr(), *vector);
+        }
+    }
+    products
+}
+
+pub(crate) fn micro_kernel_3m(level: SimdLevel, a: Sliver, b: Sliver) -> (Tile, Tile) {
+    // slivers are (real, imaginary, real + imaginary) parts of equal depth
+    let depth = a.0.len() / MR;
+    assert!(b.0.len() / NR == depth, "Slivers of A and B have to be of equal depth");
+    assert!([a.1.len(), a.2.len()].iter().all(|length| length / MR >= depth));
+    assert!([b.1.len(), b.2.len()].iter().all(|length| length / NR >= depth));
+    let products = match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { micro_kernel_3m_sse2(a, b) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 | SimdLevel::Avx512 => unsafe { micro_kernel_3m_avx2(a, b) },
+        _ => micro_kernel_3m_scalar(a, b),
+    };
+    combine_3m(products)
+}
@@ -0,0 +1,173 @@
+use std::ops::Range;
+
+use crate::complex::*;
+use crate::gemm::*;
+use crate::matrix::*;
+
+/**
+ * Fast matrix multiplication for large products
+ *
+ * The Strassen-Winograd recursion splits the operands into 2 x 2 blocks
+ * and forms the product from 7 block products and 15 block additions
+ * instead of 8 products, i.e. O(n^2.81) operations. Blocks with a
+ * dimension of at most the cutoff are multiplied by the blocked kernel. Odd
+ * dimensions are peeled off: the recursion works on the even leading
+ * part and the remaining row, column and rank one update are computed
+ * by the blocked kernel.
+ *
+ * The 3M method computes a complex product from three real products
+ * Ar Br, Ai Bi and (Ar + Ai)(Br + Bi) instead of four, which saves a
+ * quarter of the multiplications in the kernel.
+ *
+ * Error bounds, with unit roundoff u, inner dimension k and cutoff n0
+ * (see Higham, Accuracy and Stability of Numerical Algorithms, ch. 23):
+ * - blocked kernel: |C - fl(AB)| <= k u |A||B| element wise
+ * - 3M: the real part keeps this bound, the imaginary part only the
+ *   norm wise bound ||Im C - Im fl(AB)|| <= (k + 4) u (||Ar|| + ||Ai||)(||Br|| + ||Bi||),
+ *   so imaginary parts much smaller than the operands lose relative accuracy
+ * - Strassen-Winograd: only norm wise,
+ *   ||C - fl(AB)|| <= ((k/n0)^log2(18) (n0^2 + 6 n0) - 6 k) u ||A|| ||B||,
+ *   i.e. the error grows like k^4.17 instead of k with the recursion depth
+ *
+ * Matrix::multiply always uses the blocked kernel, callers that accept
+ * these bounds select another algorithm per call with multiply_with.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    // the cache blocked kernel of the gemm module
+    #[default]
+    Blocked,
+    // blocked kernel with three real products per complex product
+    ThreeM,
+    // recursion down to blocks with a dimension of at most cutoff,
+    // which are multiplied with the 3M kernel if three_m is set
+    StrassenWinograd { cutoff: usize, three_m: bool },
+}
+
+// recursion cutoff for the default Strassen-Winograd configuration
+pub const STRASSEN_CUTOFF: usize = 256;
+
+impl Algorithm {
+    pub fn strassen() -> Self {
+        Algorithm::StrassenWinograd {
+            cutoff: STRASSEN_CUTOFF,
+            three_m: false,
+        }
+    }
+}
+
+fn base_product(a: &Matrix, b: &Matrix, three_m: bool) -> Matrix {
+    let mut result = Matrix::zeros(a.num_rows, b.num_columns);
+    if three_m {
+        blocked_gemm(&Complex::from(1.0, 0.0), a, Op::Normal, b, Op::Normal, &mut result, true);
+    } else {
+        result
+            .gemm(Complex::from(1.0, 0.0), a, Op::Normal, b, Op::Normal, Complex::new())
+            .unwrap();
+    }
+    result
+}
+
+fn sub_matrix(matrix: &Matrix, rows: Range<usize>, columns: Range<usize>) -> Matrix {
+    Matrix::from_fn(rows.len(), columns.len(), |row_index, column_index| {
+        matrix.row(rows.start + row_index)[columns.start + column_index].clone()
+    })
+}
+
+fn sum(first: &Matrix, second: &Matrix) -> Matrix {
+    let mut result = first.clone();
+    result.add_assign(second).unwrap();
+    result
+}
+
+fn difference(first: &Matrix, second: &Matrix) -> Matrix {
+    let mut result = first.clone();
+    result.sub_assign(second).unwrap();
+    result
+}
+
+fn strassen_winograd(a: &Matrix, b: &Matrix, cutoff: usize, three_m: bool) -> Matrix {
+    let (m, k, n) = (a.num_rows, a.num_columns, b.num_columns);
+    if m.min(k).min(n) <= cutoff.max(1) {
+        return base_product(a, b, three_m);
+    }
+    let (m_even, k_even, n_even) = (m & !1, k & !1, n & !1);
+    if (m_even, k_even, n_even) != (m, k, n) {
+        return peel(a, b, cutoff, three_m);
+    }
+
+    let (m_half, k_half, n_half) = (m / 2, k / 2, n / 2);
+    let a11 = sub_matrix(a, 0..m_half, 0..k_half);
+    let a12 = sub_matrix(a, 0..m_half, k_half..k);
+    let a21 = sub_matrix(a, m_half..m, 0..k_half);
+    let a22 = sub_matrix(a, m_half..m, k_half..k);
+    let b11 = sub_matrix(b, 0..k_half, 0..n_half);
+    let b12 = sub_matrix(b, 0..k_half, n_half..n);
+    let b21 = sub_matrix(b, k_half..k, 0..n_half);
+    let b22 = sub_matrix(b, k_half..k, n_half..n);
+
+    // Winograd's form with 8 additions of the operands
+    let s1 = sum(&a21, &a22);
+    let s2 = difference(&s1, &a11);
+    let s3 = difference(&a11, &a21);
+    let s4 = difference(&a12, &s2);
+    let t1 = difference(&b12, &b11);
+    let t2 = difference(&b22, &t1);
+    let t3 = difference(&b22, &b12);
+    let t4 = difference(&t2, &b21);
+
+    let recurse = |x: &Matrix, y: &Matrix| strassen_winograd(x, y, cutoff, three_m);
+    let m1 = recurse(&a11, &b11);
+    let m2 = recurse(&a12, &b21);
+    let m3 = recurse(&s4, &b22);
+    let m4 = recurse(&a22, &t4);
+    let m5 = recurse(&s1, &t1);
+    let m6 = recurse(&s2, &t2);
+    let m7 = recurse(&s3, &t3);
+
+    // and 7 additions of the products
+    let c11 = sum(&m1, &m2);
+    let u2 = sum(&m1, &m6);
+    let u3 = sum(&u2, &m7);
+    let u4 = sum(&u2, &m5);
+    let c12 = sum(&u4, &m3);
+    let c21 = difference(&u3, &m4);
+    let c22 = sum(&u3, &m5);
+    Matrix::block(&[&[&c11, &c12], &[&c21, &c22]]).unwrap()
+}
+
+fn peel(a: &Matrix, b: &Matrix, cutoff: usize, three_m: bool) -> Matrix {
+    // [A11 a12; a21] [B11 b12; b21] for the even leading blocks A11 and
+    // B11, where the lower case blocks are at most one row or column wide
+    let (m, k, n) = (a.num_rows, a.num_columns, b.num_columns);
+    let (m_even, k_even, n_even) = (m & !1, k & !1, n & !1);
+
+    let mut leading = strassen_winograd(
+        &sub_matrix(a, 0..m_even, 0..k_even),
+        &sub_matrix(b, 0..k_even, 0..n_even),
+        cutoff,
+        three_m,
+    );
+    if k_even < k {
+        let rank_one = base_product(&sub_matrix(a, 0..m_even, k_even..k), &sub_matrix(b, k_even..k, 0..n_even), three_m);
+        leading.add_assign(&rank_one).unwrap();
+    }
+    let last_columns = base_product(&sub_matrix(a, 0..m_even, 0..k), &sub_matrix(b, 0..k, n_even..n), three_m);
+    let last_rows = base_product(&sub_matrix(a, m_even..m, 0..k), b, three_m);
+    let upper = Matrix::hstack(&[&leading, &last_columns]).unwrap();
+    Matrix::vstack(&[&upper, &last_rows]).unwrap()
+}
+
+impl Matrix {
+    pub fn multiply_with(&self, other: &Self, algorithm: Algorithm) -> Result<Self, &'static str> {
+        // self other with the chosen algorithm, see Algorithm for the error bounds
+        if self.num_columns != other.num_rows {
+            return Err("Number of columns of the first matrix must match the number of rows of the second matrix");
+        }
+        match algorithm {
+            Algorithm::Blocked => self.multiply(other),
+            Algorithm::ThreeM => Ok(base_product(self, other, true)),
+            Algorithm::StrassenWinograd { cutoff, three_m } => Ok(strassen_winograd(self, other, cutoff, three_m)),
+        }
+    }
+}
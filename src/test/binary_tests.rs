@@ -0,0 +1,137 @@
+// Unit test module for the binary container format
+#[cfg(test)]
+mod tests {
+
+    use crate::binary::*;
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn test_matrix() -> Matrix {
+        Matrix::from_fn(3, 5, |row, column| comp!(row as f32 - 1.5, column as f32 * 0.25))
+    }
+
+    fn error_reason(result: Result<Matrix, BinaryError>) -> &'static str {
+        match result {
+            Err(BinaryError::Format(reason)) => reason,
+            _ => panic!("expected a format error"),
+        }
+    }
+
+    #[test]
+    fn crc32_test() {
+        // standard check value of CRC-32
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn matrix_round_trip_test() {
+        for scalar in [BinaryScalar::Complex64, BinaryScalar::Complex128] {
+            let mut buffer = Vec::new();
+            test_matrix().write_binary(&mut buffer, scalar).unwrap();
+            assert_eq!(buffer.len(), 32 + 15 * 2 * if scalar == BinaryScalar::Complex64 { 4 } else { 8 } + 4);
+
+            let result = Matrix::read_binary(buffer.as_slice()).unwrap();
+            assert_eq!((result.num_rows, result.num_columns), (3, 5));
+            assert_complex_matrix(test_matrix(), result);
+        }
+    }
+
+    #[test]
+    fn large_matrix_round_trip_test() {
+        // spans several chunks
+        let matrix = Matrix::from_fn(90, 100, |row, column| comp!(row as f32, -(column as f32)));
+        let mut buffer = Vec::new();
+        matrix.write_binary(&mut buffer, BinaryScalar::Complex64).unwrap();
+        assert_complex_matrix(matrix, Matrix::read_binary(buffer.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn vector_round_trip_test() {
+        let mut vector = Vector::linspace(-1.0, 1.0, 7);
+        vector.transpose();
+        let mut buffer = Vec::new();
+        vector.write_binary(&mut buffer, BinaryScalar::Complex128).unwrap();
+
+        let result = Vector::read_binary(buffer.as_slice()).unwrap();
+        assert_eq!(result.get_type(), VectorType::ROW_VECTOR);
+        assert_eq!(result.size(), 7);
+        assert_complex_vec(vector, result);
+    }
+
+    #[test]
+    fn column_major_test() {
+        // a hand written column major 2 x 2 file
+        let mut buffer = b"LINALGB\0\x01\x00\x00\x00\x01\x00\x00\x00".to_vec();
+        buffer.extend_from_slice(&2u64.to_le_bytes());
+        buffer.extend_from_slice(&2u64.to_le_bytes());
+        for value in [1.0f32, 0.0, 3.0, 0.0, 2.0, 0.0, 4.0, 0.0] {
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        let crc = crc32(&buffer);
+        buffer.extend_from_slice(&crc.to_le_bytes());
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0), comp!(4.0, 0.0)],
+        )
+        .unwrap();
+        assert_complex_matrix(expected, Matrix::read_binary(buffer.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn corruption_test() {
+        let mut buffer = Vec::new();
+        test_matrix().write_binary(&mut buffer, BinaryScalar::Complex64).unwrap();
+
+        let mut corrupt = buffer.clone();
+        corrupt[40] ^= 0x01;
+        assert_eq!(
+            error_reason(Matrix::read_binary(corrupt.as_slice())),
+            "Checksum does not match, the file is corrupt"
+        );
+
+        let truncated = &buffer[..buffer.len() - 10];
+        assert_eq!(error_reason(Matrix::read_binary(truncated)), "File is truncated");
+        assert_eq!(error_reason(Matrix::read_binary(&buffer[..20])), "File is truncated");
+
+        let mut wrong_magic = buffer.clone();
+        wrong_magic[0] = b'X';
+        assert_eq!(error_reason(Matrix::read_binary(wrong_magic.as_slice())), "Not a linalg binary file");
+
+        let mut wrong_version = buffer;
+        wrong_version[8] = 9;
+        assert_eq!(error_reason(Matrix::read_binary(wrong_version.as_slice())), "Unsupported format version");
+    }
+
+    #[test]
+    fn kind_mismatch_test() {
+        let mut buffer = Vec::new();
+        Vector::ones(3).write_binary(&mut buffer, BinaryScalar::Complex64).unwrap();
+        assert_eq!(
+            error_reason(Matrix::read_binary(buffer.as_slice())),
+            "File contains a vector, not a matrix"
+        );
+
+        let mut buffer = Vec::new();
+        test_matrix().write_binary(&mut buffer, BinaryScalar::Complex64).unwrap();
+        assert!(Vector::read_binary(buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn file_test() {
+        let path = std::env::temp_dir().join(format!("linalg_binary_test_{}.bin", std::process::id()));
+        test_matrix().save_binary(&path, BinaryScalar::Complex64).unwrap();
+        let result = Matrix::load_binary(&path);
+        #[cfg(feature = "mmap")]
+        let mapped = Matrix::map_binary(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_complex_matrix(test_matrix(), result.unwrap());
+        #[cfg(feature = "mmap")]
+        assert_complex_matrix(test_matrix(), mapped.unwrap());
+    }
+}
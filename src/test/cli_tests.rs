@@ -0,0 +1,93 @@
+// Unit test module for the command line interface
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+    use std::fs;
+
+    use crate::cli::*;
+    use crate::complex::*;
+    use crate::formats::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+
+    fn temp_path(name: &str) -> String {
+        env::temp_dir().join(format!("linalg_cli_{}", name)).to_str().unwrap().to_string()
+    }
+
+    fn write_file(name: &str, content: &str) -> String {
+        let path = temp_path(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn run_command(arguments: &[&str]) -> (Result<(), CliError>, String) {
+        let arguments: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+        let mut stdout = Vec::new();
+        let result = run(&arguments, &mut stdout);
+        (result, String::from_utf8(stdout).unwrap())
+    }
+
+    #[test]
+    fn multiply_to_stdout() {
+        let a = write_file("multiply_a.csv", "1,2\n3,4\n");
+        let b = write_file("multiply_b.csv", "0,1\n1,0\n");
+        let (result, stdout) = run_command(&["multiply", &a, &b]);
+        assert_eq!(Ok(()), result);
+        assert_eq!("2+0i,1+0i\n4+0i,3+0i\n", stdout);
+    }
+
+    #[test]
+    fn solve_to_file() {
+        let a = write_file("solve_a.csv", "2,0\n0,4\n");
+        let b = write_file("solve_b.csv", "2\n8\n");
+        let output = temp_path("solve_x.mtx");
+        let (result, stdout) = run_command(&["solve", &a, &b, "-o", &output]);
+        assert_eq!(Ok(()), result);
+        assert!(stdout.is_empty());
+        let expected = Matrix::from_array(2, 1, &[comp!(1.0, 0.0), comp!(2.0, 0.0)]).unwrap();
+        assert_complex_matrix(expected, load_matrix(&output).unwrap());
+    }
+
+    #[test]
+    fn hermitian_eigenvalues_are_sorted() {
+        let path = write_file("eig.csv", "2,1-1i\n1+1i,3\n");
+        let (result, stdout) = run_command(&["eig", &path, "--hermitian"]);
+        assert_eq!(Ok(()), result);
+        let values: Vec<f32> = stdout
+            .lines()
+            .map(|line| line.parse::<Complex<f32>>().unwrap().real)
+            .collect();
+        assert_approx_eq!(1.0, values[0], 1e-4);
+        assert_approx_eq!(4.0, values[1], 1e-4);
+
+        let path = write_file("eig_general.csv", "1,2\n0,1\n");
+        let (result, _) = run_command(&["eig", &path, "--hermitian"]);
+        assert_eq!(4, result.unwrap_err().exit_code());
+    }
+
+    #[test]
+    fn info_and_convert() {
+        let path = write_file("info.csv", "1,0\n0,2\n");
+        let output = temp_path("info.npy");
+        assert_eq!(Ok(()), run_command(&["convert", &path, &output]).0);
+        let (result, stdout) = run_command(&["info", &output]);
+        assert_eq!(Ok(()), result);
+        assert!(stdout.contains("shape: 2x2\n"));
+        assert!(stdout.contains("condition: 2\n"));
+        assert!(stdout.contains("hermitian: yes\n"));
+    }
+
+    #[test]
+    fn exit_codes() {
+        let square = write_file("codes_square.csv", "1,2\n2,4\n");
+        let column = write_file("codes_column.csv", "1\n1\n1\n");
+        assert_eq!(2, run_command(&[]).0.unwrap_err().exit_code());
+        assert_eq!(2, run_command(&["transpose", &square]).0.unwrap_err().exit_code());
+        assert_eq!(2, run_command(&["kron", &square]).0.unwrap_err().exit_code());
+        assert_eq!(2, run_command(&["info", "matrix.xyz"]).0.unwrap_err().exit_code());
+        assert_eq!(3, run_command(&["info", &temp_path("missing.csv")]).0.unwrap_err().exit_code());
+        assert_eq!(4, run_command(&["solve", &square, &square]).0.unwrap_err().exit_code());
+        assert_eq!(4, run_command(&["multiply", &square, &column]).0.unwrap_err().exit_code());
+    }
+}
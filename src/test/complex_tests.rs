@@ -116,5 +116,55 @@ mod tests {
         let expected = Complex::from(3.0f32, 2.0f32);
 
         assert_complex(expected, actual);
-    } 
+    }
+
+    #[test]
+    fn polar_test() {
+        let num = Complex::from_polar(2.0f32, 3.0 * std::f32::consts::FRAC_PI_4);
+        assert_complex(comp!(-std::f32::consts::SQRT_2, std::f32::consts::SQRT_2), num.clone());
+
+        let (norm, angle) = num.to_polar();
+        assert_approx_eq!(2.0f32, norm);
+        assert_approx_eq!(3.0 * std::f32::consts::FRAC_PI_4, angle);
+    }
+
+    #[test]
+    fn exp_ln_test() {
+        let num = comp!(0.5f32, -2.0f32);
+        assert_complex(num.clone(), num.exp().ln());
+
+        let expected = comp!(0.0f32, std::f32::consts::PI);
+        assert_complex(expected, comp!(-1.0f32, 0.0f32).ln());
+    }
+
+    #[test]
+    fn sqrt_test() {
+        assert_complex(comp!(0.0f32, 2.0f32), comp!(-4.0f32, 0.0f32).sqrt());
+        assert_complex(comp!(2.0f32, 1.0f32), comp!(3.0f32, 4.0f32).sqrt());
+    }
+
+    #[test]
+    fn sin_cos_test() {
+        let num = comp!(0.7f32, -0.3f32);
+        let sin = num.sin();
+        let cos = num.cos();
+        let result = sin.multiplication(&sin).addition(&cos.multiplication(&cos));
+        assert_complex(comp!(1.0f32, 0.0f32), result);
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(format!("{}", Complex::from(1.0f32, 2.0f32)), "1 + 2i");
+        assert_eq!(format!("{}", Complex::from(1.5f32, -0.25f32)), "1.5 - 0.25i");
+        assert_eq!(format!("{:.3}", Complex::from(1.0f32, -2.0f32)), "1.000 - 2.000i");
+    }
+
+    #[test]
+    fn display_width_test() {
+        let num = Complex::from(1.0f32, 2.0f32);
+        assert_eq!(format!("{:>8}", num), "  1 + 2i");
+        assert_eq!(format!("{:<8}|", num), "1 + 2i  |");
+        assert_eq!(format!("{:*^10.1}", num), "1.0 + 2.0i");
+        assert_eq!(format!("{:*^12}", num), "***1 + 2i***");
+    }
 }
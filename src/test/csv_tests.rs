@@ -0,0 +1,133 @@
+// Unit test module for delimited text
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::csv::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn test_matrix() -> Matrix {
+        Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 0.0),
+                comp!(-3.5, 0.0),
+                comp!(0.0, 0.25),
+                comp!(7.0, -1.0),
+                comp!(0.5, 0.0)
+            ])
+            .unwrap()
+    }
+
+    fn write(matrix: &Matrix, options: &CsvOptions) -> String {
+        let mut buffer = Vec::new();
+        matrix.write_csv(&mut buffer, options).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn read_csv_test() {
+        let text = "re,im\n1+2i, 0, -3.5\n\n\"0.25i\",7-i,0.5\n";
+        let options = CsvOptions {
+            header_lines: 1,
+            ..CsvOptions::csv()
+        };
+        let matrix = Matrix::read_csv(text.as_bytes(), &options).unwrap();
+        assert_eq!((matrix.num_rows, matrix.num_columns), (2, 3));
+        assert_complex_matrix(test_matrix(), matrix);
+    }
+
+    #[test]
+    fn read_paired_test() {
+        let text = "1\t2\t0\t0\t-3.5\t0\n0\t0.25\t7\t-1\t0.5\t0\n";
+        let options = CsvOptions {
+            complex_cells: ComplexCells::Paired,
+            ..CsvOptions::tsv()
+        };
+        assert_complex_matrix(test_matrix(), Matrix::read_csv(text.as_bytes(), &options).unwrap());
+    }
+
+    #[test]
+    fn read_whitespace_test() {
+        let text = "  1+2i   0  -3.5\n0.25i\t7-1i 0.5 \n";
+        let matrix = Matrix::read_csv(text.as_bytes(), &CsvOptions::whitespace()).unwrap();
+        assert_complex_matrix(test_matrix(), matrix);
+    }
+
+    #[test]
+    fn write_test() {
+        assert_eq!(write(&test_matrix(), &CsvOptions::csv()), "1+2i,0+0i,-3.5+0i\n0+0.25i,7-1i,0.5+0i\n");
+
+        let options = CsvOptions {
+            complex_cells: ComplexCells::Paired,
+            precision: Some(2),
+            ..CsvOptions::tsv()
+        };
+        assert_eq!(
+            write(&test_matrix(), &options),
+            "1.00\t2.00\t0.00\t0.00\t-3.50\t0.00\n0.00\t0.25\t7.00\t-1.00\t0.50\t0.00\n"
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for options in [CsvOptions::csv(), CsvOptions::tsv(), CsvOptions::whitespace()] {
+            for complex_cells in [ComplexCells::Combined, ComplexCells::Paired] {
+                let options = CsvOptions {
+                    complex_cells,
+                    ..options.clone()
+                };
+                let text = write(&test_matrix(), &options);
+                assert_complex_matrix(test_matrix(), Matrix::read_csv(text.as_bytes(), &options).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn vector_test() {
+        let column = Vector::read_csv("1\n2i\n3\n".as_bytes(), &CsvOptions::csv()).unwrap();
+        assert_eq!(column.get_type(), VectorType::COLUMN_VECTOR);
+        assert_complex_vec(Vector::from_array(&[comp!(1.0, 0.0), comp!(0.0, 2.0), comp!(3.0, 0.0)]), column.clone());
+
+        let mut row = column;
+        row.transpose();
+        let mut buffer = Vec::new();
+        row.write_csv(&mut buffer, &CsvOptions::csv()).unwrap();
+        assert_eq!(String::from_utf8(buffer.clone()).unwrap(), "1+0i,0+2i,3+0i\n");
+        assert_eq!(Vector::read_csv(buffer.as_slice(), &CsvOptions::csv()).unwrap().get_type(), VectorType::ROW_VECTOR);
+
+        assert!(Vector::read_csv("1,2\n3,4\n".as_bytes(), &CsvOptions::csv()).is_err());
+    }
+
+    #[test]
+    fn ragged_row_test() {
+        let text = "header\n1,2,3\n\n4,5,6\n7,8\n";
+        let options = CsvOptions {
+            header_lines: 1,
+            ..CsvOptions::csv()
+        };
+        let error = Matrix::read_csv(text.as_bytes(), &options).unwrap_err();
+        assert!(matches!(error, CsvError::Parse { line: 5, .. }));
+        assert_eq!(
+            format!("{}", error),
+            "Row has a different number of elements than the first row in line 5"
+        );
+    }
+
+    #[test]
+    fn invalid_cell_test() {
+        let error = Matrix::read_csv("1,2\n3,x\n".as_bytes(), &CsvOptions::csv()).unwrap_err();
+        assert!(matches!(error, CsvError::Parse { line: 2, reason: "Expected a number" }));
+
+        let options = CsvOptions {
+            complex_cells: ComplexCells::Paired,
+            ..CsvOptions::csv()
+        };
+        let error = Matrix::read_csv("1,2,3\n".as_bytes(), &options).unwrap_err();
+        assert!(matches!(error, CsvError::Parse { line: 1, reason: "Paired columns need an even number of cells" }));
+    }
+}
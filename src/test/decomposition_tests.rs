@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+
+    fn test_matrix() -> Matrix {
+        Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(4.0, 1.0), comp!(-2.0, 0.0), comp!(1.0, 0.5),
+                comp!(3.0, 0.0), comp!(6.0, -1.0), comp!(-4.0, 0.0),
+                comp!(2.0, 2.0), comp!(1.0, 0.0), comp!(8.0, 0.0),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn solve_test() {
+        let matrix = test_matrix();
+        let expected = Matrix::from_array(
+            3,
+            1,
+            &[comp!(1.0, 0.0), comp!(0.0, -1.0), comp!(2.0, 1.0)],
+        )
+        .unwrap();
+
+        let mut rhs = Matrix::zeros(3, 1);
+        for row_index in 0..3 {
+            let mut element = Complex::new();
+            for column_index in 0..3 {
+                let product = matrix
+                    .get_element(row_index, column_index)
+                    .unwrap()
+                    .multiplication(&expected.get_element(column_index, 0).unwrap());
+                element = element.addition(&product);
+            }
+            rhs.set_element(row_index, 0, element).unwrap();
+        }
+
+        let actual = matrix.solve(&rhs).unwrap();
+        assert_complex_matrix(expected, actual);
+    }
+
+    #[test]
+    fn inverse_test() {
+        let matrix = test_matrix();
+        let inverse = matrix.inverse().unwrap();
+
+        assert_complex_matrix(Matrix::identity(3), matrix.multiply(&inverse).unwrap());
+        assert_complex_matrix(Matrix::identity(3), inverse.multiply(&matrix).unwrap());
+    }
+
+    #[test]
+    fn small_magnitude_inverse_test() {
+        // well conditioned, but every pivot is far below the tolerance
+        // of Complex::division
+        let mut small = Matrix::identity(3);
+        small.scale(1e-3);
+        let mut solution = small.solve(&Matrix::identity(3)).unwrap();
+        solution.scale(1e-3);
+        assert_complex_matrix(Matrix::identity(3), solution);
+
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1e-3, 2e-3), comp!(0.0, -1e-3), comp!(3e-4, 0.0), comp!(-2e-3, 1e-3)],
+        )
+        .unwrap();
+        let inverse = matrix.inverse().unwrap();
+        assert_complex_matrix(Matrix::identity(2), matrix.multiply(&inverse).unwrap());
+        assert_complex_matrix(Matrix::identity(2), inverse.multiply(&matrix).unwrap());
+    }
+
+    #[test]
+    fn singular_inverse_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 1.0), comp!(2.0, 2.0), comp!(2.0, 2.0), comp!(4.0, 4.0)],
+        )
+        .unwrap();
+
+        assert!(matrix.inverse().is_err());
+    }
+
+    #[test]
+    fn qr_decomposition_test() {
+        let matrix = Matrix::from_array(
+            3,
+            2,
+            &[
+                comp!(1.0, 1.0), comp!(2.0, 0.0),
+                comp!(0.0, -1.0), comp!(1.0, 3.0),
+                comp!(4.0, 0.0), comp!(-1.0, 0.5),
+            ],
+        )
+        .unwrap();
+        let (unitary, triangular) = matrix.qr_decomposition().unwrap();
+
+        assert_eq!(3, unitary.num_rows);
+        assert_eq!(3, unitary.num_columns);
+        assert_eq!(3, triangular.num_rows);
+        assert_eq!(2, triangular.num_columns);
+        for (row_index, column_index) in [(1, 0), (2, 0), (2, 1)] {
+            assert_complex(comp!(0.0, 0.0), triangular.get_element(row_index, column_index).unwrap());
+        }
+
+        let mut adjoint = unitary.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(Matrix::identity(3), unitary.multiply(&adjoint).unwrap());
+
+        for row_index in 0..3 {
+            for column_index in 0..2 {
+                let mut element = Complex::new();
+                for inner_index in 0..3 {
+                    let product = unitary
+                        .get_element(row_index, inner_index)
+                        .unwrap()
+                        .multiplication(&triangular.get_element(inner_index, column_index).unwrap());
+                    element = element.addition(&product);
+                }
+                assert_complex(matrix.get_element(row_index, column_index).unwrap(), element);
+            }
+        }
+    }
+
+    #[test]
+    fn schur_decomposition_test() {
+        let matrix = test_matrix();
+        let (unitary, triangular) = matrix.schur_decomposition().unwrap();
+
+        for row_index in 1..3 {
+            for column_index in 0..row_index {
+                assert_complex(comp!(0.0, 0.0), triangular.get_element(row_index, column_index).unwrap());
+            }
+        }
+
+        let mut adjoint = unitary.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(Matrix::identity(3), unitary.multiply(&adjoint).unwrap());
+
+        let reconstructed = unitary.multiply(&triangular).unwrap().multiply(&adjoint).unwrap();
+        assert_complex_matrix(matrix, reconstructed);
+    }
+
+    #[test]
+    fn schur_complex_eigenvalues_test() {
+        // real rotation generator with eigenvalues +i and -i
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.0, 0.0), comp!(1.0, 0.0), comp!(-1.0, 0.0), comp!(0.0, 0.0)],
+        )
+        .unwrap();
+
+        let mut eigenvalues = matrix.calculate_eigenvalues().unwrap();
+        eigenvalues.sort_by(|a, b| a.imaginary.partial_cmp(&b.imaginary).unwrap());
+
+        assert_complex(comp!(0.0, -1.0), eigenvalues[0].clone());
+        assert_complex(comp!(0.0, 1.0), eigenvalues[1].clone());
+    }
+
+    fn assert_singular_value_decomposition(matrix: Matrix) {
+        let (left, singular_values, right) = matrix.singular_value_decomposition().unwrap();
+        let rank = usize::min(matrix.num_rows, matrix.num_columns);
+        assert_eq!(rank, singular_values.len());
+        assert!(singular_values.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        // U^H U = V^H V = I
+        let mut left_adjoint = left.clone();
+        left_adjoint.adjoint();
+        let mut right_adjoint = right.clone();
+        right_adjoint.adjoint();
+        for (unitary, adjoint) in [(&left, &left_adjoint), (&right, &right_adjoint)] {
+            for first in 0..rank {
+                for second in 0..rank {
+                    let mut element = Complex::new();
+                    for index in 0..unitary.num_rows {
+                        let product = adjoint
+                            .get_element(first, index)
+                            .unwrap()
+                            .multiplication(&unitary.get_element(index, second).unwrap());
+                        element = element.addition(&product);
+                    }
+                    let expected = if first == second { 1.0 } else { 0.0 };
+                    assert_complex(comp!(expected, 0.0), element);
+                }
+            }
+        }
+
+        // A = U S V^H
+        for row_index in 0..matrix.num_rows {
+            for column_index in 0..matrix.num_columns {
+                let mut element = Complex::new();
+                for (index, singular_value) in singular_values.iter().enumerate() {
+                    let mut product = left
+                        .get_element(row_index, index)
+                        .unwrap()
+                        .multiplication(&right_adjoint.get_element(index, column_index).unwrap());
+                    product.scale(*singular_value);
+                    element = element.addition(&product);
+                }
+                assert_complex(matrix.get_element(row_index, column_index).unwrap(), element);
+            }
+        }
+    }
+
+    #[test]
+    fn singular_value_decomposition_test() {
+        let matrix = Matrix::from_array(
+            3,
+            2,
+            &[
+                comp!(1.0, 0.0), comp!(2.0, -1.0),
+                comp!(0.0, 3.0), comp!(-1.0, 0.0),
+                comp!(2.0, 2.0), comp!(0.5, 0.0),
+            ],
+        )
+        .unwrap();
+        assert_singular_value_decomposition(matrix);
+
+        let wide = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 1.0), comp!(0.0, 0.0), comp!(-2.0, 0.0),
+                comp!(0.0, 0.0), comp!(3.0, 0.0), comp!(0.0, 1.0),
+            ],
+        )
+        .unwrap();
+        assert_singular_value_decomposition(wide);
+    }
+
+    #[test]
+    fn singular_values_test() {
+        // rank deficient matrix with singular values 5, 0
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(3.0, 0.0), comp!(0.0, 0.0), comp!(4.0, 0.0), comp!(0.0, 0.0)],
+        )
+        .unwrap();
+
+        let (_, singular_values, _) = matrix.singular_value_decomposition().unwrap();
+        assert_approx_eq!(5.0f32, singular_values[0], 1e-4);
+        assert_approx_eq!(0.0f32, singular_values[1], 1e-4);
+        assert_singular_value_decomposition(matrix);
+    }
+}
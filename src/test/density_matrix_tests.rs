@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::density_matrix::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn bell_state() -> Vector {
+        let factor = f32::sqrt(0.5);
+        Vector::from_array(&[
+            comp!(factor, 0.0),
+            comp!(0.0, 0.0),
+            comp!(0.0, 0.0),
+            comp!(factor, 0.0),
+        ])
+    }
+
+    fn plus_state() -> Vector {
+        let factor = f32::sqrt(0.5);
+        Vector::from_array(&[comp!(factor, 0.0), comp!(factor, 0.0)])
+    }
+
+    fn basis_state(size: usize, index: usize) -> Vector {
+        let mut state = Vector::zeros(size);
+        state.set_element(index, comp!(1.0, 0.0)).unwrap();
+        state
+    }
+
+    #[test]
+    fn density_matrix_test() {
+        let state = Vector::from_array(&[comp!(0.6, 0.0), comp!(0.0, 0.8)]);
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.36, 0.0), comp!(0.0, -0.48), comp!(0.0, 0.48), comp!(0.64, 0.0)],
+        )
+        .unwrap();
+
+        let density = density_matrix(&state).unwrap();
+        assert_complex(comp!(1.0, 0.0), density.trace().unwrap());
+        assert_complex_matrix(expected, density);
+    }
+
+    #[test]
+    fn partial_trace_bell_test() {
+        let density = density_matrix(&bell_state()).unwrap();
+        let mut expected = Matrix::identity(2);
+        expected.scale(0.5);
+
+        assert_complex_matrix(expected.clone(), partial_trace(&density, &[2, 2], &[0]).unwrap());
+        assert_complex_matrix(expected, partial_trace(&density, &[2, 2], &[1]).unwrap());
+    }
+
+    #[test]
+    fn partial_trace_product_state_test() {
+        // |1> (x) |2> (x) |+> with dimensions 2, 3, 2
+        let state = basis_state(2, 1)
+            .kron_vector(&basis_state(3, 2))
+            .unwrap()
+            .kron_vector(&plus_state())
+            .unwrap();
+        let density = density_matrix(&state).unwrap();
+
+        let outer = partial_trace(&density, &[2, 3, 2], &[1]).unwrap();
+        let expected = density_matrix(&basis_state(2, 1).kron_vector(&plus_state()).unwrap()).unwrap();
+        assert_eq!(4, outer.num_rows);
+        assert_complex_matrix(expected, outer);
+
+        let middle = partial_trace(&density, &[2, 3, 2], &[2, 0]).unwrap();
+        assert_complex_matrix(density_matrix(&basis_state(3, 2)).unwrap(), middle);
+
+        let everything = partial_trace(&density, &[2, 3, 2], &[0, 1, 2]).unwrap();
+        assert_complex(comp!(1.0, 0.0), everything.get_element(0, 0).unwrap());
+
+        assert!(partial_trace(&density, &[2, 2, 2], &[0]).is_err());
+        assert!(partial_trace(&density, &[2, 3, 2], &[3]).is_err());
+        assert!(partial_trace(&density, &[2, 3, 2], &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn purity_test() {
+        let pure = density_matrix(&bell_state()).unwrap();
+        assert_approx_eq!(1.0f32, purity(&pure).unwrap(), 1e-4);
+
+        let reduced = partial_trace(&pure, &[2, 2], &[1]).unwrap();
+        assert_approx_eq!(0.5f32, purity(&reduced).unwrap(), 1e-4);
+    }
+
+    #[test]
+    fn von_neumann_entropy_test() {
+        let pure = density_matrix(&bell_state()).unwrap();
+        assert_approx_eq!(0.0f32, von_neumann_entropy(&pure).unwrap(), 1e-4);
+
+        // one half of a bell pair carries one bit of entropy
+        let reduced = partial_trace(&pure, &[2, 2], &[0]).unwrap();
+        assert_approx_eq!(1.0f32, von_neumann_entropy(&reduced).unwrap(), 1e-4);
+    }
+
+    #[test]
+    fn fidelity_test() {
+        let zero = density_matrix(&basis_state(2, 0)).unwrap();
+        let one = density_matrix(&basis_state(2, 1)).unwrap();
+        let plus = density_matrix(&plus_state()).unwrap();
+        let mut mixed = Matrix::identity(2);
+        mixed.scale(0.5);
+
+        assert_approx_eq!(1.0f32, fidelity(&zero, &zero).unwrap(), 1e-3);
+        assert_approx_eq!(0.0f32, fidelity(&zero, &one).unwrap(), 1e-3);
+        assert_approx_eq!(0.5f32, fidelity(&zero, &plus).unwrap(), 1e-3);
+        assert_approx_eq!(0.5f32, fidelity(&mixed, &zero).unwrap(), 1e-3);
+    }
+
+    #[test]
+    fn trace_distance_test() {
+        let zero = density_matrix(&basis_state(2, 0)).unwrap();
+        let one = density_matrix(&basis_state(2, 1)).unwrap();
+        let plus = density_matrix(&plus_state()).unwrap();
+
+        assert_approx_eq!(0.0f32, trace_distance(&zero, &zero).unwrap(), 1e-4);
+        assert_approx_eq!(1.0f32, trace_distance(&zero, &one).unwrap(), 1e-4);
+        assert_approx_eq!(f32::sqrt(0.5), trace_distance(&zero, &plus).unwrap(), 1e-4);
+    }
+
+    fn assert_reconstructs(state: &Vector, decomposition: &SchmidtDecomposition) {
+        let mut reconstructed = Vector::zeros(state.size());
+        for (index, coefficient) in decomposition.coefficients.iter().enumerate() {
+            let mut term = decomposition.states_a[index]
+                .kron_vector(&decomposition.states_b[index])
+                .unwrap();
+            term.scale(*coefficient);
+            for (element_index, element) in term.iter().enumerate() {
+                let sum = reconstructed.get_element(element_index).unwrap().addition(&element);
+                reconstructed.set_element(element_index, sum).unwrap();
+            }
+        }
+        assert_complex_vec(state.clone(), reconstructed);
+    }
+
+    #[test]
+    fn schmidt_decomposition_test() {
+        let decomposition = schmidt_decomposition(&bell_state(), 2, 2).unwrap();
+        assert_eq!(2, decomposition.coefficients.len());
+        assert_approx_eq!(f32::sqrt(0.5), decomposition.coefficients[0], 1e-4);
+        assert_approx_eq!(f32::sqrt(0.5), decomposition.coefficients[1], 1e-4);
+        assert_reconstructs(&bell_state(), &decomposition);
+
+        let product = basis_state(2, 1).kron_vector(&plus_state()).unwrap();
+        let decomposition = schmidt_decomposition(&product, 2, 2).unwrap();
+        assert_eq!(1, decomposition.coefficients.len());
+        assert_approx_eq!(1.0f32, decomposition.coefficients[0], 1e-4);
+        assert_reconstructs(&product, &decomposition);
+    }
+
+    #[test]
+    fn schmidt_decomposition_rectangular_test() {
+        let mut state = Vector::from_array(&[
+            comp!(0.3, 0.1), comp!(0.0, -0.2), comp!(0.5, 0.0),
+            comp!(-0.1, 0.4), comp!(0.2, 0.2), comp!(0.0, 0.6),
+        ]);
+        state.normalize();
+
+        let decomposition = schmidt_decomposition(&state, 2, 3).unwrap();
+        let squares: f32 = decomposition.coefficients.iter().map(|c| c * c).sum();
+        assert_approx_eq!(1.0f32, squares, 1e-4);
+        assert_reconstructs(&state, &decomposition);
+
+        assert!(schmidt_decomposition(&state, 2, 2).is_err());
+    }
+}
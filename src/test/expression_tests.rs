@@ -0,0 +1,188 @@
+// Unit test module for expressions
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::expression::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+
+    fn run(source: &str, variables: &Bindings) -> Result<Value, ExpressionError> {
+        parse(source).and_then(|expression| evaluate(&expression, variables))
+    }
+
+    fn matrix(value: Value) -> Matrix {
+        value.to_matrix()
+    }
+
+    #[test]
+    fn scalar_arithmetic() {
+        let value = run("(1 + 2i) * 3 - 4 / 2", &Bindings::new()).unwrap();
+        match value {
+            Value::Scalar(scalar) => assert_complex(comp!(1.0, 6.0), scalar),
+            _ => panic!("expected a scalar"),
+        }
+    }
+
+    #[test]
+    fn matrix_product_and_adjoint() {
+        let mut variables = Bindings::new();
+        variables.bind("a", run("[1 2; 3 4i]", &Bindings::new()).unwrap());
+        let value = run("a' * a", &variables).unwrap();
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(10.0, 0.0), comp!(2.0, 12.0), comp!(2.0, -12.0), comp!(20.0, 0.0)])
+            .unwrap();
+        assert_complex_matrix(expected, matrix(value));
+    }
+
+    #[test]
+    fn kron_and_solve() {
+        let variables = Bindings::new();
+        let value = run("kron([1 0; 0 1], [2])", &variables).unwrap();
+        assert_eq!((2, 2), value.shape());
+
+        let value = run("solve([2 0; 0 4], [2; 8])", &variables).unwrap();
+        assert_eq!((2, 1), value.shape());
+        let expected = Matrix::from_array(2, 1, &[comp!(1.0, 0.0), comp!(2.0, 0.0)]).unwrap();
+        assert_complex_matrix(expected, matrix(value));
+    }
+
+    #[test]
+    fn eig_of_diagonal() {
+        let value = run("eig([3 0; 0 5])", &Bindings::new()).unwrap();
+        let eigenvalues = matrix(value);
+        let mut eigenvalues: Vec<f32> = (0..eigenvalues.num_rows * eigenvalues.num_columns)
+            .map(|index| eigenvalues.get_element(index / eigenvalues.num_columns, index % eigenvalues.num_columns).unwrap().real)
+            .collect();
+        eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_approx_eq!(3.0, eigenvalues[0], 1e-4);
+        assert_approx_eq!(5.0, eigenvalues[1], 1e-4);
+    }
+
+    #[test]
+    fn unknown_variable_points_at_name() {
+        let error = run("1 + foo", &Bindings::new()).unwrap_err();
+        assert_eq!((4, 7), (error.start, error.end));
+        assert!(error.render("1 + foo").contains("    ^^^"));
+    }
+
+    #[test]
+    fn shape_mismatch_is_reported() {
+        let error = run("[1 2] * [1 2]", &Bindings::new()).unwrap_err();
+        assert_eq!(0, error.start);
+        assert_eq!(13, error.end);
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(parse("1 +").is_err());
+        assert!(parse("(1 + 2").is_err());
+        assert!(parse("kron(1,)").is_err());
+    }
+
+    #[test]
+    fn bindings_from_library_values() {
+        let pauli_x = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.0, 0.0), comp!(1.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0)])
+            .unwrap();
+        let pauli_z = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(-1.0, 0.0)])
+            .unwrap();
+        let mut bindings = Bindings::new();
+        bindings
+            .bind("X", pauli_x.clone())
+            .bind("Z", pauli_z.clone())
+            .bind("I", Matrix::identity(2))
+            .bind("g", comp!(0.5, 0.0));
+        assert_eq!(vec!["I", "X", "Z", "g"], bindings.names());
+
+        let value = bindings.evaluate("kron(X, I) + g * kron(Z, Z)").unwrap();
+        let mut expected = pauli_z.kronecker_product(&pauli_z).unwrap();
+        expected.scale(0.5);
+        let expected = pauli_x.kronecker_product(&Matrix::identity(2)).unwrap().add(&expected).unwrap();
+        assert_complex_matrix(expected, matrix(value));
+    }
+
+    #[test]
+    fn aliases() {
+        let bindings = Bindings::new();
+        let value = bindings.evaluate("tr(adj([1 2i; 3 4]))").unwrap();
+        match value {
+            Value::Scalar(scalar) => assert_complex(comp!(5.0, 0.0), scalar),
+            _ => panic!("expected a scalar"),
+        }
+        let value = bindings.evaluate("inv([2 0; 0 4]) * expm(zeros(2, 2))").unwrap();
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.5, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(0.25, 0.0)])
+            .unwrap();
+        assert_complex_matrix(expected, matrix(value));
+    }
+
+    #[test]
+    fn shapes_are_checked_before_evaluation() {
+        let mut bindings = Bindings::new();
+        bindings.bind("a", Matrix::identity(3)).bind("v", Matrix::zeros(2, 1));
+        let expression = parse("kron(a, a) + a").unwrap();
+        assert_eq!(Ok(Shape::Matrix(9, 9)), check(&parse("kron(a, a)").unwrap(), &bindings));
+        let error = check(&expression, &bindings).unwrap_err();
+        assert_eq!((0, 14), (error.start, error.end));
+        assert_eq!("Cannot add a 9x9 matrix and a 3x3 matrix", error.reason);
+
+        // the singular matrix is never inverted because the shapes do not match
+        let error = bindings.evaluate("inv(zeros(3, 3)) * v").unwrap_err();
+        assert_eq!("Cannot multiply a 3x3 matrix by a 2x1 matrix", error.reason);
+
+        assert_eq!(Ok(Shape::Vector(3, 1)), check(&parse("eig(a)").unwrap(), &bindings));
+        assert_eq!(Ok(Shape::Vector(1, 3)), check(&parse("[1; 2; 3]'").unwrap(), &bindings));
+        bindings.bind("n", comp!(2.0, 0.0));
+        assert_eq!(Ok(Shape::Unknown), check(&parse("eye(n)").unwrap(), &bindings));
+        assert_eq!(Ok(Shape::Scalar), check(&parse("tr(eye(n))").unwrap(), &bindings));
+    }
+
+    #[test]
+    fn errors_point_at_arguments() {
+        let mut bindings = Bindings::new();
+        bindings.bind("m", Matrix::zeros(2, 3));
+        let source = "1 + expm(m)";
+        let error = bindings.evaluate(source).unwrap_err();
+        assert_eq!((9, 10), (error.start, error.end));
+        assert_eq!("Argument has to be a square matrix", error.reason);
+        assert_eq!("1 + expm(m)\n         ^\nArgument has to be a square matrix", error.render(source));
+
+        let error = bindings.evaluate("solve(eye(3), [1; 2])").unwrap_err();
+        assert_eq!((14, 20), (error.start, error.end));
+        let error = bindings.evaluate("tr(m, m)").unwrap_err();
+        assert_eq!((0, 8), (error.start, error.end));
+        assert_eq!("tr expects 1 argument(s)", error.reason);
+    }
+
+    #[test]
+    fn created_sizes_are_bounded() {
+        let mut bindings = Bindings::new();
+        let error = bindings.evaluate("eye(100000)").unwrap_err();
+        assert_eq!((4, 10), (error.start, error.end));
+        assert_eq!("Matrix would have too many elements", error.reason);
+        let error = bindings.evaluate("zeros(2, 1e30)").unwrap_err();
+        assert_eq!((9, 13), (error.start, error.end));
+
+        // sizes bound to names are only known during evaluation
+        bindings.bind("n", comp!(100000.0, 0.0));
+        let error = bindings.evaluate("1 + zeros(n, n)").unwrap_err();
+        assert_eq!((13, 14), (error.start, error.end));
+        let error = bindings.evaluate("eye(-n)").unwrap_err();
+        assert_eq!((4, 6), (error.start, error.end));
+        assert_eq!("Argument has to be a non negative integer", error.reason);
+
+        bindings.bind("n", comp!(3.0, 0.0));
+        let sum = matrix(bindings.evaluate("zeros(n, n) + eye(n)").unwrap());
+        assert_eq!((3, 3), (sum.num_rows, sum.num_columns));
+    }
+}
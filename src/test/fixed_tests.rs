@@ -0,0 +1,86 @@
+// Unit test module for the fixed size vectors and matrices
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::fixed::*;
+    use crate::matrix::*;
+    use crate::quantum::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    type ComplexNum = Complex<FloatType>;
+
+    #[test]
+    fn vector_arithmetic() {
+        let a = SVector::from_array([comp!(1.0, 2.0), comp!(-3.0, 0.5), comp!(0.0, -1.0)]);
+        let b = SVector::from_array([comp!(4.0, -1.0), comp!(2.0, 2.0), comp!(1.5, 0.0)]);
+        let (dynamic_a, dynamic_b) = (a.to_vector(), b.to_vector());
+
+        assert_complex_vec(dynamic_a.add(&dynamic_b).unwrap(), a.add(&b).to_vector());
+        assert_complex_vec(dynamic_a.subtract(&dynamic_b).unwrap(), a.subtract(&b).to_vector());
+        assert_complex(dynamic_a.inner_product(&dynamic_b).unwrap(), a.inner_product(&b));
+        assert_complex(comp!(-4.0, 2.0), a.scale(&comp!(0.0, 2.0)).get_element(0).unwrap().clone());
+        assert_complex(comp!(0.0, 1.0), a.conjugate().get_element(2).unwrap().clone());
+    }
+
+    #[test]
+    fn basis_and_bounds() {
+        let e1: SVector<ComplexNum, 3> = SVector::basis(1).unwrap();
+        assert_complex(comp!(1.0, 0.0), e1.get_element(1).unwrap().clone());
+        assert_complex(comp!(0.0, 0.0), e1.get_element(2).unwrap().clone());
+        assert!(SVector::<ComplexNum, 3>::basis(3).is_err());
+        assert!(e1.get_element(3).is_none());
+
+        let mut zeros = SMatrix::<ComplexNum, 2, 3>::zeros();
+        assert!(zeros.set_element(1, 2, comp!(5.0, 0.0)).is_ok());
+        assert!(zeros.set_element(2, 0, comp!(5.0, 0.0)).is_err());
+        assert_eq!((2, 3), zeros.dimensions());
+    }
+
+    #[test]
+    fn products_match_dynamic_matrices() {
+        let a = SMatrix::<ComplexNum, 2, 3>::from_fn(|i, j| comp!((i + j) as FloatType, i as FloatType - 1.0));
+        let b = SMatrix::<ComplexNum, 3, 4>::from_fn(|i, j| comp!(j as FloatType * 0.5, (i * j) as FloatType));
+        let x = SVector::<ComplexNum, 3>::from_fn(|i| comp!(1.0, i as FloatType));
+        let (dynamic_a, dynamic_b) = (a.to_matrix(), b.to_matrix());
+
+        let product: SMatrix<ComplexNum, 2, 4> = a.multiply(&b);
+        assert_complex_matrix(dynamic_a.multiply(&dynamic_b).unwrap(), product.to_matrix());
+        let image = dynamic_a.multiply_vector(&x.to_vector()).unwrap();
+        assert_complex_vec(image, a.multiply_vector(&x).to_vector());
+
+        let mut adjoint = dynamic_a.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(adjoint, a.adjoint().to_matrix());
+        assert_complex_matrix(dynamic_a.add(&dynamic_a).unwrap(), a.add(&a).to_matrix());
+    }
+
+    #[test]
+    fn square_matrices() {
+        let identity = SMatrix::<ComplexNum, 4, 4>::identity();
+        assert_complex(comp!(4.0, 0.0), identity.trace());
+
+        // the outer product of a column and a row vector
+        let x = SVector::from_array([comp!(1.0, 0.0), comp!(0.0, 1.0)]);
+        let y = SVector::from_array([comp!(2.0, 0.0), comp!(0.0, 0.0), comp!(1.0, 1.0)]);
+        let outer = x.outer_product(&y);
+        assert_complex_matrix(x.to_column().multiply(&y.to_row()).to_matrix(), outer.to_matrix());
+        assert_complex(comp!(-1.0, 1.0), outer.get_element(1, 2).unwrap().clone());
+    }
+
+    #[test]
+    fn conversions_check_dimensions() {
+        let hadamard_gate = SMatrix::<ComplexNum, 2, 2>::try_from(&hadamard()).unwrap();
+        assert_complex_matrix(Matrix::identity(2), hadamard_gate.multiply(&hadamard_gate).to_matrix());
+        assert!(SMatrix::<ComplexNum, 4, 4>::try_from(&hadamard()).is_err());
+        assert!(SMatrix::<ComplexNum, 4, 4>::try_from(&cnot()).is_ok());
+
+        let vector = Vector::ones(3);
+        assert!(SVector::<ComplexNum, 3>::try_from(&vector).is_ok());
+        assert!(SVector::<ComplexNum, 2>::try_from(&vector).is_err());
+        let column = SMatrix::<ComplexNum, 3, 1>::try_from(&Matrix::from_columns(&[vector]).unwrap()).unwrap();
+        assert_eq!(3, column.column(0).unwrap().size());
+        assert!(column.column(1).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+// Unit test module for the blocked matrix multiplication
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::gemm::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+
+    fn apply(matrix: &Matrix, op: Op) -> Matrix {
+        let mut result = matrix.clone();
+        match op {
+            Op::Normal => {}
+            Op::Transpose => result.transpose(),
+            Op::Adjoint => result.adjoint(),
+        }
+        result
+    }
+
+    fn reference_product(a: &Matrix, b: &Matrix) -> Matrix {
+        // textbook triple loop
+        Matrix::from_fn(a.num_rows, b.num_columns, |row, column| {
+            let mut sum = Complex::new();
+            for index in 0..a.num_columns {
+                let product = a
+                    .get_element(row, index)
+                    .unwrap()
+                    .multiplication(&b.get_element(index, column).unwrap());
+                sum = sum.addition(&product);
+            }
+            sum
+        })
+    }
+
+    fn assert_close(expected: &Matrix, actual: &Matrix, tolerance: f32) {
+        assert_eq!((expected.num_rows, expected.num_columns), (actual.num_rows, actual.num_columns));
+        for row in 0..expected.num_rows {
+            for column in 0..expected.num_columns {
+                let expected = expected.get_element(row, column).unwrap();
+                let actual = actual.get_element(row, column).unwrap();
+                assert_approx_eq!(expected.real, actual.real, tolerance);
+                assert_approx_eq!(expected.imaginary, actual.imaginary, tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn small_product() {
+        let a = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 0.0),
+                comp!(2.0, 1.0),
+                comp!(0.0, -1.0),
+                comp!(3.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(1.0, 1.0)
+            ])
+            .unwrap();
+        let b = Matrix::from_array(3, 1, &[comp!(1.0, 0.0), comp!(0.0, 1.0), comp!(2.0, 0.0)]).unwrap();
+        let expected = Matrix::from_array(2, 1, &[comp!(0.0, 0.0), comp!(5.0, 2.0)]).unwrap();
+        let product = a.multiply(&b).unwrap();
+        assert_eq!((2, 1), (product.num_rows, product.num_columns));
+        assert_complex_matrix(expected, product);
+    }
+
+    #[test]
+    fn blocked_sizes_match_reference() {
+        // sizes that leave partial micro tiles and cross the cache blocks
+        for (m, k, n) in [(1, 1, 1), (5, 3, 9), (13, 300, 17), (130, 7, 3), (67, 260, 70)] {
+            let a = Matrix::random_gaussian(m, k, 1);
+            let b = Matrix::random_gaussian(k, n, 2);
+            let tolerance = 1e-5 * k as f32 + 1e-5;
+            assert_close(&reference_product(&a, &b), &a.multiply(&b).unwrap(), tolerance);
+        }
+    }
+
+    #[test]
+    fn transposition_flags() {
+        let ops = [Op::Normal, Op::Transpose, Op::Adjoint];
+        for op_a in ops {
+            for op_b in ops {
+                let a = apply(&Matrix::random_gaussian(6, 11, 3), op_a);
+                let b = apply(&Matrix::random_gaussian(11, 9, 4), op_b);
+                // op(op(X)) = X for all three flags
+                let product = a.multiply_op(op_a, &b, op_b).unwrap();
+                let expected = reference_product(&apply(&a, op_a), &apply(&b, op_b));
+                assert_close(&expected, &product, 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn alpha_and_beta() {
+        let a = Matrix::random_gaussian(7, 5, 5);
+        let b = Matrix::random_gaussian(7, 9, 6);
+        let c = Matrix::random_gaussian(5, 9, 7);
+        let alpha = comp!(0.5, -2.0);
+        let beta = comp!(-1.0, 0.5);
+
+        let mut result = c.clone();
+        result.gemm(alpha.clone(), &a, Op::Adjoint, &b, Op::Normal, beta.clone()).unwrap();
+
+        let product = reference_product(&apply(&a, Op::Adjoint), &b);
+        let expected = Matrix::from_fn(5, 9, |row, column| {
+            let scaled_product = product.get_element(row, column).unwrap().multiplication(&alpha);
+            let scaled_c = c.get_element(row, column).unwrap().multiplication(&beta);
+            scaled_product.addition(&scaled_c)
+        });
+        assert_close(&expected, &result, 1e-4);
+    }
+
+    #[test]
+    fn zero_beta_overwrites() {
+        let a = Matrix::identity(3);
+        let mut c = Matrix::from_fn(3, 3, |_, _| comp!(f32::NAN, 0.0));
+        c.gemm(comp!(2.0, 0.0), &a, Op::Normal, &a, Op::Normal, comp!(0.0, 0.0)).unwrap();
+        let mut expected = Matrix::identity(3);
+        expected.scale(2.0);
+        assert_complex_matrix(expected, c);
+    }
+
+    #[test]
+    fn empty_and_mismatched() {
+        let a = Matrix::zeros(3, 0);
+        let b = Matrix::zeros(0, 4);
+        let product = a.multiply(&b).unwrap();
+        assert_complex_matrix(Matrix::zeros(3, 4), product);
+
+        assert!(Matrix::zeros(2, 3).multiply(&Matrix::zeros(2, 3)).is_err());
+        assert!(Matrix::zeros(2, 3)
+            .multiply_op(Op::Transpose, &Matrix::zeros(2, 3), Op::Normal)
+            .is_ok());
+        let mut c = Matrix::zeros(2, 2);
+        let identity = Matrix::identity(3);
+        assert!(c
+            .gemm(comp!(1.0, 0.0), &identity, Op::Normal, &identity, Op::Normal, comp!(0.0, 0.0))
+            .is_err());
+    }
+}
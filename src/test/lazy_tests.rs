@@ -0,0 +1,97 @@
+// Unit test module for the lazy elementwise expressions
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::lazy::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    #[test]
+    fn fused_vector_expression() {
+        let a = Vector::from_array(&[comp!(1.0, 2.0), comp!(-3.0, 0.5), comp!(0.0, -1.0)]);
+        let b = Vector::from_array(&[comp!(4.0, -1.0), comp!(2.0, 2.0), comp!(1.5, 0.0)]);
+        let c = Vector::from_array(&[comp!(0.5, 0.5), comp!(-1.0, 3.0), comp!(2.0, -2.0)]);
+
+        let mut expected = a.clone();
+        expected.scale(2.0);
+        let expected = expected.add(&b).unwrap().subtract(&c).unwrap();
+        let actual = a.lazy().scale(2.0).add(b.lazy()).unwrap().subtract(c.lazy()).unwrap();
+        assert_complex_vec(expected, actual.evaluate());
+    }
+
+    #[test]
+    fn vector_conjugate_transpose_and_map() {
+        let a = Vector::from_array(&[comp!(1.0, 2.0), comp!(-3.0, 0.5)]);
+        let mut expected = a.clone();
+        expected.adjoint();
+        let actual = a.lazy().adjoint().evaluate();
+        assert_eq!(VectorType::ROW_VECTOR, actual.get_type());
+        assert_complex_vec(expected, actual);
+
+        let squared = a.lazy().map(|x| x.multiplication(&x)).evaluate();
+        assert_complex(comp!(-3.0, 4.0), squared.get_element(0).unwrap().clone());
+        assert_complex(comp!(8.75, -3.0), squared.get_element(1).unwrap().clone());
+    }
+
+    #[test]
+    fn vector_shapes_are_checked() {
+        let a = Vector::zeros(3);
+        let b = Vector::zeros(4);
+        assert!(a.lazy().add(b.lazy()).is_err());
+        assert!(a.lazy().subtract(a.lazy().transpose()).is_err());
+
+        let mut destination = Vector::zeros(4);
+        assert!(a.lazy().scale(2.0).evaluate_into(&mut destination).is_err());
+        let mut destination = Vector::zeros(3);
+        assert!(a.lazy().transpose().evaluate_into(&mut destination).is_err());
+    }
+
+    #[test]
+    fn fused_matrix_expression() {
+        let a = Matrix::random_gaussian(5, 3, 1);
+        let b = Matrix::random_gaussian(3, 5, 2);
+        let c = Matrix::random_gaussian(5, 3, 3);
+
+        // a - 0.5 conj(b^T) + c
+        let mut expected = a.add(&c).unwrap();
+        let mut half_adjoint = b.clone();
+        half_adjoint.adjoint();
+        half_adjoint.scale(0.5);
+        expected.sub_assign(&half_adjoint).unwrap();
+
+        let expression = a
+            .lazy()
+            .subtract(b.lazy().adjoint().scale(0.5))
+            .unwrap()
+            .add(c.lazy())
+            .unwrap();
+        assert_eq!((5, 3), expression.dimensions());
+        let mut actual = Matrix::zeros(5, 3);
+        expression.evaluate_into(&mut actual).unwrap();
+        assert_complex_matrix(expected, actual);
+    }
+
+    #[test]
+    fn matrix_transpose_and_map() {
+        let a = Matrix::from_fn(2, 3, |row, column| comp!(row as FloatType, column as FloatType));
+        let mut expected = a.clone();
+        expected.transpose();
+        let actual = a.lazy().transpose().evaluate();
+        assert_eq!((3, 2), (actual.num_rows, actual.num_columns));
+        assert_complex_matrix(expected, actual);
+
+        let exponentials = a.lazy().map(|x| x.exp()).evaluate();
+        assert_complex(comp!(1.0, 2.0).exp(), exponentials.get_element(1, 2).unwrap());
+    }
+
+    #[test]
+    fn matrix_shapes_are_checked() {
+        let a = Matrix::zeros(2, 3);
+        assert!(a.lazy().add(a.lazy().transpose()).is_err());
+        assert!(a.lazy().subtract(a.lazy().conjugate()).is_ok());
+        let mut destination = Matrix::zeros(3, 2);
+        assert!(a.lazy().evaluate_into(&mut destination).is_err());
+    }
+}
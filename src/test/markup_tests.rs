@@ -0,0 +1,99 @@
+// Unit test module for LaTeX and MathML export
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::markup::*;
+    use crate::matrix::*;
+    use crate::vector::*;
+
+    #[test]
+    fn complex_latex_test() {
+        let options = MarkupOptions::default();
+        assert_eq!(comp!(1.5f32, -2.0f32).to_latex(&options), "1.5 - 2\\mathrm{i}");
+        assert_eq!(comp!(3.0f32, 0.0f32).to_latex(&options), "3");
+
+        let options = MarkupOptions {
+            imaginary_unit: ImaginaryUnit::J,
+            precision: Some(2),
+            hide_zero_imaginary: false,
+            ..MarkupOptions::default()
+        };
+        assert_eq!(comp!(3.0f32, 0.0f32).to_latex(&options), "3.00 + 0.00j");
+        assert_eq!(comp!(-1.0f32, 0.125f32).to_latex(&options), "-1.00 + 0.12j");
+    }
+
+    #[test]
+    fn hide_rounded_imaginary_test() {
+        // parts that round to zero are hidden as well
+        let options = MarkupOptions {
+            precision: Some(2),
+            ..MarkupOptions::default()
+        };
+        assert_eq!(comp!(1.0f32, 1e-4f32).to_latex(&options), "1.00");
+        assert_eq!(comp!(1.0f32, -1e-4f32).to_latex(&options), "1.00");
+    }
+
+    #[test]
+    fn matrix_latex_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(0.0, 1.0), comp!(-2.0, 0.0), comp!(4.0, -0.5)],
+        )
+        .unwrap();
+        assert_eq!(
+            matrix.to_latex(&MarkupOptions::default()),
+            "\\begin{pmatrix}\n1 & 0 + 1\\mathrm{i} \\\\\n-2 & 4 - 0.5\\mathrm{i}\n\\end{pmatrix}"
+        );
+        let options = MarkupOptions {
+            delimiters: MatrixDelimiters::Brackets,
+            ..MarkupOptions::default()
+        };
+        assert!(matrix.to_latex(&options).starts_with("\\begin{bmatrix}\n"));
+    }
+
+    #[test]
+    fn vector_latex_test() {
+        let mut vector = Vector::from_array(&[comp!(1.0, 0.0), comp!(2.0, 0.0)]);
+        assert_eq!(vector.to_latex(&MarkupOptions::default()), "\\begin{pmatrix}\n1 \\\\\n2\n\\end{pmatrix}");
+        vector.transpose();
+        assert_eq!(vector.to_latex(&MarkupOptions::default()), "\\begin{pmatrix}\n1 & 2\n\\end{pmatrix}");
+    }
+
+    #[test]
+    fn complex_mathml_test() {
+        let options = MarkupOptions::default();
+        assert_eq!(
+            comp!(-1.0f32, 2.0f32).to_mathml(&options),
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">\
+             <mrow><mo>-</mo><mn>1</mn><mo>+</mo><mn>2</mn><mi mathvariant=\"normal\">i</mi></mrow></math>"
+        );
+        let options = MarkupOptions {
+            imaginary_unit: ImaginaryUnit::J,
+            ..MarkupOptions::default()
+        };
+        assert!(comp!(0.0f32, -1.0f32).to_mathml(&options).contains("<mo>-</mo><mn>1</mn><mi>j</mi>"));
+    }
+
+    #[test]
+    fn vector_mathml_test() {
+        let mut vector = Vector::from_array(&[comp!(1.0, 0.0), comp!(2.0, 0.0)]);
+        let column = vector.to_mathml(&MarkupOptions::default());
+        assert!(column.contains(
+            "<mo>(</mo><mtable><mtr><mtd><mrow><mn>1</mn></mrow></mtd></mtr>\
+             <mtr><mtd><mrow><mn>2</mn></mrow></mtd></mtr></mtable><mo>)</mo>"
+        ));
+
+        vector.transpose();
+        let options = MarkupOptions {
+            delimiters: MatrixDelimiters::Brackets,
+            ..MarkupOptions::default()
+        };
+        let row = vector.to_mathml(&options);
+        assert!(row.contains(
+            "<mo>[</mo><mtable><mtr><mtd><mrow><mn>1</mn></mrow></mtd>\
+             <mtd><mrow><mn>2</mn></mrow></mtd></mtr></mtable><mo>]</mo>"
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+
+    fn non_normal_matrix() -> Matrix {
+        Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(0.5, 0.2), comp!(-0.3, 0.0),
+                comp!(0.1, 0.0), comp!(2.0, 0.0), comp!(0.4, -0.1),
+                comp!(0.0, 0.0), comp!(0.2, 0.0), comp!(3.0, 0.5),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn expm_diagonal_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(-2.0, 0.0)],
+        )
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(2.7182817, 0.0), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.13533528, 0.0),
+            ],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.expm().unwrap());
+    }
+
+    #[test]
+    fn expm_rotation_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.0, 0.0), comp!(2.0, 0.0), comp!(-2.0, 0.0), comp!(0.0, 0.0)],
+        )
+        .unwrap();
+
+        let (sin, cos) = (f32::sin(2.0), f32::cos(2.0));
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(cos, 0.0), comp!(sin, 0.0), comp!(-sin, 0.0), comp!(cos, 0.0)],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.expm().unwrap());
+    }
+
+    #[test]
+    fn expm_nilpotent_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0)],
+        )
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(1.0, 0.0)],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.expm().unwrap());
+    }
+
+    #[test]
+    fn logm_inverts_expm_test() {
+        let matrix = non_normal_matrix();
+        let logarithm = matrix.expm().unwrap().logm().unwrap();
+        assert_complex_matrix(matrix, logarithm);
+    }
+
+    #[test]
+    fn logm_expm_roundtrip_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(4.0, 0.0), comp!(1.0, 0.0), comp!(-2.0, 1.0), comp!(3.0, 0.0)],
+        )
+        .unwrap();
+
+        let result = matrix.logm().unwrap().expm().unwrap();
+        assert_complex_matrix(matrix, result);
+    }
+
+    #[test]
+    fn logm_negative_eigenvalue_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(-1.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(2.0, 0.0)],
+        )
+        .unwrap();
+
+        assert!(matrix.logm().is_err());
+    }
+
+    #[test]
+    fn logm_singular_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(2.0, 0.0), comp!(4.0, 0.0)],
+        )
+        .unwrap();
+
+        assert!(matrix.logm().is_err());
+    }
+
+    #[test]
+    fn sqrtm_triangular_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(4.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(9.0, 0.0)],
+        )
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(2.0, 0.0), comp!(0.2, 0.0), comp!(0.0, 0.0), comp!(3.0, 0.0)],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.sqrtm().unwrap());
+    }
+
+    #[test]
+    fn sqrtm_squares_to_matrix_test() {
+        let matrix = non_normal_matrix();
+        let root = matrix.sqrtm().unwrap();
+        assert_complex_matrix(matrix, root.multiply(&root).unwrap());
+    }
+
+    #[test]
+    fn sqrtm_negative_eigenvalue_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(-4.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(1.0, 0.0)],
+        )
+        .unwrap();
+
+        assert!(matrix.sqrtm().is_err());
+    }
+
+    #[test]
+    fn sinm_cosm_identity_test() {
+        // sin^2(A) + cos^2(A) = I
+        let matrix = non_normal_matrix();
+        let sin = matrix.sinm().unwrap();
+        let cos = matrix.cosm().unwrap();
+
+        let result = sin
+            .multiply(&sin)
+            .unwrap()
+            .add(&cos.multiply(&cos).unwrap())
+            .unwrap();
+        assert_complex_matrix(Matrix::identity(3), result);
+    }
+
+    #[test]
+    fn sinm_diagonal_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.5, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 1.0)],
+        )
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(0.5, 0.0).sin(), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.0, 1.0).sin(),
+            ],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.sinm().unwrap());
+    }
+
+    #[test]
+    fn funm_matches_expm_test() {
+        let matrix = non_normal_matrix();
+        let expected = matrix.expm().unwrap();
+        let actual = matrix.funm(|z| z.exp()).unwrap();
+        assert_complex_matrix(expected, actual);
+    }
+
+    #[test]
+    fn funm_normal_matrix_test() {
+        // hermitian matrix with eigenvalues 1 and 3
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(2.0, 0.0), comp!(0.0, 1.0), comp!(0.0, -1.0), comp!(2.0, 0.0)],
+        )
+        .unwrap();
+
+        let actual = matrix.funm(|z| z.multiplication(&z)).unwrap();
+        let expected = matrix.multiply(&matrix).unwrap();
+        assert_complex_matrix(expected, actual);
+    }
+
+    #[test]
+    fn funm_jordan_block_test() {
+        // f(J) = [f(l) f'(l); 0 f(l)] for a jordan block J with eigenvalue l
+        let matrix = Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(0.5, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.5, 0.0), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(2.0, 0.0),
+            ],
+        )
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(0.5, 0.0).sin(), comp!(0.5, 0.0).cos(), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.5, 0.0).sin(), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(2.0, 0.0).sin(),
+            ],
+        )
+        .unwrap();
+
+        assert_complex_matrix(expected, matrix.funm(|z| z.sin()).unwrap());
+    }
+
+    #[test]
+    fn funm_branch_cut_test() {
+        // close eigenvalues on both sides of the branch cut of sqrt
+        let across = Matrix::from_array(2, 2, &[comp!(-1.0, 0.02), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(-1.0, -0.02)]).unwrap();
+        assert_eq!(
+            across.funm(|z| z.sqrt()).unwrap_err(),
+            "Function is not analytic around a cluster of close eigenvalues"
+        );
+
+        // the same cluster away from the cut
+        let away = Matrix::from_array(2, 2, &[comp!(1.0, 0.02), comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(1.0, -0.02)]).unwrap();
+        let root = away.funm(|z| z.sqrt()).unwrap();
+        assert_complex_matrix(away, root.multiply(&root).unwrap());
+    }
+}
@@ -0,0 +1,241 @@
+// Unit test module for the Matrix Market format
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::matrix_market::*;
+    use crate::test::util::*;
+
+    fn read(text: &str) -> Result<Matrix, MatrixMarketError> {
+        read_matrix_market(text.as_bytes()).map(|(_, matrix)| matrix)
+    }
+
+    fn round_trip(matrix: &Matrix, header: MatrixMarketHeader) -> Matrix {
+        let mut buffer = Vec::new();
+        write_matrix_market(matrix, &header, &mut buffer).unwrap();
+        let (read_header, result) = read_matrix_market(buffer.as_slice()).unwrap();
+        assert_eq!(header, read_header);
+        result
+    }
+
+    fn header(
+        format: MatrixMarketFormat,
+        field: MatrixMarketField,
+        symmetry: MatrixMarketSymmetry,
+    ) -> MatrixMarketHeader {
+        MatrixMarketHeader::new(format, field, symmetry).unwrap()
+    }
+
+    #[test]
+    fn read_coordinate_test() {
+        let text = "%%MatrixMarket matrix coordinate real general\n\
+                    % a comment\n\
+                    \n\
+                    2 3 3\n\
+                    1 1 1.5\n\
+                    2 3 -2\n\
+                    1 2 4e1\n";
+        let expected = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.5, 0.0),
+                comp!(40.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(-2.0, 0.0)
+            ])
+            .unwrap();
+        assert_complex_matrix(expected, read(text).unwrap());
+    }
+
+    #[test]
+    fn read_symmetry_test() {
+        let hermitian = read("%%MatrixMarket matrix coordinate complex hermitian\n2 2 2\n1 1 1 0\n2 1 3 4\n").unwrap();
+        assert_complex(comp!(3.0, 4.0), hermitian.get_element(1, 0).unwrap());
+        assert_complex(comp!(3.0, -4.0), hermitian.get_element(0, 1).unwrap());
+
+        let skew = read("%%MatrixMarket matrix array integer skew-symmetric\n3 3\n1\n2\n3\n").unwrap();
+        let expected = Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(0.0, 0.0),
+                comp!(-1.0, 0.0),
+                comp!(-2.0, 0.0),
+                comp!(1.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(-3.0, 0.0),
+                comp!(2.0, 0.0),
+                comp!(3.0, 0.0),
+                comp!(0.0, 0.0)
+            ])
+            .unwrap();
+        assert_complex_matrix(expected, skew);
+
+        let pattern = read("%%MatrixMarket matrix coordinate pattern symmetric\n2 2 1\n2 1\n").unwrap();
+        assert_complex(comp!(1.0, 0.0), pattern.get_element(0, 1).unwrap());
+        assert_complex(comp!(0.0, 0.0), pattern.get_element(0, 0).unwrap());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let general = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 0.0),
+                comp!(-3.5, 0.0),
+                comp!(0.0, 0.25),
+                comp!(7.0, -1.0),
+                comp!(0.0, 0.0)
+            ])
+            .unwrap();
+        for format in [MatrixMarketFormat::Coordinate, MatrixMarketFormat::Array] {
+            let result = round_trip(&general, header(format, MatrixMarketField::Complex, MatrixMarketSymmetry::General));
+            assert_eq!((result.num_rows, result.num_columns), (2, 3));
+            assert_complex_matrix(general.clone(), result);
+        }
+
+        let symmetric = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0)],
+        )
+        .unwrap();
+        let result = round_trip(
+            &symmetric,
+            header(MatrixMarketFormat::Array, MatrixMarketField::Integer, MatrixMarketSymmetry::Symmetric),
+        );
+        assert_complex_matrix(symmetric.clone(), result);
+        let result = round_trip(
+            &symmetric,
+            header(MatrixMarketFormat::Coordinate, MatrixMarketField::Real, MatrixMarketSymmetry::Symmetric),
+        );
+        assert_complex_matrix(symmetric, result);
+
+        let hermitian = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, -1.0), comp!(2.0, 1.0), comp!(3.0, 0.0)],
+        )
+        .unwrap();
+        let result = round_trip(
+            &hermitian,
+            header(MatrixMarketFormat::Coordinate, MatrixMarketField::Complex, MatrixMarketSymmetry::Hermitian),
+        );
+        assert_complex_matrix(hermitian, result);
+    }
+
+    #[test]
+    fn write_test() {
+        let matrix = Matrix::identity(2);
+        let mut buffer = Vec::new();
+        let header = header(MatrixMarketFormat::Coordinate, MatrixMarketField::Pattern, MatrixMarketSymmetry::General);
+        write_matrix_market(&matrix, &header, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 1\n2 2\n"
+        );
+    }
+
+    #[test]
+    fn write_error_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(0.0, 0.0), comp!(0.5, 1.0)],
+        )
+        .unwrap();
+        let mut buffer = Vec::new();
+
+        let symmetric = header(MatrixMarketFormat::Array, MatrixMarketField::Complex, MatrixMarketSymmetry::Symmetric);
+        assert!(matches!(
+            write_matrix_market(&matrix, &symmetric, &mut buffer),
+            Err(MatrixMarketError::Write("Matrix does not have the symmetry of the header"))
+        ));
+        let real = header(MatrixMarketFormat::Array, MatrixMarketField::Real, MatrixMarketSymmetry::General);
+        assert!(matches!(
+            write_matrix_market(&matrix, &real, &mut buffer),
+            Err(MatrixMarketError::Write("Complex elements need a complex field"))
+        ));
+        assert!(MatrixMarketHeader::new(
+            MatrixMarketFormat::Array,
+            MatrixMarketField::Pattern,
+            MatrixMarketSymmetry::General
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn failing_write_leaves_writer_empty_test() {
+        // the offending elements come after valid ones in column major order
+        let matrix = Matrix::from_array(2, 2, &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0), comp!(0.5, 1.0)]).unwrap();
+        for format in [MatrixMarketFormat::Coordinate, MatrixMarketFormat::Array] {
+            let mut buffer = Vec::new();
+            let real = header(format, MatrixMarketField::Real, MatrixMarketSymmetry::General);
+            assert!(write_matrix_market(&matrix, &real, &mut buffer).is_err());
+            assert!(buffer.is_empty());
+
+            let mut halves = matrix.clone();
+            halves.scale(0.5);
+            let integer = header(format, MatrixMarketField::Integer, MatrixMarketSymmetry::General);
+            assert!(write_matrix_market(&halves, &integer, &mut buffer).is_err());
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn read_error_test() {
+        let reason = |text: &str| match read(text) {
+            Err(MatrixMarketError::Parse { line, reason }) => (line, reason),
+            _ => panic!("expected a parse error"),
+        };
+        assert_eq!(reason("2 2\n1\n"), (1, "Missing %%MatrixMarket banner"));
+        assert_eq!(
+            reason("%%MatrixMarket vector coordinate real general\n"),
+            (1, "Only matrix objects are supported")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix coordinate float general\n"),
+            (1, "Unknown field, expected real, complex, integer or pattern")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix array real hermitian\n"),
+            (1, "Hermitian matrices need a complex field")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1\n"),
+            (3, "Index is out of bounds")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1\n"),
+            (3, "Fewer entries than announced")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix array complex general\n1 1\n1\n"),
+            (3, "Number of values does not match the field")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix array real general\n1 1\n1\n2\n"),
+            (4, "More entries than expected")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix coordinate real general\n100000000 100000000 0\n"),
+            (2, "Matrix is too large to be stored densely")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix coordinate real general\n18446744073709551615 2 0\n"),
+            (2, "Matrix is too large to be stored densely")
+        );
+        assert_eq!(
+            reason("%%MatrixMarket matrix array real general\n10000 10000\n1\n"),
+            (2, "Fewer entries than expected")
+        );
+        let error = read("%%MatrixMarket matrix coordinate real symmetric\n2 3 0\n").unwrap_err();
+        assert_eq!(format!("{}", error), "Symmetric matrices have to be quadratic in line 2");
+    }
+}
@@ -3,7 +3,7 @@ mod tests {
 
     #[macro_use]
     use crate::matrix::*;
-    use crate::{complex::*, Vector};
+    use crate::{complex::*, vector::Vector};
     use crate::test::util::*;
 
     #[test]
@@ -44,6 +44,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn identity_test() {
+        let created_matrix = Matrix::identity(3);
+        let expected = Matrix::from_fn(3, 3, |row_index, column_index| {
+            if row_index == column_index { comp!(1.0, 0.0) } else { comp!(0.0, 0.0) }
+        });
+
+        assert_eq!(3, created_matrix.num_rows);
+        assert_eq!(3, created_matrix.num_columns);
+        assert_complex_matrix(expected, created_matrix);
+    }
+
+    #[test]
+    fn from_diagonal_test() {
+        let diagonal = Vector::from_array(&[comp!(1.0, 2.0), comp!(-3.0, 0.0)]);
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(-3.0, 0.0)
+            ])
+            .unwrap();
+
+        assert_complex_matrix(expected, Matrix::from_diagonal(&diagonal));
+    }
+
+    #[test]
+    fn from_rows_columns_test() {
+        let first = Vector::from_array(&[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0)]);
+        let second = Vector::from_array(&[comp!(4.0, 0.0), comp!(5.0, 0.0), comp!(6.0, 1.0)]);
+
+        let expected_rows = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0),
+                comp!(4.0, 0.0), comp!(5.0, 0.0), comp!(6.0, 1.0)
+            ])
+            .unwrap();
+        let rows = Matrix::from_rows(&[first.clone(), second.clone()]).unwrap();
+        assert_eq!(2, rows.num_rows);
+        assert_eq!(3, rows.num_columns);
+        assert_complex_matrix(expected_rows, rows);
+
+        let expected_columns = Matrix::from_array(
+            3,
+            2,
+            &[
+                comp!(1.0, 0.0), comp!(4.0, 0.0),
+                comp!(2.0, 0.0), comp!(5.0, 0.0),
+                comp!(3.0, 0.0), comp!(6.0, 1.0)
+            ])
+            .unwrap();
+        let columns = Matrix::from_columns(&[first.clone(), second]).unwrap();
+        assert_eq!(3, columns.num_rows);
+        assert_eq!(2, columns.num_columns);
+        assert_complex_matrix(expected_columns, columns);
+
+        let short = Vector::from_array(&[comp!(1.0, 0.0)]);
+        assert!(Matrix::from_rows(&[first.clone(), short.clone()]).is_err());
+        assert!(Matrix::from_columns(&[first, short]).is_err());
+        assert!(Matrix::from_rows(&[]).is_err());
+    }
+
+    #[test]
+    fn from_fn_test() {
+        let created_matrix = Matrix::from_fn(2, 3, |row_index, column_index| {
+            comp!(row_index as f32, column_index as f32)
+        });
+
+        assert_eq!(2, created_matrix.num_rows);
+        assert_eq!(3, created_matrix.num_columns);
+        assert_complex(comp!(1.0, 2.0), created_matrix.get_element(1, 2).unwrap());
+        assert_complex(comp!(0.0, 1.0), created_matrix.get_element(0, 1).unwrap());
+    }
+
+    #[test]
+    fn stack_test() {
+        let left = Matrix::from_array(2, 1, &[comp!(1.0, 0.0), comp!(2.0, 0.0)]).unwrap();
+        let right = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(3.0, 0.0),
+                comp!(4.0, 0.0),
+                comp!(5.0, 0.0),
+                comp!(6.0, 0.0)
+            ])
+            .unwrap();
+
+        let horizontal = Matrix::hstack(&[&left, &right]).unwrap();
+        let expected = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(3.0, 0.0), comp!(4.0, 0.0),
+                comp!(2.0, 0.0), comp!(5.0, 0.0), comp!(6.0, 0.0)
+            ])
+            .unwrap();
+        assert_eq!(3, horizontal.num_columns);
+        assert_complex_matrix(expected, horizontal);
+
+        let vertical = Matrix::vstack(&[&right, &Matrix::identity(2)]).unwrap();
+        let expected = Matrix::from_array(
+            4,
+            2,
+            &[
+                comp!(3.0, 0.0), comp!(4.0, 0.0),
+                comp!(5.0, 0.0), comp!(6.0, 0.0),
+                comp!(1.0, 0.0), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(1.0, 0.0)
+            ])
+            .unwrap();
+        assert_eq!(4, vertical.num_rows);
+        assert_complex_matrix(expected, vertical);
+
+        assert!(Matrix::hstack(&[&left, &Matrix::identity(3)]).is_err());
+        assert!(Matrix::vstack(&[&left, &right]).is_err());
+    }
+
+    #[test]
+    fn block_test() {
+        let top_left = Matrix::identity(2);
+        let top_right = Matrix::zeros(2, 1);
+        let bottom_left = Matrix::from_array(1, 2, &[comp!(7.0, 0.0), comp!(8.0, 0.0)]).unwrap();
+        let bottom_right = Matrix::from_array(1, 1, &[comp!(0.0, 9.0)]).unwrap();
+
+        let created_matrix = Matrix::block(&[
+            &[&top_left, &top_right],
+            &[&bottom_left, &bottom_right],
+        ])
+        .unwrap();
+
+        let expected = Matrix::from_array(
+            3,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 0.0),
+                comp!(0.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0),
+                comp!(7.0, 0.0), comp!(8.0, 0.0), comp!(0.0, 9.0)
+            ])
+            .unwrap();
+        assert_eq!(3, created_matrix.num_rows);
+        assert_eq!(3, created_matrix.num_columns);
+        assert_complex_matrix(expected, created_matrix);
+
+        // block columns of differing width
+        let narrow = Matrix::zeros(1, 1);
+        let wide = Matrix::zeros(1, 2);
+        assert!(Matrix::block(&[&[&narrow, &wide], &[&wide, &narrow]]).is_err());
+        assert!(Matrix::block(&[&[&top_left, &top_right], &[&bottom_left]]).is_err());
+    }
+
     #[test]
     fn set_element_test() {
         let mut created_matrix = Matrix::zeros(4, 5);
@@ -211,21 +367,346 @@ mod tests {
         assert_complex_matrix(expected, result);
     }
 
+    #[test]
+    fn kronecker_product_rectangular_test() {
+        let matrix1 = Matrix::from_array(
+            2,
+            1,
+            &[
+                comp!(1.0, 0.0),
+                comp!(0.0, 2.0)
+            ])
+            .unwrap();
+
+        let matrix2 = Matrix::from_array(
+            1,
+            3,
+            &[
+                comp!(1.0, 0.0),
+                comp!(2.0, 0.0),
+                comp!(3.0, -1.0)
+            ])
+            .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, -1.0),
+                comp!(0.0, 2.0), comp!(0.0, 4.0), comp!(2.0, 6.0)
+            ])
+            .unwrap();
+
+        let result = Matrix::kronecker_product(&matrix1, &matrix2).unwrap();
+        assert_eq!(2, result.num_rows);
+        assert_eq!(3, result.num_columns);
+        assert_complex_matrix(expected, result);
+    }
+
+    #[test]
+    fn kronecker_sum_test() {
+        let matrix1 = Matrix::from_array(
+            1,
+            1,
+            &[comp!(2.0, 0.0)])
+            .unwrap();
+
+        let matrix2 = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 0.0),
+                comp!(0.0, 1.0),
+                comp!(0.0, -1.0),
+                comp!(3.0, 0.0)
+            ])
+            .unwrap();
+
+        // 2 (+) B = 2 I + B
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(3.0, 0.0),
+                comp!(0.0, 1.0),
+                comp!(0.0, -1.0),
+                comp!(5.0, 0.0)
+            ])
+            .unwrap();
+
+        assert_complex_matrix(expected, matrix1.kronecker_sum(&matrix2).unwrap());
+
+        let pauli_z = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(0.0, 0.0),
+                comp!(-1.0, 0.0)
+            ])
+            .unwrap();
+
+        // Z (+) Z = diag(2, 0, 0, -2)
+        let result = pauli_z.kronecker_sum(&pauli_z).unwrap();
+        let diagonal = [2.0, 0.0, 0.0, -2.0];
+        for (row_index, diagonal_element) in diagonal.iter().enumerate() {
+            for column_index in 0..4 {
+                let expected = if row_index == column_index { *diagonal_element } else { 0.0 };
+                assert_complex(comp!(expected, 0.0), result.get_element(row_index, column_index).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn kron_all_test() {
+        let pauli_x = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(0.0, 0.0),
+                comp!(1.0, 0.0),
+                comp!(1.0, 0.0),
+                comp!(0.0, 0.0)
+            ])
+            .unwrap();
+
+        let result = Matrix::kron_all(&[pauli_x.clone(), Matrix::identity(2), pauli_x.clone()]).unwrap();
+        assert_eq!(8, result.num_rows);
+        assert_eq!(8, result.num_columns);
+
+        // X (x) I (x) X flips the first and last bit of the basis index
+        for row_index in 0..8 {
+            for column_index in 0..8 {
+                let expected = if row_index ^ 0b101 == column_index { 1.0 } else { 0.0 };
+                assert_complex(comp!(expected, 0.0), result.get_element(row_index, column_index).unwrap());
+            }
+        }
+
+        assert!(Matrix::kron_all(&[]).is_err());
+    }
+
     #[test]
     fn is_quadratic_test() {}
 
     #[test]
-    fn eigenvalue_calc_test() {}
+    fn eigenvalue_calc_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(2.0, 0.0),
+                comp!(1.0, 0.0),
+                comp!(1.0, 0.0),
+                comp!(2.0, 0.0)
+            ])
+            .unwrap();
+
+        let mut eigenvalues = matrix.calculate_eigenvalues().unwrap();
+        eigenvalues.sort_by(|a, b| a.real.partial_cmp(&b.real).unwrap());
+
+        assert_eq!(2, eigenvalues.len());
+        assert_complex(comp!(1.0, 0.0), eigenvalues[0].clone());
+        assert_complex(comp!(3.0, 0.0), eigenvalues[1].clone());
+    }
 
     #[test]
     fn eigenvector_calc_test() {}
 
     #[test]
-    fn transpose_test() {}
+    fn transpose_test() {
+        let mut matrix = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 1.0),
+                comp!(4.0, 0.0), comp!(5.0, 0.0), comp!(6.0, -1.0)
+            ])
+            .unwrap();
+
+        let expected = Matrix::from_array(
+            3,
+            2,
+            &[
+                comp!(1.0, 0.0), comp!(4.0, 0.0),
+                comp!(2.0, 0.0), comp!(5.0, 0.0),
+                comp!(3.0, 1.0), comp!(6.0, -1.0)
+            ])
+            .unwrap();
+
+        matrix.transpose();
+        assert_eq!(3, matrix.num_rows);
+        assert_eq!(2, matrix.num_columns);
+        assert_complex_matrix(expected, matrix);
+    }
 
     #[test]
     fn conjugate_test() {}
 
     #[test]
-    fn adjoint_test() {}
+    fn adjoint_test() {
+        let mut matrix = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 3.0),
+                comp!(4.0, 0.0),
+                comp!(5.0, -1.0)
+            ])
+            .unwrap();
+
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, -2.0),
+                comp!(4.0, 0.0),
+                comp!(0.0, -3.0),
+                comp!(5.0, 1.0)
+            ])
+            .unwrap();
+
+        matrix.adjoint();
+        assert_complex_matrix(expected, matrix);
+    }
+
+    #[test]
+    fn display_test() {
+        let matrix = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 0.0),
+                comp!(0.0, -1.0),
+                comp!(10.0, 2.0),
+                comp!(3.0, 0.0)
+            ])
+            .unwrap();
+
+        assert_eq!(format!("{}", matrix), "[ 1 + 0i  0 - 1i]\n[10 + 2i  3 + 0i]");
+        assert_eq!(format!("{:.1}", matrix), "[ 1.0 + 0.0i  0.0 - 1.0i]\n[10.0 + 2.0i  3.0 + 0.0i]");
+        assert_eq!(format!("{:8}", matrix), "[  1 + 0i    0 - 1i]\n[ 10 + 2i    3 + 0i]");
+    }
+
+    #[test]
+    fn display_truncation_test() {
+        let matrix = Matrix::identity(20);
+        let rendered = format!("{}", matrix);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "[1 + 0i  0 + 0i  0 + 0i  0 + 0i  ...  0 + 0i  0 + 0i  0 + 0i  0 + 0i]");
+        assert_eq!(lines[4], "[   ...     ...     ...     ...  ...     ...     ...     ...     ...]");
+        assert_eq!(lines[8], "[0 + 0i  0 + 0i  0 + 0i  0 + 0i  ...  0 + 0i  0 + 0i  0 + 0i  1 + 0i]");
+        assert_eq!(format!("{}", Matrix::zeros(0, 3)), "[]");
+    }
+
+    #[test]
+    fn hermitian_symmetric_test() {
+        let hermitian = Matrix::from_array(
+            2,
+            2,
+            &[comp!(2.0, 0.0), comp!(1.0, -1.0), comp!(1.0, 1.0), comp!(3.0, 0.0)])
+            .unwrap();
+        assert!(hermitian.is_hermitian());
+        assert!(!hermitian.is_symmetric());
+
+        let symmetric = Matrix::from_array(
+            2,
+            2,
+            &[comp!(0.0, 1.0), comp!(2.0, 0.0), comp!(2.0, 0.0), comp!(0.0, 0.0)])
+            .unwrap();
+        assert!(symmetric.is_symmetric());
+        assert!(!symmetric.is_hermitian());
+        assert!(!Matrix::zeros(2, 3).is_symmetric());
+    }
+
+    #[test]
+    fn transpose_shapes_test() {
+        // compares against the element wise definition for shapes whose
+        // transposition has cycles of different lengths
+        for (num_rows, num_cols) in [(1, 5), (5, 1), (2, 3), (3, 7), (4, 6), (6, 4), (5, 5), (0, 3), (3, 0)] {
+            let matrix = Matrix::random_gaussian(num_rows, num_cols, 8);
+            let mut transposed = matrix.clone();
+            transposed.transpose();
+            assert_eq!((num_cols, num_rows), (transposed.num_rows, transposed.num_columns));
+            for row_index in 0..num_rows {
+                for column_index in 0..num_cols {
+                    assert_complex(
+                        matrix.get_element(row_index, column_index).unwrap(),
+                        transposed.get_element(column_index, row_index).unwrap()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transpose_square_test() {
+        let mut matrix = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 1.0), comp!(3.0, 0.0), comp!(4.0, -1.0)])
+            .unwrap();
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(3.0, 0.0), comp!(2.0, 1.0), comp!(4.0, -1.0)])
+            .unwrap();
+
+        matrix.transpose_square().unwrap();
+        assert_complex_matrix(expected, matrix);
+        assert!(Matrix::zeros(2, 3).transpose_square().is_err());
+    }
+
+    #[test]
+    fn transpose_into_test() {
+        let matrix = Matrix::random_gaussian(3, 5, 12);
+        let mut expected = matrix.clone();
+        expected.transpose();
+        let mut result = Matrix::random_gaussian(5, 3, 13);
+
+        matrix.transpose_into(&mut result).unwrap();
+        assert_complex_matrix(expected, result.clone());
+        assert!(matrix.transpose_into(&mut Matrix::zeros(3, 5)).is_err());
+    }
+
+    #[test]
+    fn add_sub_assign_test() {
+        let first = Matrix::random_gaussian(3, 4, 9);
+        let second = Matrix::random_gaussian(3, 4, 10);
+
+        let mut sum = first.clone();
+        sum.add_assign(&second).unwrap();
+        assert_complex_matrix(first.add(&second).unwrap(), sum.clone());
+
+        sum.sub_assign(&second).unwrap();
+        assert_complex_matrix(first, sum.clone());
+        assert!(sum.add_assign(&Matrix::zeros(4, 3)).is_err());
+    }
+
+    #[test]
+    fn gemv_into_test() {
+        let matrix = Matrix::random_gaussian(4, 3, 11);
+        let vector = Vector::from_array(&[comp!(1.0, 0.0), comp!(0.0, 1.0), comp!(-2.0, 0.5)]);
+        let mut result = Vector::ones(4);
+
+        matrix.gemv_into(&vector, &mut result).unwrap();
+        assert_complex_vec(matrix.multiply_vector(&vector).unwrap(), result.clone());
+        assert!(matrix.gemv_into(&result, &mut Vector::zeros(4)).is_err());
+        assert!(matrix.gemv_into(&vector, &mut Vector::zeros(3)).is_err());
+    }
+
+    #[test]
+    fn gemm_into_test() {
+        let first = Matrix::random_gaussian(3, 5, 12);
+        let second = Matrix::random_gaussian(5, 2, 13);
+        // previous contents of the result are overwritten
+        let mut result = Matrix::random_gaussian(3, 2, 14);
+
+        first.gemm_into(&second, &mut result).unwrap();
+        assert_complex_matrix(first.multiply(&second).unwrap(), result.clone());
+        assert!(second.gemm_into(&first, &mut result).is_err());
+    }
 }
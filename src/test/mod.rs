@@ -2,3 +2,24 @@ pub mod util;
 pub mod complex_tests;
 pub mod vector_tests;
 pub mod matrix_tests;
+pub mod decomposition_tests;
+pub mod matrix_functions_tests;
+pub mod quantum_tests;
+pub mod density_matrix_tests;
+pub mod random_tests;
+pub mod parse_tests;
+pub mod matrix_market_tests;
+pub mod npy_tests;
+pub mod serde_tests;
+pub mod binary_tests;
+pub mod csv_tests;
+pub mod markup_tests;
+pub mod expression_tests;
+pub mod repl_tests;
+pub mod cli_tests;
+pub mod gemm_tests;
+pub mod parallel_tests;
+pub mod simd_tests;
+pub mod strassen_tests;
+pub mod lazy_tests;
+pub mod fixed_tests;
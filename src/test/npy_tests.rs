@@ -0,0 +1,231 @@
+// Unit test module for the NumPy formats
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::npy::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    fn stored_zip(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        // minimal zip archive without compression, crc values are not checked
+        let mut archive = Vec::new();
+        let mut directory = Vec::new();
+        for (name, data) in files {
+            let offset = archive.len() as u32;
+            archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+            archive.extend_from_slice(&[0; 14]);
+            archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            archive.extend_from_slice(&0u16.to_le_bytes());
+            archive.extend_from_slice(name.as_bytes());
+            archive.extend_from_slice(data);
+
+            directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            directory.extend_from_slice(&[0; 16]);
+            directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+        let directory_offset = archive.len() as u32;
+        archive.extend_from_slice(&directory);
+        archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&[0; 4]);
+        archive.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&directory_offset.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive
+    }
+
+    fn test_matrix() -> Matrix {
+        Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 0.0),
+                comp!(-3.5, 0.0),
+                comp!(0.0, 0.25),
+                comp!(7.0, -1.0),
+                comp!(0.5, 0.0)
+            ])
+            .unwrap()
+    }
+
+    #[test]
+    fn matrix_round_trip_test() {
+        for dtype in [NpyDtype::Complex64, NpyDtype::Complex128] {
+            let mut buffer = Vec::new();
+            test_matrix().write_npy(&mut buffer, dtype).unwrap();
+            // the data starts at a multiple of 64 bytes
+            let header_length = u16::from_le_bytes([buffer[8], buffer[9]]) as usize;
+            assert_eq!((10 + header_length) % 64, 0);
+
+            let result = Matrix::read_npy(&mut buffer.as_slice()).unwrap();
+            assert_eq!((result.num_rows, result.num_columns), (2, 3));
+            assert_complex_matrix(test_matrix(), result);
+        }
+    }
+
+    #[test]
+    fn vector_round_trip_test() {
+        let vector = Vector::from_array(&[comp!(1.0, 0.0), comp!(-2.5, 0.0), comp!(4.0, 0.0)]);
+        for dtype in [NpyDtype::Float32, NpyDtype::Float64] {
+            let mut buffer = Vec::new();
+            vector.write_npy(&mut buffer, dtype).unwrap();
+            let header = String::from_utf8_lossy(&buffer[10..]).into_owned();
+            assert!(header.contains("'shape': (3,)"));
+
+            let result = Vector::read_npy(&mut buffer.as_slice()).unwrap();
+            assert_eq!(result.size(), 3);
+            assert_eq!(result.get_type(), VectorType::COLUMN_VECTOR);
+            assert_complex_vec(vector.clone(), result);
+        }
+    }
+
+    #[test]
+    fn write_real_dtype_error_test() {
+        let mut buffer = Vec::new();
+        assert!(test_matrix().write_npy(&mut buffer, NpyDtype::Float64).is_err());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn read_big_endian_fortran_test() {
+        // [[1, 2], [3, 4]] stored big endian in column major order
+        let mut data = Vec::new();
+        for value in [1.0f64, 3.0, 2.0, 4.0] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+        let bytes = npy_bytes("{'descr': '>f8', 'fortran_order': True, 'shape': (2, 2), }\n", &data);
+        let matrix = Matrix::read_npy(&mut bytes.as_slice()).unwrap();
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[comp!(1.0, 0.0), comp!(2.0, 0.0), comp!(3.0, 0.0), comp!(4.0, 0.0)],
+        )
+        .unwrap();
+        assert_complex_matrix(expected, matrix);
+    }
+
+    #[test]
+    fn read_row_vector_test() {
+        let mut data = Vec::new();
+        for value in [1.0f32, -1.0, 0.5, 2.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let bytes = npy_bytes("{'descr': '<c8', 'fortran_order': False, 'shape': (1, 2), }\n", &data);
+        let vector = Vector::read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(vector.get_type(), VectorType::ROW_VECTOR);
+        assert_complex_vec(Vector::from_array(&[comp!(1.0, -1.0), comp!(0.5, 2.0)]), vector);
+    }
+
+    #[test]
+    fn read_error_test() {
+        let reason = |bytes: Vec<u8>| match Matrix::read_npy(&mut bytes.as_slice()) {
+            Err(NpyError::Format(reason)) => reason,
+            _ => panic!("expected a format error"),
+        };
+        assert_eq!(reason(b"NOTNUMPYxxxx".to_vec()), "Missing NumPy magic string");
+        assert_eq!(
+            reason(npy_bytes("{'descr': '<i4', 'fortran_order': False, 'shape': (1, 1), }", &[0; 4])),
+            "Unsupported dtype, expected f4, f8, c8 or c16"
+        );
+        assert_eq!(
+            reason(npy_bytes("{'descr': '<f4', 'fortran_order': False, 'shape': (2,), }", &[0; 8])),
+            "Matrices have to be stored as two dimensional arrays"
+        );
+        assert_eq!(
+            reason(npy_bytes("{'descr': '<f4', 'shape': (1, 1), }", &[0; 4])),
+            "Header is missing a key"
+        );
+        // too little data
+        let bytes = npy_bytes("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 2), }", &[0; 8]);
+        assert!(matches!(Matrix::read_npy(&mut bytes.as_slice()), Err(NpyError::Io(_))));
+    }
+
+    #[test]
+    fn oversized_shape_test() {
+        // the element count overflows
+        let bytes = npy_bytes("{'descr': '<c16', 'fortran_order': False, 'shape': (4611686018427387904, 8), }", &[0; 16]);
+        assert!(matches!(
+            Matrix::read_npy(&mut bytes.as_slice()),
+            Err(NpyError::Format("Shape is too large"))
+        ));
+        // fits into memory sizes but not into the file, the truncated
+        // payload is detected before the shape is allocated
+        let bytes = npy_bytes("{'descr': '<c8', 'fortran_order': False, 'shape': (1099511627776, 1024), }", &[0; 64]);
+        assert!(matches!(Matrix::read_npy(&mut bytes.as_slice()), Err(NpyError::Io(_))));
+        let bytes = npy_bytes("{'descr': '<f8', 'fortran_order': True, 'shape': (3, 3), }", &[0; 70]);
+        assert!(matches!(Matrix::read_npy(&mut bytes.as_slice()), Err(NpyError::Io(_))));
+    }
+
+    #[test]
+    fn npz_zip64_size_test() {
+        // an entry whose zip64 sizes point far past the end of the archive
+        let name = b"huge.npy";
+        let mut archive = 0x0403_4b50u32.to_le_bytes().to_vec();
+        archive.extend_from_slice(&[0; 22]);
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive.extend_from_slice(name);
+
+        let directory_offset = archive.len() as u32;
+        let mut directory = 0x0201_4b50u32.to_le_bytes().to_vec();
+        directory.extend_from_slice(&[0; 16]);
+        directory.extend_from_slice(&[0xff; 8]);
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes());
+        directory.extend_from_slice(&[0; 14]);
+        directory.extend_from_slice(name);
+        directory.extend_from_slice(&1u16.to_le_bytes());
+        directory.extend_from_slice(&16u16.to_le_bytes());
+        directory.extend_from_slice(&(u64::MAX - 8).to_le_bytes());
+        directory.extend_from_slice(&(u64::MAX - 8).to_le_bytes());
+        archive.extend_from_slice(&directory);
+
+        archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&[0, 0, 0, 0, 1, 0, 1, 0]);
+        archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&directory_offset.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        assert!(matches!(
+            NpzArchive::from_bytes(archive),
+            Err(NpyError::Format("Archive is truncated"))
+        ));
+    }
+
+    #[test]
+    fn npz_test() {
+        let mut matrix_bytes = Vec::new();
+        test_matrix().write_npy(&mut matrix_bytes, NpyDtype::Complex64).unwrap();
+        let mut vector_bytes = Vec::new();
+        Vector::ones(4).write_npy(&mut vector_bytes, NpyDtype::Float32).unwrap();
+
+        let archive = NpzArchive::from_bytes(stored_zip(&[
+            ("weights.npy", matrix_bytes),
+            ("bias.npy", vector_bytes),
+        ]))
+        .unwrap();
+        assert_eq!(archive.names(), vec!["weights", "bias"]);
+        assert_complex_matrix(test_matrix(), archive.matrix("weights").unwrap());
+        assert_complex_vec(Vector::ones(4), archive.vector("bias.npy").unwrap());
+        assert!(archive.matrix("missing").is_err());
+        assert!(NpzArchive::from_bytes(b"not a zip archive".to_vec()).is_err());
+    }
+}
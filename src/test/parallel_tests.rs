@@ -0,0 +1,89 @@
+// Unit test module for multithreading, only built with the parallel feature
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+
+    use crate::complex::*;
+    use crate::gemm::*;
+    use crate::matrix::*;
+    use crate::vector::*;
+
+    fn with_threads<R: Send>(num_threads: usize, function: impl FnOnce() -> R + Send) -> R {
+        // a pool with a single thread takes the serial path
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap()
+            .install(function)
+    }
+
+    fn assert_identical(expected: &Matrix, actual: &Matrix) {
+        assert_eq!((expected.num_rows, expected.num_columns), (actual.num_rows, actual.num_columns));
+        for row in 0..expected.num_rows {
+            for column in 0..expected.num_columns {
+                let expected = expected.get_element(row, column).unwrap();
+                let actual = actual.get_element(row, column).unwrap();
+                assert_eq!(expected.real.to_bits(), actual.real.to_bits());
+                assert_eq!(expected.imaginary.to_bits(), actual.imaginary.to_bits());
+            }
+        }
+    }
+
+    fn compare<G: Fn() -> Matrix + Sync + Send>(function: G) {
+        let serial = with_threads(1, &function);
+        let parallel = with_threads(4, &function);
+        assert_identical(&serial, &parallel);
+    }
+
+    #[test]
+    fn multiplication_test() {
+        let a = Matrix::random_gaussian(300, 200, 1);
+        let b = Matrix::random_gaussian(200, 150, 2);
+        compare(|| a.multiply(&b).unwrap());
+        compare(|| a.multiply_op(Op::Adjoint, &a, Op::Normal).unwrap());
+    }
+
+    #[test]
+    fn matrix_vector_test() {
+        let a = Matrix::random_gaussian(400, 300, 3);
+        let vector = Vector::random_unit(300, 4);
+        compare(|| {
+            let product = a.multiply_vector(&vector).unwrap();
+            Matrix::from_columns(&[product]).unwrap()
+        });
+    }
+
+    #[test]
+    fn elementwise_test() {
+        let a = Matrix::random_gaussian(300, 300, 5);
+        let b = Matrix::random_gaussian(300, 300, 6);
+        compare(|| a.add(&b).unwrap());
+        compare(|| {
+            let mut scaled = a.clone();
+            scaled.scale(0.3);
+            scaled.adjoint();
+            scaled
+        });
+    }
+
+    #[test]
+    fn kronecker_test() {
+        let a = Matrix::random_gaussian(20, 15, 7);
+        let b = Matrix::random_gaussian(12, 16, 8);
+        compare(|| a.kronecker_product(&b).unwrap());
+    }
+
+    #[test]
+    fn factorization_test() {
+        let a = Matrix::random_gaussian(200, 200, 9);
+        let b = Matrix::random_gaussian(200, 40, 10);
+        compare(|| a.solve(&b).unwrap());
+        compare(|| a.qr_decomposition().unwrap().0);
+        compare(|| a.hessenberg_decomposition().unwrap().1);
+    }
+
+    #[test]
+    fn small_inputs_match() {
+        let a = Matrix::from_array(2, 2, &[comp!(1.0, 2.0), comp!(0.0, 1.0), comp!(3.0, 0.0), comp!(1.0, -1.0)]).unwrap();
+        compare(|| a.multiply(&a).unwrap());
+    }
+}
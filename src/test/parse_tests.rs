@@ -0,0 +1,113 @@
+// Unit test module for parsing from text
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::parse::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn parse(text: &str) -> Complex<FloatType> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_complex_test() {
+        assert_complex(comp!(3.0, 0.0), parse("3"));
+        assert_complex(comp!(0.0, -2.5), parse("-2.5i"));
+        assert_complex(comp!(1.0, 2.0), parse("1+2i"));
+        assert_complex(comp!(1.5e-3, -4.0), parse("1.5e-3-4j"));
+        assert_complex(comp!(1.0, -1.0), parse(" 1 - i "));
+        assert_complex(comp!(-2.0, 0.5), parse("0.5i - 2"));
+    }
+
+    #[test]
+    fn parse_polar_test() {
+        let num = parse("2∠0.5");
+        assert_complex(Complex::from_polar(2.0, 0.5), num);
+        assert_complex(comp!(0.0, 1.0), parse("1 ∠ 1.5707964"));
+    }
+
+    #[test]
+    fn parse_display_round_trip_test() {
+        let num = comp!(-1.25, -3.5);
+        assert_complex(num.clone(), parse(&format!("{}", num)));
+    }
+
+    #[test]
+    fn parse_complex_error_test() {
+        let error = "1+2i3".parse::<Complex<FloatType>>().unwrap_err();
+        assert_eq!(error, ParseError { position: 4, reason: "Expected '+' or '-'" });
+
+        let error = "1+2+3i".parse::<Complex<FloatType>>().unwrap_err();
+        assert_eq!(error.position, 1);
+        assert_eq!(error.reason, "Duplicate real part");
+
+        let error = "abc".parse::<Complex<FloatType>>().unwrap_err();
+        assert_eq!(error.position, 0);
+        assert_eq!(format!("{}", error), "Expected a number at position 0");
+
+        assert!("".parse::<Complex<FloatType>>().is_err());
+        assert!("2∠".parse::<Complex<FloatType>>().is_err());
+        assert!("1.2.3".parse::<Complex<FloatType>>().is_err());
+    }
+
+    #[test]
+    fn parse_vector_test() {
+        let vector: Vector = "[1, 2+i, 3]".parse().unwrap();
+        assert_eq!(vector.get_type(), VectorType::ROW_VECTOR);
+        assert_eq!(vector.size(), 3);
+        assert_complex_vec(
+            Vector::from_array(&[comp!(1.0, 0.0), comp!(2.0, 1.0), comp!(3.0, 0.0)]),
+            vector,
+        );
+
+        let vector: Vector = "[1; -2i]".parse().unwrap();
+        assert_eq!(vector.get_type(), VectorType::COLUMN_VECTOR);
+        assert_eq!(vector.size(), 2);
+        assert_complex_vec(Vector::from_array(&[comp!(1.0, 0.0), comp!(0.0, -2.0)]), vector);
+
+        assert!("[1 2; 3 4]".parse::<Vector>().is_err());
+    }
+
+    #[test]
+    fn parse_matrix_test() {
+        let matrix: Matrix = "[1 2; 3 4i]".parse().unwrap();
+        let expected = Matrix::from_array(
+            2,
+            2,
+            &[
+                comp!(1.0, 0.0),
+                comp!(2.0, 0.0),
+                comp!(3.0, 0.0),
+                comp!(0.0, 4.0)
+            ])
+            .unwrap();
+        assert_complex_matrix(expected, matrix);
+
+        // whitespace around a sign joins the parts of one element
+        let matrix: Matrix = "[1 + 2i  -3, 4]".parse().unwrap();
+        assert_eq!((matrix.num_rows, matrix.num_columns), (1, 3));
+        assert_complex(comp!(1.0, 2.0), matrix.get_element(0, 0).unwrap());
+        assert_complex(comp!(-3.0, 0.0), matrix.get_element(0, 1).unwrap());
+
+        let matrix: Matrix = "[1 2\n 3 4\n]".parse().unwrap();
+        assert_eq!((matrix.num_rows, matrix.num_columns), (2, 2));
+    }
+
+    #[test]
+    fn parse_matrix_error_test() {
+        let error = "[1 2; 3]".parse::<Matrix>().unwrap_err();
+        assert_eq!(error, ParseError { position: 5, reason: "Rows have different numbers of elements" });
+
+        let error = "[1 2; 3 x]".parse::<Matrix>().unwrap_err();
+        assert_eq!(error.position, 8);
+
+        let error = "[1,, 2]".parse::<Matrix>().unwrap_err();
+        assert_eq!(error, ParseError { position: 3, reason: "Expected an element" });
+
+        assert_eq!("1 2".parse::<Matrix>().unwrap_err().reason, "Expected '['");
+        assert_eq!("[1 2".parse::<Matrix>().unwrap_err().reason, "Expected ']'");
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::quantum::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn assert_unitary(gate: Matrix) {
+        let mut adjoint = gate.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(Matrix::identity(gate.num_rows), gate.multiply(&adjoint).unwrap());
+    }
+
+    #[test]
+    fn gates_unitary_test() {
+        let gates = [
+            pauli_x(),
+            pauli_y(),
+            pauli_z(),
+            hadamard(),
+            s_gate(),
+            t_gate(),
+            rotation_x(0.3),
+            rotation_y(-1.2),
+            rotation_z(2.5),
+            cnot(),
+            toffoli(),
+            swap(),
+        ];
+        for gate in gates {
+            assert_unitary(gate);
+        }
+    }
+
+    #[test]
+    fn gate_relations_test() {
+        // XY = iZ, HZH = X, T^2 = S and RZ(pi) = -iZ
+        assert_complex_matrix(
+            Matrix::from_array(2, 2, &[comp!(0.0, 1.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(0.0, -1.0)]).unwrap(),
+            pauli_x().multiply(&pauli_y()).unwrap(),
+        );
+        assert_complex_matrix(
+            pauli_x(),
+            hadamard().multiply(&pauli_z()).unwrap().multiply(&hadamard()).unwrap(),
+        );
+        assert_complex_matrix(s_gate(), t_gate().multiply(&t_gate()).unwrap());
+        assert_complex_matrix(
+            Matrix::from_array(2, 2, &[comp!(0.0, -1.0), comp!(0.0, 0.0), comp!(0.0, 0.0), comp!(0.0, 1.0)]).unwrap(),
+            rotation_z(std::f32::consts::PI),
+        );
+    }
+
+    #[test]
+    fn bell_state_test() {
+        let mut state = QuantumState::new(2);
+        state.apply_gate(&hadamard(), &[0]).unwrap();
+        state.apply_gate(&cnot(), &[0, 1]).unwrap();
+
+        let factor = f32::sqrt(0.5);
+        let expected = Vector::from_array(&[
+            comp!(factor, 0.0),
+            comp!(0.0, 0.0),
+            comp!(0.0, 0.0),
+            comp!(factor, 0.0),
+        ]);
+        assert_complex_vec(expected, state.amplitudes().clone());
+
+        let probabilities = state.probabilities();
+        assert_approx_eq!(0.5f32, probabilities[0]);
+        assert_approx_eq!(0.0f32, probabilities[1]);
+        assert_approx_eq!(0.0f32, probabilities[2]);
+        assert_approx_eq!(0.5f32, probabilities[3]);
+    }
+
+    #[test]
+    fn apply_gate_matches_kronecker_test() {
+        // applying a gate to qubit 1 of 3 equals multiplying with I (x) G (x) I
+        let amplitudes = Vector::from_array(&[
+            comp!(0.1, 0.2), comp!(0.3, 0.0), comp!(0.0, -0.4), comp!(0.2, 0.1),
+            comp!(0.5, 0.0), comp!(0.1, -0.1), comp!(0.0, 0.3), comp!(0.2, 0.2),
+        ]);
+        let mut normalized = amplitudes.clone();
+        normalized.normalize();
+
+        let gate = rotation_y(0.7).multiply(&rotation_z(1.1)).unwrap();
+        let mut state = QuantumState::from_vector(normalized.clone()).unwrap();
+        state.apply_gate(&gate, &[1]).unwrap();
+
+        let operator = Matrix::kron_all(&[Matrix::identity(2), gate, Matrix::identity(2)]).unwrap();
+        let expected = operator.multiply_vector(&normalized).unwrap();
+        assert_complex_vec(expected, state.amplitudes().clone());
+    }
+
+    #[test]
+    fn apply_gate_target_order_test() {
+        // CNOT with control on qubit 2 and target on qubit 0: |001> -> |101>
+        let mut state = QuantumState::from_basis_state(3, 0b001).unwrap();
+        state.apply_gate(&cnot(), &[2, 0]).unwrap();
+        assert_approx_eq!(1.0f32, state.probabilities()[0b101]);
+
+        // SWAP of qubits 0 and 1: |101> -> |011>
+        state.apply_gate(&swap(), &[0, 1]).unwrap();
+        assert_approx_eq!(1.0f32, state.probabilities()[0b011]);
+
+        // toffoli flips qubit 0 as both controls are set
+        state.apply_gate(&toffoli(), &[1, 2, 0]).unwrap();
+        assert_approx_eq!(1.0f32, state.probabilities()[0b111]);
+    }
+
+    #[test]
+    fn apply_gate_errors_test() {
+        let mut state = QuantumState::new(2);
+        assert!(state.apply_gate(&hadamard(), &[2]).is_err());
+        assert!(state.apply_gate(&cnot(), &[0]).is_err());
+        assert!(state.apply_gate(&cnot(), &[1, 1]).is_err());
+        assert!(QuantumState::from_vector(Vector::from_array(&[comp!(0.5, 0.0), comp!(0.5, 0.0), comp!(0.5, 0.0)])).is_err());
+        assert!(QuantumState::from_vector(Vector::from_array(&[comp!(1.0, 0.0), comp!(1.0, 0.0)])).is_err());
+    }
+
+    #[test]
+    fn sample_test() {
+        let mut state = QuantumState::new(2);
+        state.apply_gate(&hadamard(), &[0]).unwrap();
+        state.apply_gate(&cnot(), &[0, 1]).unwrap();
+
+        let samples = state.sample(1000, &mut StdRng::seed_from_u64(42));
+        let repeated = state.sample(1000, &mut StdRng::seed_from_u64(42));
+        assert_eq!(samples, repeated);
+
+        // only |00> and |11> can occur, each in about half of the shots
+        assert!(samples.iter().all(|outcome| *outcome == 0 || *outcome == 3));
+        let zeros = samples.iter().filter(|outcome| **outcome == 0).count();
+        assert!(zeros > 400 && zeros < 600);
+    }
+
+    #[test]
+    fn measure_qubit_test() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..10 {
+            let mut state = QuantumState::new(2);
+            state.apply_gate(&hadamard(), &[0]).unwrap();
+            state.apply_gate(&cnot(), &[0, 1]).unwrap();
+            assert_approx_eq!(0.5f32, state.qubit_probability(1).unwrap());
+
+            // measuring one half of a bell pair determines the other
+            let outcome = state.measure_qubit(0, &mut rng).unwrap();
+            let expected = if outcome { 1.0f32 } else { 0.0f32 };
+            assert_approx_eq!(expected, state.qubit_probability(1).unwrap());
+            assert_approx_eq!(1.0f32, state.amplitudes().norm_l2());
+        }
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    fn assert_hermitian(matrix: &Matrix) {
+        let mut adjoint = matrix.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(adjoint, matrix.clone());
+    }
+
+    #[test]
+    fn seed_determinism_test() {
+        let first = Matrix::random_gaussian(3, 4, 17);
+        let second = Matrix::random_gaussian(3, 4, 17);
+        let other = Matrix::random_gaussian(3, 4, 18);
+
+        assert_complex_matrix(first.clone(), second);
+        let differs = (0..3).any(|row_index| {
+            (0..4).any(|column_index| {
+                let x = first.get_element(row_index, column_index).unwrap();
+                let y = other.get_element(row_index, column_index).unwrap();
+                x.subtraction(&y).norm() > 1e-3
+            })
+        });
+        assert!(differs);
+
+        assert_complex_vec(Vector::random_unit(5, 3), Vector::random_unit(5, 3));
+        assert_complex_matrix(
+            Matrix::random_unitary(4, 9).unwrap(),
+            Matrix::random_unitary(4, 9).unwrap(),
+        );
+    }
+
+    #[test]
+    fn gaussian_moments_test() {
+        // mean 0 and E|z|^2 = 1
+        let matrix = Matrix::random_gaussian(40, 50, 5);
+        let mut mean = Complex::new();
+        let mut second_moment: f32 = 0.0;
+        for row_index in 0..40 {
+            for column_index in 0..50 {
+                let element = matrix.get_element(row_index, column_index).unwrap();
+                second_moment += f32::powi(element.norm(), 2);
+                mean = mean.addition(&element);
+            }
+        }
+        mean.scale(1.0 / 2000.0);
+        assert!(mean.norm() < 0.1);
+        assert_approx_eq!(1.0f32, second_moment / 2000.0, 0.1);
+    }
+
+    #[test]
+    fn random_unitary_test() {
+        let unitary = Matrix::random_unitary(5, 11).unwrap();
+        let mut adjoint = unitary.clone();
+        adjoint.adjoint();
+        assert_complex_matrix(Matrix::identity(5), unitary.multiply(&adjoint).unwrap());
+        assert_complex_matrix(Matrix::identity(5), adjoint.multiply(&unitary).unwrap());
+    }
+
+    #[test]
+    fn random_gue_goe_test() {
+        let gue = Matrix::random_gue(4, 1);
+        assert_hermitian(&gue);
+
+        let goe = Matrix::random_goe(4, 1);
+        assert_hermitian(&goe);
+        for row_index in 0..4 {
+            for column_index in 0..4 {
+                assert_eq!(0.0, goe.get_element(row_index, column_index).unwrap().imaginary);
+            }
+        }
+    }
+
+    #[test]
+    fn random_wishart_test() {
+        let wishart = Matrix::random_wishart(3, 6, 2).unwrap();
+        assert_eq!(3, wishart.num_rows);
+        assert_eq!(3, wishart.num_columns);
+        assert_hermitian(&wishart);
+        for eigenvalue in wishart.calculate_eigenvalues().unwrap() {
+            assert!(eigenvalue.real > 0.0);
+        }
+    }
+
+    #[test]
+    fn random_positive_definite_test() {
+        let matrix = Matrix::random_positive_definite(4, 8).unwrap();
+        assert_hermitian(&matrix);
+        for eigenvalue in matrix.calculate_eigenvalues().unwrap() {
+            assert!(eigenvalue.real >= 1.0 - 1e-4);
+        }
+    }
+
+    #[test]
+    fn random_unit_test() {
+        let vector = Vector::random_unit(7, 21);
+        assert_eq!(7, vector.size());
+        assert_approx_eq!(1.0f32, vector.norm_l2());
+    }
+}
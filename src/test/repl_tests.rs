@@ -0,0 +1,61 @@
+// Unit test module for the calculator session
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+
+    use crate::expression::*;
+    use crate::repl::*;
+
+    #[test]
+    fn assignment_and_ans() {
+        let mut session = Session::new();
+        let output = session.execute("a = [1 2; 3 4]").unwrap().unwrap();
+        assert!(output.starts_with("a = \n"));
+        session.execute("a * a").unwrap();
+        assert_eq!(Some((2, 2)), session.variable("ans").map(Value::shape));
+    }
+
+    #[test]
+    fn comments_and_blank_lines() {
+        let mut session = Session::new();
+        assert_eq!(Ok(None), session.execute("   "));
+        assert_eq!(Ok(None), session.execute("# a comment"));
+        assert!(session.history().is_empty());
+    }
+
+    #[test]
+    fn errors_keep_the_session() {
+        let mut session = Session::new();
+        session.execute("x = 2").unwrap();
+        let error = session.execute("x + y").unwrap_err();
+        assert!(error.contains("y"));
+        assert!(session.variable("x").is_some());
+        assert_eq!(2, session.history().len());
+    }
+
+    #[test]
+    fn vars_and_clear() {
+        let mut session = Session::new();
+        session.execute("v = [1; 2; 3]").unwrap();
+        session.execute("m = eye(2)").unwrap();
+        let listing = session.execute("vars").unwrap().unwrap();
+        assert_eq!("m: 2x2 matrix\nv: column vector of size 3", listing);
+        session.execute("clear v").unwrap();
+        assert!(session.variable("v").is_none());
+        assert!(session.execute("clear v").is_err());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join("linalg_repl_test.csv");
+        let path = path.to_str().unwrap();
+        let mut session = Session::new();
+        session.execute("m = [1 2i; 3 4]").unwrap();
+        session.execute(&format!("save m {}", path)).unwrap();
+        let output = session.execute(&format!("load n {}", path)).unwrap().unwrap();
+        assert_eq!("n: 2x2 matrix", output);
+        std::fs::remove_file(path).unwrap();
+        assert!(session.execute("load n missing.csv").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+// Unit test module for serialization, only built with the serde feature
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    #[test]
+    fn complex_test() {
+        let json = serde_json::to_string(&comp!(1.5f32, -2.0f32)).unwrap();
+        assert_eq!(json, r#"{"real":1.5,"imaginary":-2.0}"#);
+
+        let num: Complex<f64> = serde_json::from_str(r#"{"real":0.5,"imaginary":3.0}"#).unwrap();
+        assert_eq!((num.real, num.imaginary), (0.5, 3.0));
+    }
+
+    #[test]
+    fn vector_round_trip_test() {
+        let mut vector = Vector::from_array(&[comp!(1.0, 2.0), comp!(-3.0, 0.5)]);
+        vector.transpose();
+        let json = serde_json::to_string(&vector).unwrap();
+        assert!(json.contains(r#""vector_type":"ROW_VECTOR""#));
+
+        let result: Vector = serde_json::from_str(&json).unwrap();
+        assert_eq!(result.size(), 2);
+        assert_eq!(result.get_type(), VectorType::ROW_VECTOR);
+        assert_complex_vec(vector, result);
+    }
+
+    #[test]
+    fn matrix_round_trip_test() {
+        let matrix = Matrix::from_array(
+            2,
+            3,
+            &[
+                comp!(1.0, 2.0),
+                comp!(0.0, 0.0),
+                comp!(-3.5, 0.0),
+                comp!(0.0, 0.25),
+                comp!(7.0, -1.0),
+                comp!(0.5, 0.0)
+            ])
+            .unwrap();
+        let json = serde_json::to_string(&matrix).unwrap();
+        let result: Matrix = serde_json::from_str(&json).unwrap();
+        assert_eq!((result.num_rows, result.num_columns), (2, 3));
+        assert_complex_matrix(matrix, result);
+    }
+
+    #[test]
+    fn matrix_dimension_error_test() {
+        let element = r#"{"real":1.0,"imaginary":0.0}"#;
+        let ragged = format!(
+            r#"{{"num_rows":2,"num_columns":2,"elements":[[{0},{0}],[{0}]]}}"#,
+            element
+        );
+        let error = serde_json::from_str::<Matrix>(&ragged).err().unwrap();
+        assert!(error.to_string().contains("Length of every row has to match num_columns"));
+
+        let missing_row = format!(r#"{{"num_rows":2,"num_columns":1,"elements":[[{}]]}}"#, element);
+        assert!(serde_json::from_str::<Matrix>(&missing_row).is_err());
+    }
+}
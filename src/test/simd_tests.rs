@@ -0,0 +1,136 @@
+// Unit test module for the SIMD kernels
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::gemm::{MR, NR};
+    use crate::matrix::*;
+    use crate::simd::*;
+    use crate::test::util::*;
+    use crate::vector::*;
+
+    // lengths below, at and above every register width
+    const LENGTHS: [usize; 12] = [0, 1, 2, 3, 4, 5, 7, 8, 9, 16, 17, 37];
+
+    fn random_numbers(length: usize, seed: u64) -> Vec<Complex<FloatType>> {
+        Matrix::random_gaussian(1, length, seed).row(0).to_vec()
+    }
+
+    fn assert_close(expected: &[Complex<FloatType>], actual: &[Complex<FloatType>]) {
+        assert_eq!(expected.len(), actual.len());
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert_approx_eq!(expected.real, actual.real, 1e-4);
+            assert_approx_eq!(expected.imaginary, actual.imaginary, 1e-4);
+        }
+    }
+
+    #[test]
+    fn detection() {
+        let levels = SimdLevel::available();
+        assert_eq!(levels[0], SimdLevel::Scalar);
+        assert_eq!(*levels.last().unwrap(), SimdLevel::detect());
+    }
+
+    #[test]
+    fn dot_matches_scalar() {
+        for level in SimdLevel::available() {
+            for length in LENGTHS {
+                let x = random_numbers(length, 1);
+                let y = random_numbers(length, 2);
+                for conjugate in [false, true] {
+                    let expected = dot_with(SimdLevel::Scalar, &x, &y, conjugate);
+                    let actual = dot_with(level, &x, &y, conjugate);
+                    assert_close(&[expected], &[actual]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dot_of_known_vectors() {
+        let x = [comp!(1.0, 2.0), comp!(0.0, -1.0), comp!(3.0, 0.0)];
+        let y = [comp!(2.0, 0.0), comp!(1.0, 1.0), comp!(-1.0, 2.0)];
+        // (2 + 4i) + (1 - i) + (-3 + 6i)
+        assert_close(&[dot(&x, &y)], &[comp!(0.0, 9.0)]);
+        // (2 - 4i) + (-1 + i) + (-3 + 6i)
+        assert_close(&[dot_conjugate(&x, &y)], &[comp!(-2.0, 3.0)]);
+    }
+
+    #[test]
+    fn axpy_matches_scalar() {
+        let alpha = comp!(0.5, -1.5);
+        for level in SimdLevel::available() {
+            for length in LENGTHS {
+                let x = random_numbers(length, 3);
+                let mut expected = random_numbers(length, 4);
+                let mut actual = expected.clone();
+                axpy_with(SimdLevel::Scalar, &alpha, &x, &mut expected);
+                axpy_with(level, &alpha, &x, &mut actual);
+                assert_close(&expected, &actual);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_matches_scalar() {
+        let alpha = comp!(-2.0, 0.25);
+        for level in SimdLevel::available() {
+            for length in LENGTHS {
+                let mut expected = random_numbers(length, 5);
+                let mut actual = expected.clone();
+                scale_with(SimdLevel::Scalar, &alpha, &mut expected);
+                scale_with(level, &alpha, &mut actual);
+                assert_close(&expected, &actual);
+            }
+        }
+    }
+
+    #[test]
+    fn micro_kernel_matches_scalar() {
+        for level in SimdLevel::available() {
+            for depth in [0, 1, 3, 16, 33] {
+                let a = random_numbers(MR * depth, 6);
+                let b = random_numbers(NR * depth, 7);
+                let a_real: Vec<_> = a.iter().map(|x| x.real).collect();
+                let a_imaginary: Vec<_> = a.iter().map(|x| x.imaginary).collect();
+                let b_real: Vec<_> = b.iter().map(|x| x.real).collect();
+                let b_imaginary: Vec<_> = b.iter().map(|x| x.imaginary).collect();
+
+                let expected = micro_kernel(SimdLevel::Scalar, &a_real, &a_imaginary, &b_real, &b_imaginary);
+                let actual = micro_kernel(level, &a_real, &a_imaginary, &b_real, &b_imaginary);
+                for i in 0..MR {
+                    for j in 0..NR {
+                        assert_approx_eq!(expected.0[i][j], actual.0[i][j], 1e-4);
+                        assert_approx_eq!(expected.1[i][j], actual.1[i][j], 1e-4);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn micro_kernel_3m_matches_complex_kernel() {
+        for level in SimdLevel::available() {
+            for depth in [0, 1, 5, 33] {
+                let a = random_numbers(MR * depth, 8);
+                let b = random_numbers(NR * depth, 9);
+                let split = |numbers: &[Complex<FloatType>]| {
+                    let real: Vec<_> = numbers.iter().map(|x| x.real).collect();
+                    let imaginary: Vec<_> = numbers.iter().map(|x| x.imaginary).collect();
+                    let sum: Vec<_> = numbers.iter().map(|x| x.real + x.imaginary).collect();
+                    (real, imaginary, sum)
+                };
+                let (a, b) = (split(&a), split(&b));
+
+                let expected = micro_kernel(SimdLevel::Scalar, &a.0, &a.1, &b.0, &b.1);
+                let actual = micro_kernel_3m(level, (&a.0, &a.1, &a.2), (&b.0, &b.1, &b.2));
+                for i in 0..MR {
+                    for j in 0..NR {
+                        assert_approx_eq!(expected.0[i][j], actual.0[i][j], 1e-4);
+                        assert_approx_eq!(expected.1[i][j], actual.1[i][j], 1e-4);
+                    }
+                }
+            }
+        }
+    }
+}
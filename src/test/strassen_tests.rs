@@ -0,0 +1,85 @@
+// Unit test module for the fast multiplication algorithms
+#[cfg(test)]
+mod tests {
+
+    use crate::complex::*;
+    use crate::matrix::*;
+    use crate::strassen::*;
+    use crate::vector::*;
+
+    fn relative_error(expected: &Matrix, actual: &Matrix) -> FloatType {
+        let mut difference = actual.clone();
+        difference.sub_assign(expected).unwrap();
+        difference.norm_frobenius() / expected.norm_frobenius()
+    }
+
+    fn error_bound(a: &Matrix, b: &Matrix, factor: FloatType) -> FloatType {
+        // norm wise bound factor u ||A|| ||B|| relative to ||A B||
+        let product = a.multiply(b).unwrap();
+        factor * FloatType::EPSILON * a.norm_frobenius() * b.norm_frobenius() / product.norm_frobenius()
+    }
+
+    #[test]
+    fn blocked_is_default() {
+        let a = Matrix::random_gaussian(5, 4, 1);
+        let b = Matrix::random_gaussian(4, 3, 2);
+        assert_eq!(Algorithm::default(), Algorithm::Blocked);
+        let expected = a.multiply(&b).unwrap();
+        let actual = a.multiply_with(&b, Algorithm::default()).unwrap();
+        assert_eq!(relative_error(&expected, &actual), 0.0);
+        assert!(a.multiply_with(&a, Algorithm::strassen()).is_err());
+    }
+
+    #[test]
+    fn strassen_winograd_matches_classical() {
+        // small cutoffs force several levels of recursion
+        for size in [16, 64, 96] {
+            let a = Matrix::random_gaussian(size, size, 3);
+            let b = Matrix::random_gaussian(size, size, 4);
+            let expected = a.multiply(&b).unwrap();
+            for cutoff in [1, 4, 16] {
+                let algorithm = Algorithm::StrassenWinograd { cutoff, three_m: false };
+                let actual = a.multiply_with(&b, algorithm).unwrap();
+                let levels = (size as FloatType / cutoff as FloatType).log2().ceil().max(0.0);
+                let factor = FloatType::powf(18.0, levels) * (cutoff * cutoff + 6 * cutoff) as FloatType;
+                assert!(relative_error(&expected, &actual) <= error_bound(&a, &b, factor));
+            }
+        }
+    }
+
+    #[test]
+    fn odd_and_rectangular_sizes() {
+        for (m, k, n) in [(7, 7, 7), (37, 23, 51), (50, 33, 2), (1, 40, 40), (40, 1, 40)] {
+            let a = Matrix::random_gaussian(m, k, 5);
+            let b = Matrix::random_gaussian(k, n, 6);
+            let expected = a.multiply(&b).unwrap();
+            for three_m in [false, true] {
+                let algorithm = Algorithm::StrassenWinograd { cutoff: 3, three_m };
+                let actual = a.multiply_with(&b, algorithm).unwrap();
+                assert_eq!((m, n), (actual.num_rows, actual.num_columns));
+                assert!(relative_error(&expected, &actual) < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn three_m_matches_classical() {
+        for (m, k, n) in [(3, 5, 2), (33, 17, 41), (130, 300, 9)] {
+            let a = Matrix::random_gaussian(m, k, 7);
+            let b = Matrix::random_gaussian(k, n, 8);
+            let expected = a.multiply(&b).unwrap();
+            let actual = a.multiply_with(&b, Algorithm::ThreeM).unwrap();
+            // the bound on the imaginary part with ||Ar|| + ||Ai|| <= sqrt(2) ||A||
+            assert!(relative_error(&expected, &actual) <= error_bound(&a, &b, 2.0 * (k + 4) as FloatType));
+        }
+    }
+
+    #[test]
+    fn three_m_on_real_products() {
+        // real operands have vanishing imaginary parts in all three products
+        let a = Matrix::from_fn(3, 3, |row, column| Complex::from((row + 2 * column) as FloatType, 0.0));
+        let b = Matrix::identity(3);
+        let actual = a.multiply_with(&b, Algorithm::ThreeM).unwrap();
+        assert_eq!(relative_error(&a, &actual), 0.0);
+    }
+}
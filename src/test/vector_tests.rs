@@ -162,6 +162,82 @@ mod tests {
         assert_complex(expected, actual);
     }
 
+    #[test]
+    fn inner_product_size_mismatch_test() {
+        let vec1 = Vector::ones(3);
+        let vec2 = Vector::ones(2);
+        assert!(vec1.inner_product(&vec2).is_err());
+        assert!(vec2.inner_product(&vec1).is_err());
+    }
+
+    #[test]
+    fn kron_vector_test() {
+        let vec1 = Vector::from_array(&[
+            Complex::from(1.0f32, 0.0f32),
+            Complex::from(0.0f32, 2.0f32),
+        ]);
+
+        let vec2 = Vector::from_array(&[
+            Complex::from(3.0f32, 0.0f32),
+            Complex::from(1.0f32, -1.0f32),
+            Complex::from(0.0f32, 0.0f32),
+        ]);
+
+        let expected = Vector::from_array(&[
+            Complex::from(3.0f32, 0.0f32),
+            Complex::from(1.0f32, -1.0f32),
+            Complex::from(0.0f32, 0.0f32),
+            Complex::from(0.0f32, 6.0f32),
+            Complex::from(2.0f32, 2.0f32),
+            Complex::from(0.0f32, 0.0f32),
+        ]);
+
+        let actual = vec1.kron_vector(&vec2).unwrap();
+        assert_eq!(6, actual.size());
+        assert_complex_vec(expected, actual);
+
+        let mut row_vec = vec2.clone();
+        row_vec.transpose();
+        assert!(vec1.kron_vector(&row_vec).is_err());
+    }
+
+    #[test]
+    fn ones_test() {
+        let vec = Vector::ones(3);
+        assert_eq!(3, vec.size());
+        vec.iter().for_each(|x| assert_complex(comp!(1.0, 0.0), x));
+    }
+
+    #[test]
+    fn basis_test() {
+        let expected = Vector::from_array(&[comp!(0.0, 0.0), comp!(1.0, 0.0), comp!(0.0, 0.0)]);
+        assert_complex_vec(expected, Vector::basis(3, 1).unwrap());
+        assert!(Vector::basis(3, 3).is_err());
+    }
+
+    #[test]
+    fn linspace_test() {
+        let expected = Vector::from_array(&[
+            comp!(-1.0, 0.0),
+            comp!(-0.5, 0.0),
+            comp!(0.0, 0.0),
+            comp!(0.5, 0.0),
+            comp!(1.0, 0.0),
+        ]);
+        let actual = Vector::linspace(-1.0, 1.0, 5);
+        assert_eq!(5, actual.size());
+        assert_complex_vec(expected, actual);
+
+        assert_complex_vec(Vector::from_array(&[comp!(2.0, 0.0)]), Vector::linspace(2.0, 3.0, 1));
+    }
+
+    #[test]
+    fn from_fn_test() {
+        let expected = Vector::from_array(&[comp!(0.0, 0.0), comp!(1.0, -1.0), comp!(4.0, -2.0)]);
+        let actual = Vector::from_fn(3, |index| comp!((index * index) as f32, -(index as f32)));
+        assert_complex_vec(expected, actual);
+    }
+
     #[test]
     fn complex_vec_macro_test() {
         let val1 = comp!(1.0f32, 2.0f32);
@@ -176,6 +252,46 @@ mod tests {
         assert_complex_vec(expected, actual);
     }
 
+    #[test]
+    fn display_test() {
+        let mut vector = Vector::from_array(&[
+            Complex::from(1.0f32, 0.0f32),
+            Complex::from(-2.5f32, 1.0f32),
+        ]);
+        assert_eq!(format!("{}", vector), "[   1 + 0i]\n[-2.5 + 1i]");
+
+        vector.transpose();
+        assert_eq!(format!("{:.1}", vector), "[ 1.0 + 0.0i, -2.5 + 1.0i]");
+    }
 
+    #[test]
+    fn display_truncation_test() {
+        let mut vector = Vector::linspace(0.0, 19.0, 20);
+        vector.transpose();
+        let rendered = format!("{}", vector);
+        assert!(rendered.starts_with("[ 0 + 0i,  1 + 0i,  2 + 0i,  3 + 0i,     ..., 16 + 0i"));
+        assert_eq!(rendered.matches('i').count(), 8);
+    }
 
+    #[test]
+    fn assign_variants_test() {
+        let first = Vector::from_array(&[comp!(1.0, 2.0), comp!(-3.0, 0.5)]);
+        let second = Vector::from_array(&[comp!(0.5, -1.0), comp!(2.0, 2.0)]);
+
+        let mut result = first.clone();
+        result.add_assign(&second).unwrap();
+        assert_complex_vec(first.add(&second).unwrap(), result.clone());
+
+        result.sub_assign(&second).unwrap();
+        assert_complex_vec(first.clone(), result.clone());
+
+        // (1 + 2i) + i (0.5 - i) and (-3 + 0.5i) + i (2 + 2i)
+        result.axpy(&comp!(0.0, 1.0), &second).unwrap();
+        assert_complex_vec(Vector::from_array(&[comp!(2.0, 2.5), comp!(-5.0, 2.5)]), result.clone());
+
+        let mut row = second.clone();
+        row.transpose();
+        assert!(result.add_assign(&row).is_err());
+        assert!(result.axpy(&comp!(1.0, 0.0), &Vector::zeros(3)).is_err());
+    }
 }
@@ -1,5 +1,9 @@
+use std::fmt;
+
 use crate::complex::*;
 use crate::matrix::*;
+use crate::parallel::*;
+use crate::simd;
 
 #[macro_export]
 // macro to create a complex vector element from an array of complex values
@@ -12,7 +16,13 @@ pub(crate) use vector;
 
 pub type FloatType = f32;
 
+// vectors and matrices with more entries along one dimension are
+// displayed with the leading and trailing DISPLAY_EDGE_ITEMS only
+pub(crate) const DISPLAY_MAX_ITEMS: usize = 10;
+pub(crate) const DISPLAY_EDGE_ITEMS: usize = 4;
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VectorType {
     ROW_VECTOR,
     COLUMN_VECTOR,
@@ -69,12 +79,38 @@ impl Vector {
 
     pub fn ones(size: usize) -> Self {
         let complex_vec: Vec<Complex<FloatType>> = (0..size)
-            .into_iter()
-            .map(|_| Complex::from(0.0f32, 0.0f32))
+            .map(|_| Complex::from(1.0f32, 0.0f32))
             .collect();
         Vector::from_vec(complex_vec)
     }
 
+    pub fn basis(size: usize, index: usize) -> Result<Self, &'static str> {
+        // unit vector e_index
+        if index >= size {
+            return Err("Index of basis vector has to be smaller than its size");
+        }
+        let mut result = Vector::zeros(size);
+        result.numbers[index] = Complex::from(1.0f32, 0.0f32);
+        Ok(result)
+    }
+
+    pub fn linspace(start: FloatType, end: FloatType, size: usize) -> Self {
+        // size evenly spaced real values from start to end, both included
+        let step = if size > 1 {
+            (end - start) / (size - 1) as FloatType
+        } else {
+            0.0
+        };
+        Vector::from_fn(size, |index| Complex::from(start + step * index as FloatType, 0.0))
+    }
+
+    pub fn from_fn<G>(size: usize, function: G) -> Self
+    where
+        G: Fn(usize) -> Complex<FloatType>,
+    {
+        Vector::from_vec((0..size).map(function).collect())
+    }
+
     pub fn get_type(&self) -> VectorType {
         self.vector_type.clone()
     }
@@ -100,48 +136,70 @@ impl Vector {
         self.size
     }
 
+    pub(crate) fn as_slice(&self) -> &[Complex<FloatType>] {
+        &self.numbers
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [Complex<FloatType>] {
+        &mut self.numbers
+    }
+
     pub fn normalize(&mut self) {
         self.scale(1.0 / self.norm_l2());
     }
 
     pub fn add(&self, other: &Self) -> Result<Self, &'static str> {
+        let mut result = self.clone();
+        result.add_assign(other)?;
+        Ok(result)
+    }
+
+    pub fn subtract(&self, other: &Self) -> Result<Self, &'static str> {
+        let mut result = self.clone();
+        result.sub_assign(other)?;
+        Ok(result)
+    }
+
+    fn check_same_shape(&self, other: &Self) -> Result<(), &'static str> {
         if self.vector_type != other.vector_type {
             Err("Adding vectors of differing types is not possible \n")
         } else if self.size != other.size {
-            Err("Adding vectors of differing types is not possible \n")
+            Err("Adding vectors of differing sizes is not possible \n")
         } else {
-            let mut result_vector = Vector::new();
-            for i in 0..self.size {
-                let first_num = self.numbers.get(i).unwrap();
-                let second_num = other.numbers.get(i).unwrap();
-                let elem = Complex::addition(first_num, second_num);
-
-                result_vector.numbers.insert(i, elem);
-            }
-            Ok(result_vector)
+            Ok(())
         }
     }
 
-    pub fn subtract(&self, other: &Self) -> Result<Self, &'static str> {
-        let mut other_copy = other.clone();
-        other_copy.scale(-1.0);
-        self.add(&other_copy)
+    pub fn add_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        // self <- self + other without allocating
+        self.check_same_shape(other)?;
+        for_each_mut(&mut self.numbers, self.size, |index, x| *x = x.addition(&other.numbers[index]));
+        Ok(())
+    }
+
+    pub fn sub_assign(&mut self, other: &Self) -> Result<(), &'static str> {
+        // self <- self - other without allocating
+        self.check_same_shape(other)?;
+        for_each_mut(&mut self.numbers, self.size, |index, x| *x = x.subtraction(&other.numbers[index]));
+        Ok(())
+    }
+
+    pub fn axpy(&mut self, alpha: &Complex<FloatType>, other: &Self) -> Result<(), &'static str> {
+        // self <- alpha other + self without allocating
+        self.check_same_shape(other)?;
+        simd::axpy(alpha, &other.numbers, &mut self.numbers);
+        Ok(())
     }
 
     pub fn scale(&mut self, factor: FloatType) {
-        self.numbers.iter_mut().for_each(|x| x.scale(factor));
+        for_each_mut(&mut self.numbers, self.size, |_, x| x.scale(factor));
     }
 
     pub fn inner_product(&self, other: &Self) -> Result<Complex<FloatType>, &'static str> {
-        let mut result: Complex<FloatType> = Complex::new();
-        for i in 0..self.size {
-            let first_num = self.numbers.get(i).unwrap();
-            let second_num = other.numbers.get(i).unwrap();
-            let elem = Complex::multiplication(first_num, second_num);
-
-            result = result.addition(&elem);
+        if self.size != other.size {
+            return Err("Dimension of vectors have to match for inner product");
         }
-        Ok(result)
+        Ok(simd::dot(&self.numbers, &other.numbers))
     }
 
     pub fn outer_product(&self, other: &Self) -> Result<Matrix, &'static str> {
@@ -164,6 +222,22 @@ impl Vector {
         Ok(result)
     }
 
+    pub fn kron_vector(&self, other: &Self) -> Result<Self, &'static str> {
+        // kronecker product of two vectors of the same type,
+        // the result keeps that type
+        if self.vector_type != other.vector_type {
+            return Err("Kronecker product of vectors requires matching vector types");
+        }
+
+        let mut result = Vec::with_capacity(self.size * other.size);
+        for first_num in &self.numbers {
+            for second_num in &other.numbers {
+                result.push(Complex::multiplication(first_num, second_num));
+            }
+        }
+        Ok(Vector::from(&result, self.get_type()))
+    }
+
     pub fn norm_l2(&self) -> FloatType {
         let mut sum: FloatType = 0.0;
         for i in 0..self.size {
@@ -175,7 +249,7 @@ impl Vector {
 
     pub fn conjugate(&mut self) {
         // compute the conjugate for the vector
-        self.numbers.iter_mut().for_each(|x| x.conjugate());
+        for_each_mut(&mut self.numbers, self.size, |_, x| x.conjugate());
     }
 
     pub fn transpose(&mut self) {
@@ -200,6 +274,54 @@ impl Vector {
     }
 }
 
+pub(crate) fn display_indices(size: usize) -> Vec<Option<usize>> {
+    // indices to display, None marks the position of the ellipsis
+    if size <= DISPLAY_MAX_ITEMS {
+        (0..size).map(Some).collect()
+    } else {
+        (0..DISPLAY_EDGE_ITEMS)
+            .map(Some)
+            .chain(std::iter::once(None))
+            .chain(((size - DISPLAY_EDGE_ITEMS)..size).map(Some))
+            .collect()
+    }
+}
+
+impl fmt::Display for Vector {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // row vectors are displayed on one line, column vectors
+        // with one element per line
+        let cells: Vec<String> = display_indices(self.size)
+            .iter()
+            .map(|index| match index {
+                Some(index) => self.numbers[*index].format_with_precision(f.precision()),
+                None => String::from("..."),
+            })
+            .collect();
+        let width = cells
+            .iter()
+            .map(|cell| cell.chars().count())
+            .chain(f.width())
+            .max()
+            .unwrap_or(0);
+
+        match self.vector_type {
+            VectorType::ROW_VECTOR => {
+                let row: Vec<String> = cells.iter().map(|cell| format!("{:>width$}", cell)).collect();
+                write!(f, "[{}]", row.join(", "))
+            }
+            VectorType::COLUMN_VECTOR => {
+                if cells.is_empty() {
+                    return write!(f, "[]");
+                }
+                let column: Vec<String> = cells.iter().map(|cell| format!("[{:>width$}]", cell)).collect();
+                write!(f, "{}", column.join("\n"))
+            }
+        }
+    }
+}
+
 pub struct VectorIterator<'a> {
     vector: &'a Vector,
     index: usize,